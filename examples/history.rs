@@ -0,0 +1,23 @@
+use std::{env, error::Error};
+
+use dialoguer::{FileHistory, Input};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut history = FileHistory::open(env::temp_dir().join("dialoguer_history"))?;
+    history.max_entries(100);
+
+    println!("Use the arrow keys to go through earlier commands, type exit to quit");
+
+    loop {
+        let command: String = Input::new()
+            .with_prompt("Command")
+            .with_history(&mut history)
+            .interact_text()?;
+
+        if command == "exit" {
+            return Ok(());
+        }
+
+        println!("Running {}", command);
+    }
+}
@@ -0,0 +1,243 @@
+//! Provides the terminal interface of the prompts
+use std::io;
+
+use console::{Key, Term};
+
+/// Trait for the terminals prompts render to and read from.
+///
+/// It is implemented for [console::Term](https://docs.rs/console/latest/console/struct.Term.html),
+/// which is what the prompts use unless told otherwise, and for the
+/// in-memory [TestTerm](test/struct.TestTerm.html) of the
+/// [test](test/index.html) module.  The methods behave like their
+/// counterparts on `Term`.
+pub trait Backend: Sync {
+    /// Writes a string without a line break.
+    fn write_str(&self, s: &str) -> io::Result<()>;
+
+    /// Writes a string followed by a line break.
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.write_str(s)?;
+        self.write_str("\n")
+    }
+
+    /// Flushes buffered output.
+    fn flush(&self) -> io::Result<()>;
+
+    /// Reads a single key press.
+    fn read_key(&self) -> io::Result<Key>;
+
+    /// Reads a single character, where enter counts as `'\n'`.
+    fn read_char(&self) -> io::Result<char> {
+        loop {
+            match self.read_key()? {
+                Key::Char(chr) => return Ok(chr),
+                Key::Enter => return Ok('\n'),
+                Key::Unknown => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotConnected,
+                        "Not a terminal",
+                    ))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads a line without the trailing line break.
+    fn read_line(&self) -> io::Result<String>;
+
+    /// Reads a line which starts out as `initial`.
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String>;
+
+    /// Reads a line without echoing it.
+    fn read_secure_line(&self) -> io::Result<String>;
+
+    /// Checks whether the terminal is attended by a user.
+    fn is_term(&self) -> bool;
+
+    /// Returns the size of the terminal as rows and columns.
+    fn size(&self) -> (u16, u16);
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()>;
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()>;
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()>;
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()>;
+
+    /// Clears the current line and moves the cursor to its start.
+    fn clear_line(&self) -> io::Result<()>;
+
+    /// Clears the last `n` lines and moves the cursor to the start of the
+    /// first cleared line.
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.move_cursor_up(n)?;
+        for _ in 0..n {
+            self.clear_line()?;
+            self.move_cursor_down(1)?;
+        }
+        self.move_cursor_up(n)
+    }
+
+    /// Clears `n` characters before the cursor.
+    fn clear_chars(&self, n: usize) -> io::Result<()>;
+
+    /// Clears the line of the cursor from its start and everything below.
+    fn clear_to_end_of_screen(&self) -> io::Result<()>;
+
+    fn show_cursor(&self) -> io::Result<()>;
+
+    fn hide_cursor(&self) -> io::Result<()>;
+
+    /// Returns the console terminal behind this backend, if there is one.
+    ///
+    /// Work which needs to move the terminal to another thread, such as
+    /// reading keys for the async prompts, is only done for those.
+    fn as_term(&self) -> Option<&Term> {
+        None
+    }
+}
+
+impl Backend for Term {
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        Term::write_str(self, s)
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        Term::write_line(self, s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Term::flush(self)
+    }
+
+    fn read_key(&self) -> io::Result<Key> {
+        Term::read_key(self)
+    }
+
+    fn read_char(&self) -> io::Result<char> {
+        Term::read_char(self)
+    }
+
+    fn read_line(&self) -> io::Result<String> {
+        Term::read_line(self)
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        Term::read_line_initial_text(self, initial)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        Term::read_secure_line(self)
+    }
+
+    fn is_term(&self) -> bool {
+        Term::is_term(self)
+    }
+
+    fn size(&self) -> (u16, u16) {
+        Term::size(self)
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        Term::move_cursor_up(self, n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        Term::move_cursor_down(self, n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        Term::move_cursor_left(self, n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        Term::move_cursor_right(self, n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        Term::clear_line(self)
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        Term::clear_last_lines(self, n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        Term::clear_chars(self, n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        Term::clear_to_end_of_screen(self)
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        Term::show_cursor(self)
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        Term::hide_cursor(self)
+    }
+
+    fn as_term(&self) -> Option<&Term> {
+        Some(self)
+    }
+}
+
+/// Returns the size of `term` as rows and columns.
+///
+/// Terminals which are not attended by a user or report no size count as
+/// 24 rows and 80 columns, so layouts worked out from this never end up
+/// empty.  The prompts size their viewports with this as well.
+///
+/// ```rust
+/// use dialoguer::{console::Term, term_size, Sort};
+///
+/// let (rows, _) = term_size(&Term::stderr());
+/// let mut sort = Sort::new();
+/// sort.items(&["first", "second"]).max_length(rows as usize / 2);
+/// ```
+pub fn term_size(term: &dyn Backend) -> (u16, u16) {
+    match term.size() {
+        (rows, columns) if term.is_term() && rows > 0 && columns > 0 => (rows, columns),
+        _ => (24, 80),
+    }
+}
+
+/// Hides the cursor of a terminal until it is dropped.
+///
+/// Prompts hold one while they wait for keys, so the cursor comes back
+/// however they end, including early returns and panics in callbacks such
+/// as validators.  console only keeps the terminal in raw mode while it
+/// reads a key, so the cursor is all that needs restoring.
+pub(crate) struct HiddenCursor<'a> {
+    term: &'a dyn Backend,
+}
+
+impl<'a> HiddenCursor<'a> {
+    pub(crate) fn new(term: &'a dyn Backend) -> io::Result<HiddenCursor<'a>> {
+        term.hide_cursor()?;
+        Ok(HiddenCursor { term })
+    }
+}
+
+impl Drop for HiddenCursor<'_> {
+    fn drop(&mut self) {
+        // Errors cannot be reported from here, and failing to show the
+        // cursor should not turn into a panic while unwinding.
+        let _ = self.term.show_cursor().and_then(|_| self.term.flush());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestTerm;
+
+    #[test]
+    fn test_term_size() {
+        assert_eq!(term_size(&TestTerm::with_size(10, 40)), (10, 40));
+        assert_eq!(term_size(&TestTerm::with_size(0, 0)), (24, 80));
+    }
+}
@@ -0,0 +1,282 @@
+//! Provides completion for text inputs
+use std::{env, fs, path::PathBuf};
+
+/// Trait for the completion of input prompts.
+///
+/// [Input](struct.Input.html) asks for a completion when the user presses
+/// tab.  A generic implementation for `Fn(&str) -> Vec<String>` is provided,
+/// which returns the candidates and completes to their longest common
+/// prefix:
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::Input;
+///
+/// let branches = |input: &str| -> Vec<String> {
+///     ["main", "master", "feature"]
+///         .iter()
+///         .filter(|branch| branch.starts_with(input))
+///         .map(|branch| branch.to_string())
+///         .collect()
+/// };
+///
+/// let branch: String = Input::new()
+///     .with_prompt("Branch")
+///     .with_completion(&branches)
+///     .interact_text()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Completion {
+    /// Returns the completed input, if there is a completion.
+    fn get(&self, input: &str) -> Option<String>;
+
+    /// Returns all completions of `input`.
+    ///
+    /// Input prompts cycle through these if pressing tab again does not
+    /// complete any further.  The default only returns the result of
+    /// [get](#tymethod.get).
+    fn candidates(&self, input: &str) -> Vec<String> {
+        self.get(input).into_iter().collect()
+    }
+}
+
+impl<F: Fn(&str) -> Vec<String>> Completion for F {
+    fn get(&self, input: &str) -> Option<String> {
+        complete_common_prefix(input, &self(input))
+    }
+
+    fn candidates(&self, input: &str) -> Vec<String> {
+        self(input)
+    }
+}
+
+/// Completion against a fixed list of words.
+///
+/// The words are kept sorted, so that the ones starting with the input can
+/// be found like in a prefix tree.  Completing extends the input to the
+/// longest prefix shared by all matching words.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Input, StringListCompletion};
+///
+/// let completion = StringListCompletion::new(vec!["build", "bench", "check"]);
+///
+/// let command: String = Input::new()
+///     .with_prompt("Command")
+///     .with_completion(&completion)
+///     .interact_text()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct StringListCompletion {
+    words: Vec<String>,
+}
+
+impl StringListCompletion {
+    /// Creates a completion for `words`.
+    pub fn new<I, S>(words: I) -> StringListCompletion
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut words: Vec<String> = words.into_iter().map(Into::into).collect();
+        words.sort();
+        words.dedup();
+        StringListCompletion { words }
+    }
+
+    /// Returns the words which start with `input`.
+    fn matches(&self, input: &str) -> &[String] {
+        let start = self.words.partition_point(|word| word.as_str() < input);
+        let len = self.words[start..].partition_point(|word| word.starts_with(input));
+        &self.words[start..start + len]
+    }
+}
+
+impl Completion for StringListCompletion {
+    fn get(&self, input: &str) -> Option<String> {
+        complete_common_prefix(input, self.matches(input))
+    }
+
+    fn candidates(&self, input: &str) -> Vec<String> {
+        self.matches(input).to_vec()
+    }
+}
+
+/// Completion of file system paths.
+///
+/// The part after the last `/` is completed against the entries of the
+/// directory before it, and directories get a trailing `/`.  A leading
+/// `~` stands for the home directory.  Hidden files are only offered if
+/// the input starts them with a dot, unless
+/// [show_hidden](#method.show_hidden) is turned on.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Input, PathCompletion};
+///
+/// let completion = PathCompletion::new();
+///
+/// let path: String = Input::new()
+///     .with_prompt("Config file")
+///     .with_completion(&completion)
+///     .interact_text()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PathCompletion {
+    show_hidden: bool,
+}
+
+impl PathCompletion {
+    /// Creates a path completion.
+    pub fn new() -> PathCompletion {
+        PathCompletion::default()
+    }
+
+    /// Indicates whether hidden files are offered without a leading dot.
+    ///
+    /// The default is `false`.
+    pub fn show_hidden(&mut self, val: bool) -> &mut PathCompletion {
+        self.show_hidden = val;
+        self
+    }
+}
+
+impl Completion for PathCompletion {
+    fn get(&self, input: &str) -> Option<String> {
+        complete_common_prefix(input, &self.candidates(input))
+    }
+
+    fn candidates(&self, input: &str) -> Vec<String> {
+        let (dir, prefix) = match input.rfind('/') {
+            Some(idx) => input.split_at(idx + 1),
+            None if input == "~" => return vec!["~/".to_string()],
+            None => ("", input),
+        };
+        let show_hidden = self.show_hidden || prefix.starts_with('.');
+
+        let entries = match fs::read_dir(expand_home(dir)) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut candidates: Vec<String> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                if !name.starts_with(prefix) || (name.starts_with('.') && !show_hidden) {
+                    return None;
+                }
+
+                // Follows symlinks, so that links to directories count as such.
+                let is_dir = entry.path().is_dir();
+                Some(format!("{}{}{}", dir, name, if is_dir { "/" } else { "" }))
+            })
+            .collect();
+        candidates.sort();
+        candidates
+    }
+}
+
+/// Returns the directory to read for the directory part of an input.
+fn expand_home(dir: &str) -> PathBuf {
+    if dir.is_empty() {
+        return PathBuf::from(".");
+    }
+
+    match (dir.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(dir),
+    }
+}
+
+/// Completes `input` to the longest prefix shared by all `candidates`,
+/// if that is longer than the input.
+fn complete_common_prefix(input: &str, candidates: &[String]) -> Option<String> {
+    let first = candidates.first()?;
+
+    let mut prefix = first.as_str();
+    for word in &candidates[1..] {
+        let len = prefix
+            .char_indices()
+            .zip(word.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(prefix.len().min(word.len()), |((idx, _), _)| idx);
+        prefix = &prefix[..len];
+    }
+
+    if prefix.len() > input.len() {
+        Some(prefix.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_list_completion() {
+        let completion = StringListCompletion::new(vec!["build", "bench", "check", "bench"]);
+
+        assert_eq!(completion.get("c").as_deref(), Some("check"));
+        assert_eq!(completion.get("b"), None);
+        assert_eq!(completion.get("bu").as_deref(), Some("build"));
+        assert_eq!(completion.get("x"), None);
+        assert_eq!(completion.get("check"), None);
+        assert_eq!(completion.candidates("b"), ["bench", "build"]);
+        assert!(completion.candidates("x").is_empty());
+    }
+
+    #[test]
+    fn test_closure_completion() {
+        let completion = |input: &str| -> Vec<String> {
+            ["main", "master"]
+                .iter()
+                .filter(|word| word.starts_with(input))
+                .map(|word| word.to_string())
+                .collect()
+        };
+
+        assert_eq!(Completion::get(&completion, "m").as_deref(), Some("ma"));
+        assert_eq!(Completion::get(&completion, "mai").as_deref(), Some("main"));
+        assert_eq!(completion.candidates("ma"), ["main", "master"]);
+    }
+
+    #[test]
+    fn test_path_completion() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("setup.cfg"), "").unwrap();
+        fs::write(dir.path().join(".secret"), "").unwrap();
+        let base = format!("{}/", dir.path().display());
+
+        let mut completion = PathCompletion::new();
+        assert_eq!(
+            completion.candidates(&base),
+            [format!("{}setup.cfg", base), format!("{}src/", base)]
+        );
+        assert_eq!(completion.get(&format!("{}s", base)), None);
+        assert_eq!(
+            completion.get(&format!("{}sr", base)),
+            Some(format!("{}src/", base))
+        );
+        assert_eq!(
+            completion.get(&format!("{}.s", base)),
+            Some(format!("{}.secret", base))
+        );
+
+        completion.show_hidden(true);
+        assert_eq!(completion.candidates(&base).len(), 3);
+        assert_eq!(completion.candidates("~"), ["~/"]);
+    }
+}
@@ -1,11 +1,24 @@
 use std::{
     env,
     ffi::{OsStr, OsString},
-    fs, io,
-    io::{Read, Write},
+    fs,
+    future::{self, Future},
+    io,
+    io::Read,
+    mem,
+    path::{Path, PathBuf},
     process,
 };
 
+#[cfg(feature = "async")]
+use crate::reader::spawn_blocking;
+use crate::{error::Result, reader::block_on};
+
+type EditorValidator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// Prefix of the lines which report validation errors in the edited text.
+const ERROR_PREFIX: &str = "# error: ";
+
 /// Launches the default editor to edit a string.
 ///
 /// ## Example
@@ -24,18 +37,24 @@ use std::{
 /// ```
 pub struct Editor {
     editor: OsString,
+    args: Vec<String>,
+    is_fallback: bool,
+    envs: Vec<(OsString, OsString)>,
     extension: String,
     require_save: bool,
     trim_newlines: bool,
+    keep_file: bool,
+    comment_prefix: Option<String>,
+    validator: Option<EditorValidator>,
+    marker: Option<String>,
+}
+
+/// Returns the editor configured in the environment, if any.
+fn get_env_editor() -> Option<OsString> {
+    env::var_os("VISUAL").or_else(|| env::var_os("EDITOR"))
 }
 
 fn get_default_editor() -> OsString {
-    if let Some(prog) = env::var_os("VISUAL") {
-        return prog;
-    }
-    if let Some(prog) = env::var_os("EDITOR") {
-        return prog;
-    }
     if cfg!(windows) {
         "notepad.exe".into()
     } else {
@@ -43,6 +62,19 @@ fn get_default_editor() -> OsString {
     }
 }
 
+/// Splits an editor command like `code --wait` from the environment into
+/// the executable and its arguments.
+fn split_editor_command(cmd: OsString) -> (OsString, Vec<String>) {
+    match cmd.to_str() {
+        Some(s) if !Path::new(s).exists() && s.contains(char::is_whitespace) => {
+            let mut parts = s.split_whitespace().map(String::from);
+            let editor = parts.next().unwrap_or_default();
+            (editor.into(), parts.collect())
+        }
+        _ => (cmd, vec![]),
+    }
+}
+
 impl Default for Editor {
     fn default() -> Editor {
         Editor::new()
@@ -52,27 +84,120 @@ impl Default for Editor {
 impl Editor {
     /// Creates a new editor.
     pub fn new() -> Editor {
+        let env_editor = get_env_editor();
+        let is_fallback = env_editor.is_none();
+        let (editor, args) = split_editor_command(env_editor.unwrap_or_else(get_default_editor));
+
         Editor {
-            editor: get_default_editor(),
+            editor,
+            args,
+            is_fallback,
+            envs: vec![],
             extension: ".txt".into(),
             require_save: true,
             trim_newlines: true,
+            keep_file: false,
+            comment_prefix: None,
+            validator: None,
+            marker: None,
         }
     }
 
+    /// Returns the configured editor, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a editor that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::Editor;
+    ///
+    /// let editor = Editor::new().extension(".md").build();
+    /// ```
+    pub fn build(&mut self) -> Editor {
+        mem::take(self)
+    }
+
     /// Sets a specific editor executable.
+    ///
+    /// This overrides the editor from the `VISUAL` and `EDITOR` environment
+    /// variables, including any arguments given there.
     pub fn executable<S: AsRef<OsStr>>(&mut self, val: S) -> &mut Editor {
         self.editor = val.as_ref().into();
+        self.args.clear();
+        self.is_fallback = false;
+        self
+    }
+
+    /// Sets the editor used if neither `VISUAL` nor `EDITOR` is set.
+    ///
+    /// Like the environment variables this may contain arguments, such as
+    /// `"code --wait"`.  The default is `notepad.exe` on Windows and `vi`
+    /// elsewhere.  An editor set with [executable](#method.executable) is
+    /// kept.
+    pub fn fallback<S: AsRef<OsStr>>(&mut self, val: S) -> &mut Editor {
+        if self.is_fallback {
+            let (editor, args) = split_editor_command(val.as_ref().into());
+            self.editor = editor;
+            self.args = args;
+        }
         self
     }
 
-    /// Sets a specific extension
+    /// Sets the arguments for the editor.
+    ///
+    /// The path of the file to edit is passed after these.  As
+    /// [executable](#method.executable) resets the arguments, call this
+    /// afterwards.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Editor;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let text = Editor::new()
+    ///     .executable("code")
+    ///     .args(vec!["--wait".into()])
+    ///     .edit("Enter a commit message")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn args(&mut self, args: Vec<String>) -> &mut Editor {
+        self.args = args;
+        self
+    }
+
+    /// Sets an environment variable for the editor.
+    ///
+    /// Can be called multiple times to set several variables.  They only
+    /// apply to the editor process.
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(&mut self, key: K, value: V) -> &mut Editor {
+        self.envs
+            .push((key.as_ref().to_owned(), value.as_ref().to_owned()));
+        self
+    }
+
+    /// Sets the extension of the temporary file.
+    ///
+    /// Editors usually pick the syntax highlighting based on the extension,
+    /// so setting this to `".md"` or `".rs"` gives the user the matching
+    /// mode.  A missing leading dot is added.  The default is `".txt"`.
+    /// The temporary file is removed once editing is done, no matter how
+    /// the editor exited.
     pub fn extension(&mut self, val: &str) -> &mut Editor {
-        self.extension = val.into();
+        self.extension = if val.is_empty() || val.starts_with('.') {
+            val.into()
+        } else {
+            format!(".{}", val)
+        };
         self
     }
 
     /// Enables or disables the save requirement.
+    ///
+    /// When enabled, [edit](#method.edit) returns `None` if the editor
+    /// exited with an error or the text is unchanged, ignoring trailing
+    /// newlines.  This is on by default.
     pub fn require_save(&mut self, val: bool) -> &mut Editor {
         self.require_save = val;
         self
@@ -80,44 +205,530 @@ impl Editor {
 
     /// Enables or disables trailing newline stripping.
     ///
-    /// This is on by default.
+    /// Editors often add a newline at the end of the file.  When enabled,
+    /// trailing `\n` and `\r` characters are removed from the returned
+    /// text.  When disabled, the text is returned exactly as the editor
+    /// saved it.  This is on by default.
+    ///
+    /// The check for unchanged text done by
+    /// [require_save](#method.require_save) always ignores trailing
+    /// newlines, independent of this setting, so an editor adding a final
+    /// newline does not count as a change.
     pub fn trim_newlines(&mut self, val: bool) -> &mut Editor {
         self.trim_newlines = val;
         self
     }
 
+    /// Sets a prefix for comment lines.
+    ///
+    /// Lines starting with the prefix are removed from the returned text,
+    /// which allows explaining the task to the user in the text itself, like
+    /// git does for commit messages when using `"#"`.  If nothing but
+    /// comments and whitespace is left, [edit](#method.edit) returns `None`.
+    /// Validators only see the text without comments.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Editor;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let message = Editor::new()
+    ///     .comment_prefix("#")
+    ///     .edit("\n# Please enter the commit message for your changes.")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn comment_prefix(&mut self, prefix: &str) -> &mut Editor {
+        self.comment_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Enables or disables keeping the temporary file.
+    ///
+    /// By default the temporary file is deleted once editing is done.  When
+    /// enabled the file is left in place, still containing the text as the
+    /// editor saved it, and the caller becomes responsible for deleting it.
+    /// Use [edit_with_path](#method.edit_with_path) to learn where it is.
+    pub fn keep_file(&mut self, val: bool) -> &mut Editor {
+        self.keep_file = val;
+        self
+    }
+
+    /// Registers a validator for the edited text.
+    ///
+    /// If the validator fails, the editor is opened again with the error
+    /// added as `# error:` lines at the top, which are removed before the
+    /// text is validated again.  This repeats until the text is valid or
+    /// the user empties it, in which case [edit](#method.edit) returns
+    /// `None`.  With [require_save](#method.require_save) closing the
+    /// editor without changes also returns `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Editor;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let text = Editor::new()
+    ///     .validate_with(|text| {
+    ///         if text.lines().count() > 1 {
+    ///             Ok(())
+    ///         } else {
+    ///             Err("Add a description below the summary".to_string())
+    ///         }
+    ///     })
+    ///     .edit("Summary")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate_with<F>(&mut self, validator: F) -> &mut Editor
+    where
+        F: Fn(&str) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Sets a marker for the initial cursor position.
+    ///
+    /// The first occurrence of the marker in the text passed to
+    /// [edit](#method.edit) is removed and, for editors known to support
+    /// it, the editor is told to open the file at the line of the marker.
+    /// This works for vi, vim, nvim, nano, emacs, kak and VS Code.  Other
+    /// editors open the file as usual.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Editor;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let text = Editor::new()
+    ///     .goto_marker("<cursor>")
+    ///     .edit("# Describe the change below\n<cursor>\n")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn goto_marker(&mut self, marker: &str) -> &mut Editor {
+        self.marker = Some(marker.into());
+        self
+    }
+
     /// Launches the editor to edit a string.
     ///
-    /// Returns `None` if the file was not saved or otherwise the
-    /// entered text.
-    pub fn edit(&self, s: &str) -> io::Result<Option<String>> {
-        let mut f = tempfile::Builder::new()
+    /// Returns `None` if the text was not changed, see
+    /// [require_save](#method.require_save), or otherwise the entered text.
+    pub fn edit(&self, s: &str) -> Result<Option<String>> {
+        Ok(self.edit_with_path(s)?.0)
+    }
+
+    /// Like [edit](#method.edit) but also returns the path of the temporary file.
+    ///
+    /// The file only still exists if [keep_file](#method.keep_file) is
+    /// enabled, otherwise it is deleted before this returns.  A kept file
+    /// must be deleted by the caller.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Editor;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let (text, path) = Editor::new()
+    ///     .extension(".rs")
+    ///     .keep_file(true)
+    ///     .edit_with_path("fn main() {}")?;
+    /// // e.g. run a formatter on `path` here
+    /// std::fs::remove_file(path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn edit_with_path(&self, s: &str) -> Result<(Option<String>, PathBuf)> {
+        block_on(self._edit(s, |mut child| future::ready(child.wait())))
+    }
+
+    /// Async variant of [edit](#method.edit).
+    ///
+    /// The editor is waited for on a helper thread, so the executor is not
+    /// blocked while the user edits.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn edit_async(&self, s: &str) -> Result<Option<String>> {
+        let (rv, _) = self
+            ._edit(s, |mut child| spawn_blocking(move || child.wait()))
+            .await?;
+        Ok(rv)
+    }
+
+    /// Edits `s`, using `wait` to wait for the editor to exit.
+    async fn _edit<W, F>(&self, s: &str, wait: W) -> Result<(Option<String>, PathBuf)>
+    where
+        W: Fn(process::Child) -> F,
+        F: Future<Output = io::Result<process::ExitStatus>>,
+    {
+        let file = tempfile::Builder::new()
             .prefix("edit-")
             .suffix(&self.extension)
             .rand_bytes(12)
             .tempfile()?;
-        f.write_all(s.as_bytes())?;
-        f.flush()?;
-        let ts = fs::metadata(f.path())?.modified()?;
+        let rv = self.edit_file(file.path(), s, wait).await;
+
+        // on errors the caller never learns the path, so the file is not kept
+        let path = if self.keep_file && rv.is_ok() {
+            file.into_temp_path().keep().map_err(|err| err.error)?
+        } else {
+            file.path().to_path_buf()
+        };
+
+        Ok((rv?, path))
+    }
 
-        let rv = process::Command::new(&self.editor)
-            .arg(f.path())
-            .spawn()?
-            .wait()?;
+    async fn edit_file<W, F>(&self, path: &Path, s: &str, wait: W) -> Result<Option<String>>
+    where
+        W: Fn(process::Child) -> F,
+        F: Future<Output = io::Result<process::ExitStatus>>,
+    {
+        let (mut text, mut line) = match self.marker {
+            Some(ref marker) => remove_marker(s, marker),
+            None => (s.to_string(), None),
+        };
+        let validator = match self.validator {
+            Some(ref validator) => validator,
+            None => {
+                let rv = self.run_editor(path, &text, line, &wait).await?;
+                return Ok(rv.and_then(|rv| self.finish(rv)));
+            }
+        };
 
-        if rv.success() && self.require_save && ts >= fs::metadata(f.path())?.modified()? {
+        loop {
+            // only the first run starts at the marker, later ones show errors at the top
+            let rv = match self.run_editor(path, &text, line.take(), &wait).await? {
+                Some(rv) => strip_errors(&rv),
+                None => return Ok(None),
+            };
+            let content = self.strip_comments(&rv);
+
+            if content.trim().is_empty() {
+                return Ok(None);
+            }
+
+            match validator(&content) {
+                Ok(()) => return Ok(self.finish(content)),
+                Err(err) => {
+                    text = err
+                        .lines()
+                        .map(|line| format!("{}{}\n", ERROR_PREFIX, line))
+                        .chain(Some(rv))
+                        .collect();
+                }
+            }
+        }
+    }
+
+    /// Writes `s` to `path` and runs the editor on it once.
+    async fn run_editor<W, F>(
+        &self,
+        path: &Path,
+        s: &str,
+        line: Option<usize>,
+        wait: &W,
+    ) -> Result<Option<String>>
+    where
+        W: Fn(process::Child) -> F,
+        F: Future<Output = io::Result<process::ExitStatus>>,
+    {
+        fs::write(path, s)?;
+
+        let child = process::Command::new(&self.editor)
+            .args(&self.args)
+            .args(file_args(&self.editor, path, line))
+            .envs(self.envs.iter().map(|(key, value)| (key, value)))
+            .spawn()
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("editor {:?} not found", self.editor),
+                ),
+                _ => err,
+            })?;
+        let rv = wait(child).await?;
+
+        if self.require_save && !rv.success() {
             return Ok(None);
         }
 
-        let mut new_f = fs::File::open(f.path())?;
+        let mut new_f = fs::File::open(path)?;
         let mut rv = String::new();
         new_f.read_to_string(&mut rv)?;
 
+        if self.require_save && trim_newlines(&rv) == trim_newlines(s) {
+            return Ok(None);
+        }
+
+        Ok(Some(rv))
+    }
+
+    /// Applies the post processing to the edited text.
+    ///
+    /// Returns `None` if only comments were left.
+    fn finish(&self, rv: String) -> Option<String> {
+        let mut rv = match self.comment_prefix {
+            Some(_) => self.strip_comments(&rv),
+            None => rv,
+        };
+
+        if self.comment_prefix.is_some() && rv.trim().is_empty() {
+            return None;
+        }
+
         if self.trim_newlines {
-            let len = rv.trim_end_matches(&['\n', '\r'][..]).len();
+            let len = trim_newlines(&rv).len();
             rv.truncate(len);
         }
 
-        Ok(Some(rv))
+        Some(rv)
+    }
+
+    /// Removes the comment lines.
+    fn strip_comments(&self, s: &str) -> String {
+        match self.comment_prefix {
+            Some(ref prefix) => s
+                .split_inclusive('\n')
+                .filter(|line| !line.starts_with(prefix.as_str()))
+                .collect(),
+            None => s.to_string(),
+        }
+    }
+}
+
+/// Removes the first occurrence of `marker` and returns the line it was on.
+fn remove_marker(s: &str, marker: &str) -> (String, Option<usize>) {
+    match s.find(marker) {
+        Some(idx) => (
+            s.replacen(marker, "", 1),
+            Some(s[..idx].matches('\n').count() + 1),
+        ),
+        None => (s.to_string(), None),
+    }
+}
+
+/// Returns the arguments which open `path` at `line` in `editor`.
+fn file_args(editor: &OsStr, path: &Path, line: Option<usize>) -> Vec<OsString> {
+    let name = Path::new(editor).file_stem().and_then(OsStr::to_str);
+
+    match (line, name) {
+        (Some(line), Some("vi" | "vim" | "nvim" | "nano" | "emacs" | "kak")) => {
+            vec![format!("+{}", line).into(), path.into()]
+        }
+        (Some(line), Some("code" | "codium")) => {
+            let mut arg = path.as_os_str().to_owned();
+            arg.push(format!(":{}", line));
+            vec!["--goto".into(), arg]
+        }
+        _ => vec![path.into()],
+    }
+}
+
+/// Removes the lines which report validation errors.
+fn strip_errors(s: &str) -> String {
+    s.split_inclusive('\n')
+        .filter(|line| !line.starts_with(ERROR_PREFIX))
+        .collect()
+}
+
+fn trim_newlines(s: &str) -> &str {
+    s.trim_end_matches(&['\n', '\r'][..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn test_extension_leading_dot() {
+        assert_eq!(Editor::new().extension, ".txt");
+        assert_eq!(Editor::new().extension(".md").extension, ".md");
+        assert_eq!(Editor::new().extension("rs").extension, ".rs");
+    }
+
+    #[test]
+    fn test_split_editor_command() {
+        assert_eq!(
+            split_editor_command("code --wait".into()),
+            ("code".into(), vec!["--wait".to_string()])
+        );
+        assert_eq!(split_editor_command("vim".into()), ("vim".into(), vec![]));
+    }
+
+    #[test]
+    fn test_fallback() {
+        let mut editor = Editor::new();
+        editor.is_fallback = true;
+        editor.fallback("code --wait");
+        assert_eq!(editor.editor, "code");
+        assert_eq!(editor.args, vec!["--wait"]);
+
+        editor.executable("nano").fallback("vim");
+        assert_eq!(editor.editor, "nano");
+    }
+
+    #[test]
+    fn test_strip_errors() {
+        assert_eq!(
+            strip_errors("# error: bad\n# error: worse\nkey: 1\n"),
+            "key: 1\n"
+        );
+        assert_eq!(strip_errors("# comment\nkey: 1"), "# comment\nkey: 1");
+    }
+
+    #[test]
+    fn test_goto_marker() {
+        assert_eq!(
+            remove_marker("a\nb<>c\n", "<>"),
+            ("a\nbc\n".to_string(), Some(2))
+        );
+        assert_eq!(remove_marker("a", "<>"), ("a".to_string(), None));
+
+        let path = Path::new("/tmp/edit.txt");
+        assert_eq!(
+            file_args(OsStr::new("/usr/bin/vim"), path, Some(3)),
+            vec![OsString::from("+3"), path.into()]
+        );
+        assert_eq!(
+            file_args(OsStr::new("code"), path, Some(3)),
+            vec![OsString::from("--goto"), "/tmp/edit.txt:3".into()]
+        );
+        assert_eq!(
+            file_args(OsStr::new("ed"), path, Some(3)),
+            vec![OsString::from(path)]
+        );
+    }
+
+    #[test]
+    fn test_missing_editor() {
+        let err = Editor::new()
+            .executable("dialoguer-missing-editor")
+            .edit("")
+            .unwrap_err();
+        assert!(matches!(err, Error::Io(ref err) if err.kind() == io::ErrorKind::NotFound));
+        assert!(err.to_string().contains("dialoguer-missing-editor"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_require_save() {
+        let mut editor = Editor::new();
+        editor.executable("true");
+        assert_eq!(editor.edit("text\n").unwrap(), None);
+
+        editor.require_save(false);
+        assert_eq!(editor.edit("text\n").unwrap(), Some("text".into()));
+
+        editor.trim_newlines(false);
+        assert_eq!(editor.edit("text\r\n").unwrap(), Some("text\r\n".into()));
+        editor.trim_newlines(true);
+
+        editor.require_save(true).executable("false");
+        assert_eq!(editor.edit("text").unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "async"))]
+    fn test_edit_async() {
+        use std::{
+            pin::pin,
+            sync::Arc,
+            task::{Context, Poll, Wake},
+            thread::{self, Thread},
+        };
+
+        struct Unpark(Thread);
+
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+
+        let mut editor = Editor::new();
+        editor
+            .executable("sh")
+            .args(vec!["-c".into(), "sleep 0.1; echo more >> \"$0\"".into()]);
+
+        let mut future = pin!(editor.edit_async("text\n"));
+        let waker = Arc::new(Unpark(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+
+        let rv = loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(rv) => break rv,
+                Poll::Pending => thread::park(),
+            }
+        };
+
+        assert_eq!(rv.unwrap(), Some("text\nmore".into()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_keep_file() {
+        let mut editor = Editor::new();
+        editor.executable("true").require_save(false);
+
+        let (_, path) = editor.edit_with_path("text").unwrap();
+        assert!(!path.exists());
+
+        let (rv, path) = editor.keep_file(true).edit_with_path("text").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "text");
+        assert_eq!(rv, Some("text".into()));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_comment_prefix() {
+        let mut editor = Editor::new();
+        editor
+            .executable("true")
+            .require_save(false)
+            .comment_prefix("#");
+
+        assert_eq!(
+            editor.edit("Fix it\n# explain\n\nDetails\n").unwrap(),
+            Some("Fix it\n\nDetails".into())
+        );
+        assert_eq!(editor.edit("\n# explain\n").unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_env() {
+        let rv = Editor::new()
+            .executable("sh")
+            .args(vec!["-c".into(), "echo $A$B > \"$0\"".into()])
+            .env("A", "1")
+            .env("B", "2")
+            .edit("")
+            .unwrap();
+        assert_eq!(rv, Some("12".into()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_validate_reopens_editor() {
+        let mut editor = Editor::new();
+        editor
+            .executable("sh")
+            .args(vec!["-c".into(), "echo more >> \"$0\"".into()])
+            .validate_with(|text| match text.lines().count() {
+                3 => Ok(()),
+                _ => Err("too short".into()),
+            });
+
+        assert_eq!(
+            editor.edit("text\n").unwrap(),
+            Some("text\nmore\nmore".into())
+        );
     }
 }
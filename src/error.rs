@@ -0,0 +1,105 @@
+//! Provides the error type of the prompts
+use std::{error, fmt, io, result};
+
+/// Errors of the prompts.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading from or writing to the terminal failed.
+    Io(io::Error),
+    /// The user pressed Ctrl+C.
+    Interrupted,
+    /// The prompt needs a terminal, but input does not come from one.
+    NotATerminal,
+    /// The input was rejected more often than the prompt allows.
+    ///
+    /// Holds the error of the last rejected input.
+    ValidationExhausted(String),
+    /// The prompt was to be answered with its default, see
+    /// [set_assume_default](fn.set_assume_default.html), but it has none.
+    NoDefault,
+}
+
+/// Result of the prompts.
+pub type Result<T, E = Error> = result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "IO error: {}", err),
+            Error::Interrupted => f.write_str("interrupted by the user"),
+            Error::NotATerminal => f.write_str("not a terminal"),
+            Error::ValidationExhausted(err) => write!(f, "too many invalid inputs: {}", err),
+            Error::NoDefault => f.write_str("the prompt has no default to assume"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Turns Ctrl+C into a cancelled prompt, as the `interact_opt` methods
+/// report both 'Esc' and Ctrl+C as `None`.
+pub(crate) fn cancel_on_interrupt<T>(rv: Result<Option<T>>) -> Result<Option<T>> {
+    match rv {
+        Err(Error::Interrupted) => Ok(None),
+        rv => rv,
+    }
+}
+
+/// Maps errors of kind `Interrupted`, which the prompts report for Ctrl+C,
+/// to [Error::Interrupted](enum.Error.html#variant.Interrupted) and errors
+/// of kind `NotConnected`, which console reports without a terminal, to
+/// [Error::NotATerminal](enum.Error.html#variant.NotATerminal).
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        match err.kind() {
+            io::ErrorKind::Interrupted => Error::Interrupted,
+            io::ErrorKind::NotConnected => Error::NotATerminal,
+            _ => Error::Io(err),
+        }
+    }
+}
+
+/// Eases using prompts in functions which return `io::Result`.
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        match err {
+            Error::Io(err) => err,
+            Error::Interrupted => io::Error::new(io::ErrorKind::Interrupted, err.to_string()),
+            Error::NotATerminal => io::Error::new(io::ErrorKind::NotConnected, err.to_string()),
+            Error::ValidationExhausted(_) | Error::NoDefault => {
+                io::Error::new(io::ErrorKind::InvalidInput, err.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io_error() {
+        let err = io::Error::new(io::ErrorKind::Interrupted, "read interrupted");
+        assert!(matches!(Error::from(err), Error::Interrupted));
+
+        let err = io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe");
+        assert!(matches!(Error::from(err), Error::Io(_)));
+    }
+
+    #[test]
+    fn test_into_io_error() {
+        let err = io::Error::from(Error::NotATerminal);
+        assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+
+        let err = io::Error::from(Error::Interrupted);
+        assert!(matches!(Error::from(err), Error::Interrupted));
+    }
+}
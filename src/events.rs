@@ -0,0 +1,123 @@
+//! Provides events which let programs observe their prompts
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+type Listener = Arc<dyn Fn(&PromptEvent) + Send + Sync>;
+
+static LISTENER: Mutex<Option<Listener>> = Mutex::new(None);
+
+/// Event of a prompt, see
+/// [set_event_listener](fn.set_event_listener.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PromptEvent<'a> {
+    /// A prompt was shown for the first time.
+    Shown { prompt: &'a str },
+    /// A key press was read.  Which key it was is left out, so nothing
+    /// typed leaks into logs.
+    KeyPressed,
+    /// A prompt was answered.
+    Answered {
+        prompt: &'a str,
+        /// The plain text line the answered prompt leaves behind, as
+        /// written by `with_mirror`.
+        line: &'a str,
+        /// The time since the prompt was shown.
+        elapsed: Duration,
+    },
+}
+
+/// Calls `listener` for every event of every prompt.
+///
+/// This shows where users spend time in a flow of prompts, for example by
+/// forwarding the events to a logging or tracing framework:
+///
+/// ```rust,no_run
+/// use dialoguer::{set_event_listener, PromptEvent};
+///
+/// set_event_listener(|event| {
+///     if let PromptEvent::Answered { prompt, elapsed, .. } = event {
+///         eprintln!("{:?} took {:?}", prompt, elapsed);
+///     }
+/// });
+/// ```
+///
+/// A prompt which is cancelled or fails is shown without being answered.
+/// The listener replaces any earlier one.  It is called on the thread the
+/// prompt runs on, which for async prompts is the one polling them.
+pub fn set_event_listener<F: Fn(&PromptEvent) + Send + Sync + 'static>(listener: F) {
+    *LISTENER.lock().unwrap() = Some(Arc::new(listener));
+}
+
+/// Removes the listener set with
+/// [set_event_listener](fn.set_event_listener.html).
+pub fn clear_event_listener() {
+    *LISTENER.lock().unwrap() = None;
+}
+
+/// Passes `event` to the listener, if there is one.
+///
+/// The lock is released first, so the listener may run prompts of its own.
+pub(crate) fn emit(event: PromptEvent) {
+    let listener = LISTENER.lock().unwrap().clone();
+    if let Some(listener) = listener {
+        listener(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::TestTerm, Select, Sort};
+    use console::Key;
+
+    #[test]
+    fn test_events() {
+        let seen = Arc::new(Mutex::new(vec![]));
+        let listener_seen = seen.clone();
+        // Other tests run prompts at the same time, so only look at ours.
+        set_event_listener(move |event| match event {
+            PromptEvent::Shown {
+                prompt: "Event test",
+            } => listener_seen.lock().unwrap().push("shown".to_string()),
+            PromptEvent::Answered {
+                prompt: "Event test",
+                line,
+                ..
+            } => listener_seen.lock().unwrap().push(line.to_string()),
+            _ => {}
+        });
+
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::ArrowDown, Key::Enter]);
+        let answer = Select::new()
+            .with_prompt("Event test")
+            .items(&["red", "green"])
+            .default(0)
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), 1);
+
+        // pairwise ranking and the final order are one prompt
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::ArrowLeft]);
+        let order = Sort::new()
+            .with_prompt("Event test")
+            .items(&["red", "green"])
+            .pairwise(true)
+            .interact_on(&term);
+        clear_event_listener();
+
+        assert_eq!(order.unwrap(), vec![1, 0]);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                "shown",
+                "Event test: green",
+                "shown",
+                "Event test: green, red"
+            ]
+        );
+    }
+}
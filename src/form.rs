@@ -0,0 +1,316 @@
+//! Provides a form which runs several prompts in a row
+use std::{any::Any, collections::HashMap, mem};
+
+use console::Term;
+
+use crate::{
+    backend::Backend,
+    error::{Error, Result},
+    prompts::Prompt,
+};
+
+/// Type erased step of a [Form](struct.Form.html).
+trait FormStep {
+    fn run(&self, term: &dyn Backend) -> Result<Option<Box<dyn Any>>>;
+}
+
+impl<P> FormStep for P
+where
+    P: Prompt,
+    P::Output: 'static,
+{
+    fn run(&self, term: &dyn Backend) -> Result<Option<Box<dyn Any>>> {
+        Ok(self
+            .interact_on_step(term)?
+            .map(|answer| Box::new(answer) as Box<dyn Any>))
+    }
+}
+
+/// Runs several prompts in a row and lets the user go back.
+///
+/// Each step is a named [Prompt](trait.Prompt.html).  Going back with
+/// [Action::Back](enum.Action.html), 'Esc' by default, asks the previous
+/// step again.  A step whose input was rejected too often, see
+/// [Input::max_attempts](struct.Input.html#method.max_attempts), is asked
+/// again as well.  Cancelling a prompt with
+/// [Action::Cancel](enum.Action.html), for example with 'q' in a
+/// [Select](struct.Select.html), and Ctrl+C stop the form with
+/// [Error::Interrupted](enum.Error.html#variant.Interrupted).
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{Confirm, Form, Input, Select};
+///
+/// let mut name = Input::<String>::new();
+/// name.with_prompt("Name");
+/// let mut color = Select::new();
+/// color.with_prompt("Color").items(&["red", "green"]).default(0);
+/// let mut sure = Confirm::new();
+/// sure.with_prompt("Are you sure?");
+///
+/// let answers = Form::new()
+///     .step("name", &name)
+///     .step("color", &color)
+///     .step("sure", &sure)
+///     .interact()?;
+///
+/// let name: &String = answers.get("name").unwrap();
+/// println!("Hello {}", name);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Form<'a> {
+    steps: Vec<(String, Box<dyn FormStep + 'a>)>,
+}
+
+/// Answers collected by a [Form](struct.Form.html).
+#[derive(Debug, Default)]
+pub struct FormAnswers {
+    answers: HashMap<String, Box<dyn Any>>,
+}
+
+impl FormAnswers {
+    /// Returns the answer of the step called `name`.
+    ///
+    /// Returns `None` if there is no such step or its answer is not a `T`.
+    pub fn get<T: 'static>(&self, name: &str) -> Option<&T> {
+        self.answers.get(name)?.downcast_ref()
+    }
+
+    /// Removes the answer of the step called `name` and returns it.
+    pub fn remove<T: 'static>(&mut self, name: &str) -> Option<T> {
+        match self.answers.remove(name)?.downcast() {
+            Ok(answer) => Some(*answer),
+            Err(answer) => {
+                self.answers.insert(name.to_string(), answer);
+                None
+            }
+        }
+    }
+
+    /// Returns the number of answers.
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// Checks whether there are no answers.
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+impl<'a> Form<'a> {
+    /// Creates a form without steps.
+    pub fn new() -> Form<'a> {
+        Form::default()
+    }
+
+    /// Returns the configured form, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a form that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::{Form, Select};
+    ///
+    /// let select = Select::new().items(&["red", "green"]).default(0).build();
+    ///
+    /// let form = Form::new().step("color", select).build();
+    /// ```
+    pub fn build(&mut self) -> Form<'a> {
+        mem::take(self)
+    }
+
+    /// Adds a step called `name` which runs `prompt`.
+    ///
+    /// Pass a reference to keep using a prompt after the form is done.
+    pub fn step<S, P>(&mut self, name: S, prompt: P) -> &mut Form<'a>
+    where
+        S: Into<String>,
+        P: Prompt + 'a,
+        P::Output: 'static,
+    {
+        self.steps.push((name.into(), Box::new(prompt)));
+        self
+    }
+
+    /// Runs the steps and returns their answers.
+    ///
+    /// Going back from the first step asks it again.  The dialogs are
+    /// rendered on stderr.
+    pub fn interact(&self) -> Result<FormAnswers> {
+        self.interact_on(&Term::stderr())
+    }
+
+    /// Like [interact](#method.interact), but going back from the first
+    /// step cancels the form, in which case `None` is returned.
+    pub fn interact_opt(&self) -> Result<Option<FormAnswers>> {
+        self.interact_on_opt(&Term::stderr())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<FormAnswers> {
+        loop {
+            if let Some(answers) = self.interact_on_opt(term)? {
+                return Ok(answers);
+            }
+        }
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<FormAnswers>> {
+        let mut answers = FormAnswers::default();
+        let mut idx = 0;
+
+        while idx < self.steps.len() {
+            let (ref name, ref step) = self.steps[idx];
+
+            match step.run(term) {
+                Ok(Some(answer)) => {
+                    answers.answers.insert(name.clone(), answer);
+                    idx += 1;
+                }
+                Ok(None) => {
+                    if idx == 0 {
+                        return Ok(None);
+                    }
+                    idx -= 1;
+                    answers.answers.remove(&self.steps[idx].0);
+                }
+                Err(Error::ValidationExhausted(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(Some(answers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::TestTerm, Action, Confirm, Input, KeyBindings, Select};
+    use console::Key;
+
+    #[test]
+    fn test_back() {
+        let term = TestTerm::new();
+        term.push_line("Ann")
+            .push_key(Key::Escape)
+            .push_line("Bob")
+            .push_key(Key::ArrowDown)
+            .push_key(Key::Enter)
+            .push_str("y");
+
+        let mut name = Input::<String>::new();
+        name.with_prompt("Name");
+        let mut color = Select::new();
+        color
+            .with_prompt("Color")
+            .items(&["red", "green"])
+            .default(0);
+
+        let answers = Form::new()
+            .step("name", &name)
+            .step("color", &color)
+            .step("sure", Confirm::new())
+            .interact_on(&term)
+            .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers.get::<String>("name").unwrap(), "Bob");
+        assert_eq!(answers.get::<usize>("color"), Some(&1));
+        assert_eq!(answers.get::<bool>("sure"), Some(&true));
+        assert_eq!(answers.get::<String>("sure"), None);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let term = TestTerm::new();
+        term.push_key(Key::Escape);
+
+        let mut color = Select::new();
+        color.items(&["red"]);
+
+        let answers = Form::new()
+            .step("color", &color)
+            .interact_on_opt(&term)
+            .unwrap();
+        assert!(answers.is_none());
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let term = TestTerm::new();
+        term.push_line("Ann").push_interrupt();
+
+        let mut color = Select::new();
+        color.items(&["red"]);
+
+        let err = Form::new()
+            .step("name", Input::<String>::new())
+            .step("color", &color)
+            .interact_on_opt(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::Interrupted));
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut keys = KeyBindings::default();
+        keys.bind(Action::Back, Key::Char('\u{2}'))
+            .bind(Action::Cancel, Key::Escape);
+
+        // Ctrl+B goes back and 'Esc' stops the form
+        let term = TestTerm::new();
+        term.push_line("Ann")
+            .push_key(Key::Char('\u{2}'))
+            .push_line("Bob")
+            .push_key(Key::Escape);
+
+        let mut name = Input::<String>::new();
+        name.with_keys(&keys);
+        let mut sure = Confirm::new();
+        sure.with_keys(&keys);
+
+        let err = Form::new()
+            .step("name", &name)
+            .step("sure", &sure)
+            .interact_on_opt(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::Interrupted));
+        assert!(term.transcript().contains("Bob"));
+
+        // 'q' cancels a select
+        let term = TestTerm::new();
+        term.push_key(Key::Char('q'));
+
+        let mut color = Select::new();
+        color.items(&["red"]);
+
+        let err = Form::new()
+            .step("color", &color)
+            .interact_on_opt(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::Interrupted));
+    }
+
+    #[test]
+    fn test_validation_exhausted() {
+        let term = TestTerm::new();
+        term.push_line("x").push_line("1");
+
+        let mut number = Input::<u32>::new();
+        number.max_attempts(1);
+
+        let mut answers = Form::new()
+            .step("number", &number)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(answers.remove::<u32>("number"), Some(1));
+        assert!(answers.is_empty());
+    }
+}
@@ -0,0 +1,452 @@
+//! Provides history for text inputs
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+type IgnoreCallback = Box<dyn Fn(&str) -> bool>;
+
+/// Trait for the history of input prompts.
+///
+/// Entries are counted from the most recent one, which is at position `0`.
+pub trait History<T> {
+    /// Returns the entry at `pos`, if there is one.
+    fn read(&self, pos: usize) -> Option<String>;
+
+    /// Invoked with every value the user submitted.
+    fn write(&mut self, val: &T);
+
+    /// Returns the entries, starting with the most recent one.
+    fn iter(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        Box::new((0..).map_while(move |pos| self.read(pos)))
+    }
+
+    /// Returns the entries which contain `substring`, starting with the
+    /// most recent one.
+    fn search(&self, substring: &str) -> Vec<String> {
+        self.iter()
+            .filter(|entry| entry.contains(substring))
+            .collect()
+    }
+}
+
+/// Controls how the histories of the crate handle duplicate entries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DedupMode {
+    /// Stores every entry.
+    Off,
+    /// Stores consecutive duplicates once.
+    ///
+    /// This is the default.
+    #[default]
+    Adjacent,
+    /// Removes an earlier copy of an entry, so that it moves to the front.
+    All,
+}
+
+/// Entries of a history with the settings shared by all implementations.
+struct Entries {
+    entries: VecDeque<String>,
+    max: Option<usize>,
+    dedup: DedupMode,
+    ignore: Vec<IgnoreCallback>,
+}
+
+/// What [Entries::push](struct.Entries.html#method.push) did with an entry.
+enum Push {
+    /// The entry was ignored or already in front.
+    Skipped,
+    /// The entry was added in front of the others.
+    Added,
+    /// The entry was added and an older one was removed.
+    Removed,
+}
+
+impl Entries {
+    fn new(entries: VecDeque<String>, max: Option<usize>) -> Entries {
+        Entries {
+            entries,
+            max,
+            dedup: DedupMode::default(),
+            ignore: Vec::new(),
+        }
+    }
+
+    fn read(&self, pos: usize) -> Option<String> {
+        self.entries.get(pos).cloned()
+    }
+
+    /// Returns the entries which contain `substring` without copying the
+    /// others.
+    fn search(&self, substring: &str) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.contains(substring))
+            .cloned()
+            .collect()
+    }
+
+    /// Adds `entry` in front and drops the oldest entries past the maximum.
+    fn push(&mut self, entry: String) -> Push {
+        if self.ignore.iter().any(|ignore| ignore(&entry)) {
+            return Push::Skipped;
+        }
+
+        let mut rv = Push::Added;
+        match self.dedup {
+            DedupMode::Off => {}
+            DedupMode::Adjacent | DedupMode::All if self.entries.front() == Some(&entry) => {
+                return Push::Skipped;
+            }
+            DedupMode::Adjacent => {}
+            DedupMode::All => {
+                let len = self.entries.len();
+                self.entries.retain(|old| *old != entry);
+                if self.entries.len() != len {
+                    rv = Push::Removed;
+                }
+            }
+        }
+
+        self.entries.push_front(entry);
+
+        match self.max {
+            Some(max) if self.entries.len() > max => {
+                self.entries.truncate(max);
+                Push::Removed
+            }
+            _ => rv,
+        }
+    }
+}
+
+/// Turns a submitted value into a single line entry.
+fn entry_for<T: Display>(val: &T) -> String {
+    val.to_string().replace('\n', " ")
+}
+
+/// History which is kept in memory and holds a limited number of entries.
+///
+/// Once the history is full, the oldest entries are dropped.  Consecutive
+/// duplicates are stored once unless [dedup](#method.dedup) says
+/// otherwise.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{BufferedHistory, Input};
+///
+/// let mut history = BufferedHistory::with_capacity(50);
+/// history.prepopulate(vec!["status", "help"]);
+///
+/// loop {
+///     let command: String = Input::new()
+///         .with_prompt("Command")
+///         .with_history(&mut history)
+///         .interact_text()?;
+/// #   break;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct BufferedHistory {
+    entries: Entries,
+}
+
+impl BufferedHistory {
+    /// Creates an empty history which holds at most `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> BufferedHistory {
+        BufferedHistory {
+            entries: Entries::new(VecDeque::with_capacity(capacity), Some(capacity)),
+        }
+    }
+
+    /// Indicates whether consecutive duplicates are stored once.
+    ///
+    /// This is a shorthand for [dedup](#method.dedup) with
+    /// `DedupMode::Adjacent` or `DedupMode::Off`.
+    pub fn dedup_adjacent(&mut self, val: bool) -> &mut BufferedHistory {
+        self.dedup(if val {
+            DedupMode::Adjacent
+        } else {
+            DedupMode::Off
+        })
+    }
+
+    /// Sets how duplicate entries are handled.
+    ///
+    /// The default is `DedupMode::Adjacent`.
+    pub fn dedup(&mut self, mode: DedupMode) -> &mut BufferedHistory {
+        self.entries.dedup = mode;
+        self
+    }
+
+    /// Skips storing entries for which `ignore` returns `true`.
+    ///
+    /// This can keep secrets out of the history, or anything starting with
+    /// a space like the `HISTCONTROL` setting of bash.  Entries are
+    /// ignored if any of the registered predicates matches.
+    pub fn with_ignore<F: Fn(&str) -> bool + 'static>(
+        &mut self,
+        ignore: F,
+    ) -> &mut BufferedHistory {
+        self.entries.ignore.push(Box::new(ignore));
+        self
+    }
+
+    /// Adds entries as if they had been submitted, the oldest one first.
+    pub fn prepopulate<I, S>(&mut self, entries: I) -> &mut BufferedHistory
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for entry in entries {
+            self.entries.push(entry.into());
+        }
+        self
+    }
+}
+
+impl<T: Display> History<T> for BufferedHistory {
+    fn read(&self, pos: usize) -> Option<String> {
+        self.entries.read(pos)
+    }
+
+    fn search(&self, substring: &str) -> Vec<String> {
+        self.entries.search(substring)
+    }
+
+    fn write(&mut self, val: &T) {
+        self.entries.push(entry_for(val));
+    }
+}
+
+/// History which is kept in a file with one entry per line.
+///
+/// The entries are loaded when the history is opened and every new entry
+/// is appended to the file.  Consecutive duplicates are stored once unless
+/// [dedup](#method.dedup) says otherwise.
+///
+/// ## Example usage
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{FileHistory, Input};
+///
+/// let mut history = FileHistory::open(".my_history")?;
+/// history.max_entries(100);
+///
+/// let command: String = Input::new()
+///     .with_prompt("Command")
+///     .with_history(&mut history)
+///     .interact_text()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FileHistory {
+    path: PathBuf,
+    entries: Entries,
+}
+
+impl FileHistory {
+    /// Opens the history in the file at `path`.
+    ///
+    /// A missing file is created once the first entry is written.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileHistory> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().rev().map(str::to_string).collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => VecDeque::new(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(FileHistory {
+            path,
+            entries: Entries::new(entries, None),
+        })
+    }
+
+    /// Keeps at most `max` entries and drops the oldest ones.
+    ///
+    /// The default is to keep all entries.  The file is only rewritten
+    /// once entries are dropped.
+    pub fn max_entries(&mut self, max: usize) -> &mut FileHistory {
+        self.entries.max = Some(max);
+        self
+    }
+
+    /// Sets how duplicate entries are handled.
+    ///
+    /// The default is `DedupMode::Adjacent`.  With `DedupMode::All` the
+    /// file is rewritten whenever an earlier copy of an entry is removed.
+    pub fn dedup(&mut self, mode: DedupMode) -> &mut FileHistory {
+        self.entries.dedup = mode;
+        self
+    }
+
+    /// Skips storing entries for which `ignore` returns `true`.
+    ///
+    /// This can keep secrets out of the history, or anything starting with
+    /// a space like the `HISTCONTROL` setting of bash.  Entries are
+    /// ignored if any of the registered predicates matches.
+    pub fn with_ignore<F: Fn(&str) -> bool + 'static>(&mut self, ignore: F) -> &mut FileHistory {
+        self.entries.ignore.push(Box::new(ignore));
+        self
+    }
+
+    /// Writes all entries to the file, replacing its contents.
+    pub fn save(&self) -> io::Result<()> {
+        let mut contents = String::new();
+        for entry in self.entries.entries.iter().rev() {
+            contents.push_str(entry);
+            contents.push('\n');
+        }
+        fs::write(&self.path, contents)
+    }
+
+    fn append(&self, entry: &str) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", entry)
+    }
+}
+
+impl<T: Display> History<T> for FileHistory {
+    fn read(&self, pos: usize) -> Option<String> {
+        self.entries.read(pos)
+    }
+
+    fn search(&self, substring: &str) -> Vec<String> {
+        self.entries.search(substring)
+    }
+
+    /// Adds the value to the history and to the file.
+    ///
+    /// Errors while writing the file are ignored, so that a history which
+    /// cannot be stored does not break the prompt.  Use
+    /// [save](struct.FileHistory.html#method.save) to check for them.
+    fn write(&mut self, val: &T) {
+        match self.entries.push(entry_for(val)) {
+            Push::Skipped => {}
+            Push::Added => {
+                let _ = self.append(&self.entries.entries[0]);
+            }
+            Push::Removed => {
+                let _ = self.save();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffered_history() {
+        let mut history = BufferedHistory::with_capacity(3);
+        history.prepopulate(vec!["one", "two", "two"]);
+        History::<&str>::write(&mut history, &"three");
+        History::<&str>::write(&mut history, &"four");
+        assert_eq!(
+            History::<&str>::iter(&history).collect::<Vec<_>>(),
+            ["four", "three", "two"]
+        );
+
+        assert_eq!(History::<&str>::search(&history, "o"), ["four", "two"]);
+
+        history.dedup_adjacent(false);
+        History::<&str>::write(&mut history, &"four");
+        assert_eq!(History::<&str>::read(&history, 1).as_deref(), Some("four"));
+    }
+
+    #[test]
+    fn test_history_ignore_and_dedup() {
+        let mut history = BufferedHistory::with_capacity(10);
+        history
+            .dedup(DedupMode::All)
+            .with_ignore(|entry| entry.starts_with(' '));
+        history.prepopulate(vec!["one", "two", " secret", "one"]);
+        assert_eq!(
+            History::<&str>::iter(&history).collect::<Vec<_>>(),
+            ["one", "two"]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history");
+        let mut history = FileHistory::open(&path).unwrap();
+        history.dedup(DedupMode::All);
+        History::<&str>::write(&mut history, &"one");
+        History::<&str>::write(&mut history, &"two");
+        History::<&str>::write(&mut history, &"one");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two\none\n");
+    }
+
+    #[test]
+    fn test_default_search() {
+        struct Fixed;
+
+        impl History<String> for Fixed {
+            fn read(&self, pos: usize) -> Option<String> {
+                ["git push", "ls", "git pull"]
+                    .get(pos)
+                    .map(|s| s.to_string())
+            }
+
+            fn write(&mut self, _val: &String) {}
+        }
+
+        assert_eq!(Fixed.search("git"), ["git push", "git pull"]);
+        assert!(Fixed.search("cargo").is_empty());
+    }
+
+    #[test]
+    fn test_input_recall() {
+        use crate::{test::TestTerm, Input};
+        use console::Key;
+
+        let mut history = BufferedHistory::with_capacity(10);
+        history.prepopulate(vec!["git pull", "ls", "git push"]);
+
+        let term = TestTerm::new();
+        term.push_str("gi")
+            .push_keys(vec![Key::ArrowUp, Key::ArrowUp, Key::ArrowUp])
+            .push_keys(vec![Key::ArrowDown, Key::Enter])
+            .push_str("x")
+            .push_keys(vec![Key::ArrowUp, Key::ArrowDown, Key::Enter]);
+
+        let mut input = Input::<String>::new();
+        input.with_history(&mut history);
+        assert_eq!(input.interact_text_on(&term).unwrap(), "ls");
+        assert_eq!(input.interact_text_on(&term).unwrap(), "x");
+    }
+
+    #[test]
+    fn test_file_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history");
+
+        let mut history = FileHistory::open(&path).unwrap();
+        history.max_entries(2);
+        History::<&str>::write(&mut history, &"one");
+        History::<&str>::write(&mut history, &"two");
+        History::<&str>::write(&mut history, &"two");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+
+        History::<&str>::write(&mut history, &"three");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "two\nthree\n");
+
+        let history = FileHistory::open(&path).unwrap();
+        assert_eq!(History::<&str>::read(&history, 0).as_deref(), Some("three"));
+        assert_eq!(
+            History::<&str>::iter(&history).collect::<Vec<_>>(),
+            ["three", "two"]
+        );
+    }
+}
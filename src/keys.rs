@@ -0,0 +1,247 @@
+//! Provides configurable key bindings for the prompts
+use console::Key;
+
+use crate::error::{Error, Result};
+
+/// Action of a prompt which can be bound to keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Moves to the previous item.
+    Up,
+    /// Moves to the next item.
+    Down,
+    /// Moves to the previous page, or the left item of a horizontal select.
+    Left,
+    /// Moves to the next page, or the right item of a horizontal select.
+    Right,
+    /// Checks an item of a multi select, picks up an item of a sort prompt
+    /// or selects an item of a select prompt.
+    Toggle,
+    /// Accepts the answer.
+    Submit,
+    /// Cancels the prompt if it allows that, see for example
+    /// [Select::interact_opt](struct.Select.html#method.interact_opt).  In a
+    /// [Form](struct.Form.html) this stops the form like Ctrl+C.
+    Cancel,
+    /// Goes back to the previous step of a [Form](struct.Form.html).  A
+    /// prompt run on its own has no previous step, so this cancels it.
+    Back,
+}
+
+impl Action {
+    /// Returns the key the prompts handle for the action.
+    fn key(self) -> Key {
+        match self {
+            Action::Up => Key::ArrowUp,
+            Action::Down => Key::ArrowDown,
+            Action::Left => Key::ArrowLeft,
+            Action::Right => Key::ArrowRight,
+            Action::Toggle => Key::Char(' '),
+            Action::Submit => Key::Enter,
+            Action::Cancel => CANCEL,
+            Action::Back => Key::Escape,
+        }
+    }
+}
+
+/// Key the prompts handle for [Action::Cancel](enum.Action.html).
+///
+/// console reports Ctrl+C as an error instead of this character, so no
+/// key press is read as it.
+pub(crate) const CANCEL: Key = Key::Char('\u{3}');
+
+/// Returns what a prompt returns when it is quit with `key`, which is
+/// `Key::Escape` to go back or `CANCEL`.
+///
+/// Cancelling fails like Ctrl+C, which stops a [Form](struct.Form.html).
+/// The `interact_opt` methods return `None` for both.
+pub(crate) fn quit<T>(key: Key) -> Result<Option<T>> {
+    match key {
+        CANCEL => Err(Error::Interrupted),
+        _ => Ok(None),
+    }
+}
+
+const DEFAULT_BINDINGS: [(Action, Key); 11] = [
+    (Action::Up, Key::ArrowUp),
+    (Action::Up, Key::Char('k')),
+    (Action::Down, Key::ArrowDown),
+    (Action::Down, Key::Char('j')),
+    (Action::Left, Key::ArrowLeft),
+    (Action::Left, Key::Char('h')),
+    (Action::Right, Key::ArrowRight),
+    (Action::Right, Key::Char('l')),
+    (Action::Toggle, Key::Char(' ')),
+    (Action::Submit, Key::Enter),
+    (Action::Back, Key::Escape),
+];
+
+/// Keys the prompts react to.
+///
+/// By default the arrows and the vim keys `h`, `j`, `k` and `l` move, space
+/// toggles, enter submits and escape goes back, which cancels a prompt
+/// outside of a [Form](struct.Form.html).  A [Select](struct.Select.html)
+/// also cancels on `q` unless it gets other bindings, as it has no changes
+/// to lose.  The bindings can be changed and passed to
+/// [Select](struct.Select.html), [MultiSelect](struct.MultiSelect.html),
+/// [Sort](struct.Sort.html), [FuzzySelect](struct.FuzzySelect.html),
+/// [Confirm](struct.Confirm.html) and [Input](struct.Input.html) with their
+/// `with_keys` methods:
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{console::Key, Action, KeyBindings, Select};
+///
+/// let mut keys = KeyBindings::default();
+/// // Ctrl+P and Ctrl+N
+/// keys.bind(Action::Up, Key::Char('\u{10}'))
+///     .bind(Action::Down, Key::Char('\u{e}'));
+///
+/// let selection = Select::new()
+///     .items(&["red", "green"])
+///     .with_keys(&keys)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A fuzzy select, a confirm prompt and an input prompt treat printable
+/// characters as text or as their answer, so only other keys can be bound
+/// for them, and they only react to moving up and down, submitting,
+/// cancelling and going back.  A [Password](struct.Password.html) reads
+/// whole lines and has no key bindings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: Vec<(Action, Key)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        KeyBindings {
+            bindings: DEFAULT_BINDINGS.to_vec(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Creates key bindings without any keys.
+    pub fn empty() -> KeyBindings {
+        KeyBindings { bindings: vec![] }
+    }
+
+    /// Binds `key` to `action`.
+    ///
+    /// The key is taken away from any other action it was bound to.
+    pub fn bind(&mut self, action: Action, key: Key) -> &mut KeyBindings {
+        self.unbind(key);
+        self.bindings.push((action, key));
+        self
+    }
+
+    /// Removes `key` from the action it was bound to.
+    pub fn unbind(&mut self, key: Key) -> &mut KeyBindings {
+        self.bindings.retain(|&(_, bound)| bound != key);
+        self
+    }
+
+    /// Removes all keys bound to `action`.
+    pub fn clear(&mut self, action: Action) -> &mut KeyBindings {
+        self.bindings.retain(|&(bound, _)| bound != action);
+        self
+    }
+
+    /// Returns the keys bound to `action`.
+    pub fn keys(&self, action: Action) -> Vec<Key> {
+        self.bindings
+            .iter()
+            .filter(|&&(bound, _)| bound == action)
+            .map(|&(_, key)| key)
+            .collect()
+    }
+
+    /// Returns the action `key` is bound to.
+    pub fn action(&self, key: Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|&&(_, bound)| bound == key)
+            .map(|&(action, _)| action)
+    }
+
+    /// Turns `key` into the key the list prompts handle for its action.
+    ///
+    /// Keys which are bound by default but no longer are become
+    /// `Key::Unknown`, which the prompts ignore.  Other keys, such as the
+    /// digits of a sort prompt, pass through.
+    pub(crate) fn translate(&self, key: Key) -> Key {
+        self.translate_for(key, |_| true)
+    }
+
+    /// Like [translate](#method.translate) for prompts which read text,
+    /// such as a query, where printable characters are never bound.
+    pub(crate) fn translate_query(&self, key: Key) -> Key {
+        match key {
+            Key::Char(chr) if !chr.is_control() => key,
+            key => self.translate_for(key, |action| {
+                matches!(
+                    action,
+                    Action::Up | Action::Down | Action::Submit | Action::Cancel | Action::Back
+                )
+            }),
+        }
+    }
+
+    fn translate_for<F: Fn(Action) -> bool>(&self, key: Key, handled: F) -> Key {
+        match self.action(key) {
+            Some(action) if handled(action) => action.key(),
+            _ if DEFAULT_BINDINGS
+                .iter()
+                .any(|&(action, bound)| bound == key && handled(action)) =>
+            {
+                Key::Unknown
+            }
+            _ => key,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind() {
+        let mut keys = KeyBindings::default();
+        keys.bind(Action::Down, Key::Char('n'))
+            .bind(Action::Toggle, Key::Char('j'));
+
+        assert_eq!(keys.action(Key::Char('n')), Some(Action::Down));
+        assert_eq!(keys.action(Key::Char('j')), Some(Action::Toggle));
+        assert_eq!(
+            keys.keys(Action::Down),
+            vec![Key::ArrowDown, Key::Char('n')]
+        );
+
+        keys.clear(Action::Down);
+        assert!(keys.keys(Action::Down).is_empty());
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut keys = KeyBindings::default();
+        keys.unbind(Key::Char('k'))
+            .bind(Action::Up, Key::Char('\u{10}'));
+
+        assert_eq!(keys.translate(Key::Char('\u{10}')), Key::ArrowUp);
+        assert_eq!(keys.translate(Key::Char('j')), Key::ArrowDown);
+        assert_eq!(keys.translate(Key::Char('k')), Key::Unknown);
+        assert_eq!(keys.translate(Key::Char('3')), Key::Char('3'));
+        assert_eq!(keys.translate(Key::Escape), Key::Escape);
+
+        keys.bind(Action::Cancel, Key::Escape);
+        assert_eq!(keys.translate(Key::Escape), CANCEL);
+
+        assert_eq!(keys.translate_query(Key::Char('j')), Key::Char('j'));
+        assert_eq!(keys.translate_query(Key::Char('\u{10}')), Key::ArrowUp);
+        assert_eq!(keys.translate_query(Key::ArrowLeft), Key::ArrowLeft);
+    }
+}
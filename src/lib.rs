@@ -13,19 +13,96 @@
 //! * Confirmation prompts
 //! * Input prompts (regular and password)
 //! * Input validation
+//! * Input history
+//! * Input completion
 //! * Selections prompts (single and multi)
+//! * Fuzzy searchable selection prompts
+//! * Configurable key bindings
 //! * Other kind of prompts
+//! * Forms of several prompts with back navigation
 //! * Editor launching
+//! * Scripted answers without a terminal
+//! * Prompts defined as data
+//! * An in-memory terminal for tests
+//! * Events to observe prompts
+//!
+//! # Errors
+//!
+//! The prompts return an [Error](enum.Error.html) which tells apart why a
+//! prompt failed.  If the user presses Ctrl+C while a prompt waits for
+//! input, the prompt restores the cursor and returns
+//! [Error::Interrupted](enum.Error.html#variant.Interrupted):
+//!
+//! ```rust,no_run
+//! # fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! use dialoguer::{Confirm, Error};
+//!
+//! match Confirm::new().with_prompt("Continue?").interact() {
+//!     Ok(answer) => println!("Answer: {}", answer),
+//!     Err(Error::Interrupted) => println!("Cancelled"),
+//!     Err(err) => return Err(err.into()),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The error converts into an `io::Error`, so prompts also work in
+//! functions which return `io::Result`.
+//!
+//! # Crate Features
+//!
+//! * `async`: adds async variants of the interaction methods of all
+//!   prompts, such as
+//!   [Select::interact_async](struct.Select.html#method.interact_async),
+//!   [Confirm::interact_async](struct.Confirm.html#method.interact_async),
+//!   [Input::interact_text_async](struct.Input.html#method.interact_text_async),
+//!   [Password::interact_async](struct.Password.html#method.interact_async),
+//!   and [Editor::edit_async](struct.Editor.html#method.edit_async).
+//! * `regex`: adds the
+//!   [matches_regex](validators/fn.matches_regex.html) validator.
+//! * `serde`: makes [prompt specs](spec/index.html) and their answers
+//!   serializable, for example to read them from a configuration file, and
+//!   adds [interact_specs_value](spec/fn.interact_specs_value.html).
 
+pub use backend::{term_size, Backend};
+pub use completion::{Completion, PathCompletion, StringListCompletion};
 pub use console;
 pub use edit::Editor;
+pub use error::{Error, Result};
+pub use events::{clear_event_listener, set_event_listener, PromptEvent};
+pub use form::{Form, FormAnswers};
+pub use history::{BufferedHistory, DedupMode, FileHistory, History};
+pub use keys::{Action, KeyBindings};
+pub use non_interactive::{
+    clear_non_interactive_input, set_assume_default, set_non_interactive_input,
+};
 pub use prompts::{
-    confirm::Confirm, input::Input, multi_select::MultiSelect, password::Password, select::Select,
+    confirm::Confirm,
+    fuzzy_select::FuzzySelect,
+    input::Input,
+    multi_select::{EscapeBehavior, GroupedSelection, MultiSelect},
+    password::Password,
+    select::Select,
     sort::Sort,
+    OutputStream, Prompt,
 };
+pub use session::Session;
 pub use validate::Validator;
 
+mod backend;
+mod completion;
 mod edit;
+mod error;
+mod events;
+mod form;
+mod history;
+mod keys;
+mod non_interactive;
 mod prompts;
+mod reader;
+mod session;
+pub mod spec;
+pub mod test;
 pub mod theme;
 mod validate;
+pub mod validators;
@@ -0,0 +1,259 @@
+//! Provides answers for prompts without a terminal
+use std::{
+    env,
+    io::{self, BufRead, IsTerminal},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{
+    backend::Backend,
+    error::{Error, Result},
+    theme::TermThemeRenderer,
+};
+
+type Input = Box<dyn BufRead + Send>;
+
+static INPUT: Mutex<Option<Input>> = Mutex::new(None);
+
+static ASSUME_DEFAULT: AtomicBool = AtomicBool::new(false);
+
+/// Environment variable which makes the prompts assume their defaults.
+const ASSUME_DEFAULT_VAR: &str = "DIALOGUER_ASSUME_DEFAULT";
+
+/// Sets where prompts read their answers from without a terminal.
+///
+/// If input does not come from a terminal, for example because it is
+/// piped in or the program runs in CI, the prompts read one answer per
+/// line from `input` instead of waiting for key presses.  This lets the
+/// same code run interactively and in scripts:
+///
+/// * [Confirm](struct.Confirm.html) takes `y`, `yes`, `n` or `no`.
+/// * [Input](struct.Input.html) and [Password](struct.Password.html)
+///   take the line as it is.
+/// * [Select](struct.Select.html) and [FuzzySelect](struct.FuzzySelect.html)
+///   take the text or the index of an item, counted from `0`.
+/// * [MultiSelect](struct.MultiSelect.html) and [Sort](struct.Sort.html)
+///   take a comma separated list of item texts or indices.
+///
+/// An empty line picks the default of the prompt, if it has one.  Invalid
+/// answers are reported like in the interactive prompts and the next line
+/// is read.  Once `input` is exhausted the prompts fail.  Without an input
+/// set here, prompts assume their defaults if stdin is not a terminal, see
+/// [set_assume_default](fn.set_assume_default.html).
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_non_interactive_input, Confirm};
+/// use std::io;
+///
+/// set_non_interactive_input(io::BufReader::new(io::stdin()));
+///
+/// if Confirm::new().with_prompt("Continue?").interact()? {
+///     println!("Looks like you want to continue");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_non_interactive_input<R: BufRead + Send + 'static>(input: R) {
+    *INPUT.lock().unwrap() = Some(Box::new(input));
+}
+
+/// Removes the input set with
+/// [set_non_interactive_input](fn.set_non_interactive_input.html).
+pub fn clear_non_interactive_input() {
+    *INPUT.lock().unwrap() = None;
+}
+
+/// Makes every prompt answer itself with its default.
+///
+/// This is meant for automation, such as scripted CI runs of a program
+/// which otherwise asks questions.  The prompts are rendered with their
+/// default as the answer, as if the user pressed enter right away, even
+/// on a terminal.  Setting the environment variable
+/// `DIALOGUER_ASSUME_DEFAULT` to anything but an empty string or `0` does
+/// the same without changing the program:
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{set_assume_default, Confirm};
+///
+/// set_assume_default(std::env::args().any(|arg| arg == "--yes"));
+///
+/// if Confirm::new().with_prompt("Continue?").interact()? {
+///     println!("Looks like you want to continue");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The prompts also assume their defaults if stdin is not a terminal and
+/// no input was set with
+/// [set_non_interactive_input](fn.set_non_interactive_input.html), so that
+/// a program run from a script does not wait for an answer which never
+/// comes.
+///
+/// Prompts without a default, such as a [Password](struct.Password.html)
+/// or an [Input](struct.Input.html) which does not accept an empty value,
+/// fail with [Error::NoDefault](enum.Error.html#variant.NoDefault).  This
+/// takes precedence over
+/// [set_non_interactive_input](fn.set_non_interactive_input.html).
+pub fn set_assume_default(val: bool) {
+    ASSUME_DEFAULT.store(val, Ordering::Relaxed);
+}
+
+/// Checks whether the prompts answer themselves with their defaults, see
+/// [set_assume_default](fn.set_assume_default.html).
+pub(crate) fn assumes_default() -> bool {
+    ASSUME_DEFAULT.load(Ordering::Relaxed)
+        || env::var_os(ASSUME_DEFAULT_VAR).is_some_and(|val| !val.is_empty() && val != "0")
+        || (INPUT.lock().unwrap().is_none() && !io::stdin().is_terminal())
+}
+
+/// Checks whether prompts on `term` read their answers from the input set
+/// with [set_non_interactive_input](fn.set_non_interactive_input.html), or
+/// assume their defaults.
+///
+/// Other backends than the console terminal bring their own input.
+pub(crate) fn is_active(term: &dyn Backend) -> bool {
+    let term = match term.as_term() {
+        Some(term) => term,
+        None => return false,
+    };
+
+    assumes_default()
+        || (INPUT.lock().unwrap().is_some() && (!term.is_term() || !io::stdin().is_terminal()))
+}
+
+/// Reads the next answer without the trailing newline.
+///
+/// The answer is empty, which picks the default, if the prompts assume
+/// their defaults.
+pub(crate) fn read_answer() -> io::Result<String> {
+    if assumes_default() {
+        return Ok(String::new());
+    }
+
+    let mut guard = INPUT.lock().unwrap();
+    let input = guard
+        .as_mut()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "Not a terminal"))?;
+
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "no answers left for the prompt",
+        ));
+    }
+
+    let len = line.trim_end_matches(&['\r', '\n'][..]).len();
+    line.truncate(len);
+    Ok(line)
+}
+
+/// Reads answers until `parse` accepts one.
+///
+/// `parse` returns the error message for a rejected answer, which is shown
+/// before the next answer is read.
+pub(crate) fn answer<T, F>(render: &mut TermThemeRenderer, mut parse: F) -> Result<T>
+where
+    F: FnMut(&str) -> Result<T, String>,
+{
+    loop {
+        let answer = read_answer()?;
+        match parse(&answer) {
+            Ok(value) => return Ok(value),
+            Err(err) => render.error(&err)?,
+        }
+
+        if assumes_default() {
+            return Err(Error::NoDefault);
+        }
+    }
+}
+
+/// Finds the item named by `answer`, either by its index or its text.
+pub(crate) fn find_item(items: &[String], answer: &str) -> Result<usize, String> {
+    let answer = answer.trim();
+
+    match answer.parse::<usize>() {
+        Ok(idx) if idx < items.len() => Ok(idx),
+        _ => items
+            .iter()
+            .position(|item| item == answer)
+            .ok_or_else(|| format!("{:?} is not one of the items", answer)),
+    }
+}
+
+/// Finds the items named by a comma separated `answer`.
+pub(crate) fn find_items(items: &[String], answer: &str) -> Result<Vec<usize>, String> {
+    if answer.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    answer
+        .split(',')
+        .map(|part| find_item(items, part))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_item() {
+        let items = vec!["red".to_string(), "green".to_string(), "3".to_string()];
+
+        assert_eq!(find_item(&items, "1"), Ok(1));
+        assert_eq!(find_item(&items, " green "), Ok(1));
+        assert_eq!(find_item(&items, "3"), Ok(2));
+        assert!(find_item(&items, "blue").is_err());
+    }
+
+    #[test]
+    fn test_find_items() {
+        let items = vec!["red".to_string(), "green".to_string(), "blue".to_string()];
+
+        assert_eq!(find_items(&items, "blue, 0"), Ok(vec![2, 0]));
+        assert_eq!(find_items(&items, ""), Ok(vec![]));
+        assert!(find_items(&items, "red,,blue").is_err());
+    }
+
+    #[test]
+    fn test_assume_default() {
+        use crate::{Confirm, Input, Select};
+        use console::Term;
+
+        let term = Term::stderr();
+        set_assume_default(true);
+        let confirm = Confirm::new().default(false).interact_on(&term);
+        let select = Select::new()
+            .items(&["red", "green"])
+            .default(1)
+            .interact_on(&term);
+        let input = Input::<u32>::new().interact_on(&term);
+        set_assume_default(false);
+
+        assert!(!confirm.unwrap());
+        assert_eq!(select.unwrap(), 1);
+        assert!(matches!(input, Err(Error::NoDefault)));
+    }
+
+    #[test]
+    fn test_stdin_not_a_terminal() {
+        use crate::Confirm;
+        use console::Term;
+
+        // Without a terminal on stdin nothing could answer the prompt.
+        if io::stdin().is_terminal() {
+            return;
+        }
+
+        let confirm = Confirm::new().default(true).interact_on(&Term::stderr());
+        assert!(confirm.unwrap());
+    }
+}
@@ -1,8 +1,18 @@
-use std::io;
+use std::{cell::RefCell, io, mem};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    backend::{Backend, HiddenCursor},
+    error::{cancel_on_interrupt, Error, Result},
+    keys::{quit, KeyBindings, CANCEL},
+    non_interactive,
+    prompts::{OutputStream, PromptOptions},
+    reader::{block_on, KeyReader},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
 
+#[cfg(feature = "async")]
 use console::Term;
+use console::{Key, StyledObject};
 
 /// Renders a confirm prompt.
 ///
@@ -25,6 +35,9 @@ pub struct Confirm<'a> {
     show_default: bool,
     disable_default: bool,
     wait_for_newline: bool,
+    options: PromptOptions<'a>,
+    keys: KeyBindings,
+    output: OutputStream,
     theme: &'a dyn Theme,
 }
 
@@ -36,7 +49,7 @@ impl<'a> Default for Confirm<'a> {
 
 impl<'a> Confirm<'a> {
     /// Creates a confirm prompt.
-    pub fn new() -> Confirm<'static> {
+    pub fn new() -> Confirm<'a> {
         Confirm::with_theme(&SimpleTheme)
     }
 
@@ -63,16 +76,130 @@ impl<'a> Confirm<'a> {
             show_default: true,
             disable_default: false,
             wait_for_newline: false,
+            options: PromptOptions::default(),
+            keys: KeyBindings::default(),
+            output: OutputStream::Stderr,
             theme,
         }
     }
 
+    /// Returns the configured prompt, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a prompt that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::Confirm;
+    ///
+    /// let confirm = Confirm::new().with_prompt("Continue?").default(false).build();
+    /// ```
+    pub fn build(&mut self) -> Confirm<'a> {
+        mem::take(self)
+    }
+
     /// Sets the confirm prompt.
     pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Confirm<'a> {
         self.prompt = prompt.into();
         self
     }
 
+    /// Overrides the prompt prefix of the theme for this prompt.
+    ///
+    /// Themes without a prompt prefix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_prefix(&mut self, prefix: StyledObject<String>) -> &mut Confirm<'a> {
+        self.options.prompt_prefix = Some(prefix);
+        self
+    }
+
+    /// Overrides the prompt suffix of the theme for this prompt.
+    ///
+    /// Themes without a prompt suffix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_suffix(&mut self, suffix: StyledObject<String>) -> &mut Confirm<'a> {
+        self.options.prompt_suffix = Some(suffix);
+        self
+    }
+
+    /// Overrides the separator between the prompt and its answer once the
+    /// prompt is done, for example `" → "`.
+    pub fn with_prompt_separator(&mut self, separator: StyledObject<String>) -> &mut Confirm<'a> {
+        self.options.prompt_separator = Some(separator);
+        self
+    }
+
+    /// Shows the step of this prompt in a multi step flow, such as
+    /// `(step 2/5)`.
+    ///
+    /// `current` starts at 1.  The theme decides how the badge looks.
+    pub fn with_step(&mut self, current: usize, total: usize) -> &mut Confirm<'a> {
+        self.options.step = Some((current, total));
+        self
+    }
+
+    /// Indents every line of the prompt by `indent` spaces.
+    ///
+    /// This nests the prompt in surrounding output.  The default is no
+    /// indent.
+    pub fn with_indent(&mut self, indent: usize) -> &mut Confirm<'a> {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Indicates whether to report the answered prompt once it is done.
+    ///
+    /// The interactive prompt is still cleared as usual.  The default is
+    /// `true`.
+    pub fn report(&mut self, val: bool) -> &mut Confirm<'a> {
+        self.options.report = val;
+        self
+    }
+
+    /// Sets where [interact](#method.interact) and the other methods which
+    /// do not take a terminal render the prompt.
+    ///
+    /// The default is stderr, which keeps the prompt out of the output when
+    /// stdout is piped into another program.  Prompts rendered on stdout
+    /// become part of that output instead, and they fail with
+    /// [Error::NotATerminal](enum.Error.html#variant.NotATerminal) if
+    /// stdout is not a terminal, unless answers come from
+    /// [set_non_interactive_input](fn.set_non_interactive_input.html).
+    pub fn with_output(&mut self, output: OutputStream) -> &mut Confirm<'a> {
+        self.output = output;
+        self
+    }
+
+    /// Echoes a plain text transcript of the prompt to `writer`.
+    ///
+    /// The answered prompt and any error lines are written without styles,
+    /// for example to keep an audit log, while the interactive prompt
+    /// still goes to the terminal.
+    pub fn with_mirror<W: io::Write + 'a>(&mut self, writer: W) -> &mut Confirm<'a> {
+        self.options.mirror = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Calls `f` with the new number of rows and columns after the terminal
+    /// was resized.
+    ///
+    /// The prompt redraws itself at the new size, so this is only needed for
+    /// output of your own.  console does not report resizes, so they are
+    /// noticed when the prompt redraws after the next key press.
+    pub fn on_resize<F: FnMut(u16, u16) + 'a>(&mut self, f: F) -> &mut Confirm<'a> {
+        self.options.on_resize = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    /// Sets the keys the prompt reacts to.
+    ///
+    /// The default are the [default bindings](struct.KeyBindings.html).
+    /// Printable characters always answer the prompt, and the keys are not
+    /// used with [wait_for_newline](#method.wait_for_newline).
+    pub fn with_keys(&mut self, keys: &KeyBindings) -> &mut Confirm<'a> {
+        self.keys = keys.clone();
+        self
+    }
+
     #[deprecated(note = "Use with_prompt() instead", since = "0.6.0")]
     #[inline]
     pub fn with_text(&mut self, text: &str) -> &mut Confirm<'a> {
@@ -126,9 +253,10 @@ impl<'a> Confirm<'a> {
     /// If the user confirms the result is `true`, `false` if declines or default (configured in [default](#method.default)) if pushes enter.
     /// Otherwise function discards input waiting for valid one.
     ///
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<bool> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    pub fn interact(&self) -> Result<bool> {
+        self.interact_on(&self.output.term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
@@ -146,8 +274,115 @@ impl<'a> Confirm<'a> {
     /// #   Ok(())
     /// # }
     /// ```
-    pub fn interact_on(&self, term: &Term) -> io::Result<bool> {
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<bool> {
+        block_on(self._interact_on(&KeyReader::Blocking(term), false))?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::Other,
+                "Quit not allowed in this case",
+            ))
+        })
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// This method is similar to [interact_on_opt](#method.interact_on_opt) except for the fact that it does not allow selection of the terminal.
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    /// Result contains `Some(answer)` if the user answered or `None` if they cancelled with 'Esc' or Ctrl+C.
+    pub fn interact_opt(&self) -> Result<Option<bool>> {
+        self.interact_on_opt(&self.output.term())
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    ///
+    /// 'Esc' cannot be told apart from other input while
+    /// [wait_for_newline](#method.wait_for_newline) is set, so such a
+    /// prompt is only cancelled with Ctrl+C.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use dialoguer::Confirm;
+    /// use console::Term;
+    ///
+    /// # fn main() -> std::io::Result<()> {
+    /// match Confirm::new()
+    ///     .with_prompt("Do you wish to continue?")
+    ///     .interact_on_opt(&Term::stderr())?
+    /// {
+    ///     Some(true) => println!("Continuing"),
+    ///     Some(false) => println!("Stopping"),
+    ///     None => println!("Cancelled"),
+    /// }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<bool>> {
+        cancel_on_interrupt(self.interact_on_step(term))
+    }
+
+    /// Like [interact_on_opt](#method.interact_on_opt), but Ctrl+C and
+    /// [Action::Cancel](enum.Action.html) fail with `Error::Interrupted`, so
+    /// that a [Form](struct.Form.html) stops instead of going back.
+    pub(crate) fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<bool>> {
+        block_on(self._interact_on(&KeyReader::Blocking(term), true))
+    }
+
+    /// Async variant of [interact](#method.interact).
+    ///
+    /// Key presses are read on a helper thread, so waiting for the user
+    /// does not block the executor.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_async(&self) -> Result<bool> {
+        self.interact_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_opt](#method.interact_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_opt_async(&self) -> Result<Option<bool>> {
+        self.interact_on_opt_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_on](#method.interact_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_async(&self, term: &Term) -> Result<bool> {
+        self._interact_on(&KeyReader::Threaded(term), false)
+            .await?
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Quit not allowed in this case",
+                ))
+            })
+    }
+
+    /// Async variant of [interact_on_opt](#method.interact_on_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_opt_async(&self, term: &Term) -> Result<Option<bool>> {
+        cancel_on_interrupt(self._interact_on(&KeyReader::Threaded(term), true).await)
+    }
+
+    async fn _interact_on(&self, keys: &KeyReader<'_>, allow_quit: bool) -> Result<Option<bool>> {
+        let term = keys.term();
         let mut render = TermThemeRenderer::new(term, self.theme);
+        self.options.apply(&mut render);
+
+        if non_interactive::is_active(term) {
+            let rv = non_interactive::answer(&mut render, |answer| {
+                match &*answer.trim().to_lowercase() {
+                    "y" | "yes" => Ok(true),
+                    "n" | "no" => Ok(false),
+                    "" if !self.disable_default => Ok(self.default),
+                    _ => Err(format!("{:?} is neither yes nor no", answer)),
+                }
+            })?;
+
+            render.confirm_prompt_selection(&self.prompt, rv)?;
+            term.flush()?;
+
+            return Ok(Some(rv));
+        }
 
         let default = if self.show_default {
             Some(self.default)
@@ -156,23 +391,25 @@ impl<'a> Confirm<'a> {
         };
         render.confirm_prompt(&self.prompt, default)?;
 
-        term.hide_cursor()?;
+        let _cursor = HiddenCursor::new(term)?;
         term.flush()?;
 
         if self.wait_for_newline {
             // Waits for user input and for the user to hit the Enter key
             // before validation.
-            let mut input_buf = String::new();
+            if !term.is_term() {
+                return Err(Error::NotATerminal);
+            }
+
             loop {
-                io::stdin().read_line(&mut input_buf)?;
-                let rv = match &*input_buf.trim_end().to_lowercase() {
+                let input = keys.read_line(None).await?;
+                let rv = match &*input.trim_end().to_lowercase() {
                     "y" | "yes" => true,
                     "n" | "no" => false,
                     "" if !self.disable_default => self.default,
                     _ => {
                         // On invalid input re-render the user prompt.
                         render.confirm_prompt(&self.prompt, default)?;
-                        input_buf.clear();
                         continue;
                     }
                 };
@@ -180,17 +417,23 @@ impl<'a> Confirm<'a> {
                 term.show_cursor()?;
                 term.flush()?;
 
-                return Ok(rv);
+                return Ok(Some(rv));
             }
         } else {
             // Default behavior: matches continuously on every keystroke,
             // and does not wait for user to hit the Enter key.
             loop {
-                let input = term.read_char()?;
-                let rv = match input {
-                    'y' | 'Y' => true,
-                    'n' | 'N' => false,
-                    '\n' | '\r' if !self.disable_default => self.default,
+                let rv = match self.keys.translate_query(keys.read_key().await?) {
+                    Key::Char('y' | 'Y') => true,
+                    Key::Char('n' | 'N') => false,
+                    Key::Enter | Key::Char('\n' | '\r') if !self.disable_default => self.default,
+                    key @ (Key::Escape | CANCEL) if allow_quit => {
+                        term.clear_line()?;
+                        term.show_cursor()?;
+                        term.flush()?;
+
+                        return quit(key);
+                    }
                     _ => {
                         continue;
                     }
@@ -201,7 +444,7 @@ impl<'a> Confirm<'a> {
                 term.show_cursor()?;
                 term.flush()?;
 
-                return Ok(rv);
+                return Ok(Some(rv));
             }
         }
     }
@@ -0,0 +1,990 @@
+use std::{cell::RefCell, cmp::Reverse, io, mem};
+
+use crate::{
+    backend::{Backend, HiddenCursor},
+    error::{cancel_on_interrupt, Error, Result},
+    keys::{quit, KeyBindings, CANCEL},
+    non_interactive,
+    prompts::{OutputStream, PromptOptions},
+    reader::{block_on, KeyReader},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
+
+#[cfg(feature = "async")]
+use console::Term;
+use console::{Key, StyledObject};
+
+type Scorer<'a> = Box<dyn Fn(&str, &str) -> Option<i64> + 'a>;
+
+/// Renders a select prompt with fuzzy search.
+///
+/// The user types to filter the items and picks one of the remaining
+/// matches with the arrow keys.  Matches are ranked by how well they fit
+/// the query.  Interaction returns the index of the selected item in the
+/// order they appear in `item` invocation or `items` slice.
+///
+/// ## Example usage
+/// ```rust,no_run
+/// use dialoguer::FuzzySelect;
+///
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let items = vec!["foo", "bar", "baz"];
+/// let selection = FuzzySelect::new()
+///     .with_prompt("Pick an item")
+///     .items(&items)
+///     .interact()?;
+/// println!("{}", items[selection]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct FuzzySelect<'a> {
+    default: usize,
+    items: Vec<String>,
+    prompt: Option<String>,
+    initial_query: String,
+    clear: bool,
+    case_sensitive: bool,
+    highlight_matches: bool,
+    allow_create: bool,
+    max_length: Option<usize>,
+    max_scored: Option<usize>,
+    scorer: Option<Scorer<'a>>,
+    truncate_items: bool,
+    right_to_left: bool,
+    options: PromptOptions<'a>,
+    keys: KeyBindings,
+    output: OutputStream,
+    theme: &'a dyn Theme,
+}
+
+/// The items which matched a previous query.
+///
+/// The built-in matcher never matches an item for a query if it did not
+/// match a prefix of that query, so as long as the user only appends to
+/// the query these are the only items which need to be looked at.
+struct Candidates {
+    query: String,
+    indices: Vec<usize>,
+}
+
+/// An item which matches the current query.
+struct Match {
+    index: usize,
+    score: i64,
+    positions: Vec<usize>,
+}
+
+impl<'a> Default for FuzzySelect<'a> {
+    fn default() -> FuzzySelect<'a> {
+        FuzzySelect::new()
+    }
+}
+
+impl<'a> FuzzySelect<'a> {
+    /// Creates a fuzzy select prompt with the default theme.
+    pub fn new() -> FuzzySelect<'a> {
+        FuzzySelect::with_theme(&SimpleTheme)
+    }
+
+    /// Creates a fuzzy select prompt with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> FuzzySelect<'a> {
+        FuzzySelect {
+            default: 0,
+            items: vec![],
+            prompt: None,
+            initial_query: String::new(),
+            clear: true,
+            case_sensitive: false,
+            highlight_matches: true,
+            allow_create: false,
+            max_length: None,
+            max_scored: None,
+            scorer: None,
+            truncate_items: false,
+            right_to_left: false,
+            options: PromptOptions::default(),
+            keys: KeyBindings::default(),
+            output: OutputStream::Stderr,
+            theme,
+        }
+    }
+
+    /// Returns the configured prompt, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a prompt that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::FuzzySelect;
+    ///
+    /// let select = FuzzySelect::new().items(&["apple", "banana"]).build();
+    /// ```
+    pub fn build(&mut self) -> FuzzySelect<'a> {
+        mem::take(self)
+    }
+
+    /// Indicates whether the menu should be erased from the screen after interaction.
+    ///
+    /// The default is to clear the menu.
+    pub fn clear(&mut self, val: bool) -> &mut FuzzySelect<'a> {
+        self.clear = val;
+        self
+    }
+
+    /// Sets the maximum number of visible matches.
+    ///
+    /// Longer lists of matches scroll so that the highlighted match always
+    /// stays visible.  By default all matches are shown.
+    pub fn max_length(&mut self, val: usize) -> &mut FuzzySelect<'a> {
+        self.max_length = Some(val);
+        self
+    }
+
+    /// Sets the maximum number of items scored for each query.
+    ///
+    /// Finding out whether an item matches is cheap, scoring it and finding
+    /// the characters to highlight is not.  With this set only the first `n`
+    /// matching items, in the order they were added, are scored and shown.
+    /// Typing more narrows the matches down, so the remaining items show up
+    /// once the query is specific enough.  This keeps typing responsive for
+    /// very long lists.  By default all matches are scored.
+    pub fn with_max_scored(&mut self, n: usize) -> &mut FuzzySelect<'a> {
+        self.max_scored = Some(n);
+        self
+    }
+
+    /// Sets the item which is initially selected while the query is empty.
+    pub fn default(&mut self, val: usize) -> &mut FuzzySelect<'a> {
+        self.default = val;
+        self
+    }
+
+    /// Enables or disables case sensitive matching.
+    ///
+    /// Matching ignores case by default.  When enabled, a query character
+    /// only matches the exact same character, and only those characters
+    /// are highlighted.
+    pub fn case_sensitive(&mut self, val: bool) -> &mut FuzzySelect<'a> {
+        self.case_sensitive = val;
+        self
+    }
+
+    /// Enables or disables highlighting of the matched characters.
+    ///
+    /// How matches are highlighted depends on the theme.  This is enabled
+    /// by default.
+    pub fn highlight_matches(&mut self, val: bool) -> &mut FuzzySelect<'a> {
+        self.highlight_matches = val;
+        self
+    }
+
+    /// Enables or disables the option to create a new item.
+    ///
+    /// When enabled and the query is not empty, an entry to create an item
+    /// named after the query is offered above the matches, unless an item
+    /// with exactly that name exists.  If the user picks it, the returned
+    /// index is the number of items, which is one past the last valid
+    /// index, and [interact_with_query](#method.interact_with_query)
+    /// returns the name as the query.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use dialoguer::FuzzySelect;
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut tags = vec!["bug".to_string(), "feature".to_string()];
+    /// let (idx, query) = FuzzySelect::new()
+    ///     .items(&tags)
+    ///     .allow_create(true)
+    ///     .interact_with_query()?;
+    ///
+    /// if idx == tags.len() {
+    ///     tags.push(query);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn allow_create(&mut self, val: bool) -> &mut FuzzySelect<'a> {
+        self.allow_create = val;
+        self
+    }
+
+    /// Sets a custom scoring function.
+    ///
+    /// The function is called with an item and the current query and
+    /// returns the score of the item, higher is better, or `None` to filter
+    /// it out.  Matches are sorted by this score.  The highlighted
+    /// characters are still the ones found by the built-in matcher, if it
+    /// matches the item at all.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use dialoguer::FuzzySelect;
+    ///
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// // only keep items starting with the query, shortest first
+    /// let selection = FuzzySelect::new()
+    ///     .items(&["add", "address", "remove"])
+    ///     .with_scorer(|item, query| {
+    ///         if item.starts_with(query) {
+    ///             Some(-(item.len() as i64))
+    ///         } else {
+    ///             None
+    ///         }
+    ///     })
+    ///     .interact()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_scorer<F>(&mut self, scorer: F) -> &mut FuzzySelect<'a>
+    where
+        F: Fn(&str, &str) -> Option<i64> + 'a,
+    {
+        self.scorer = Some(Box::new(scorer));
+        self
+    }
+
+    /// Add a single item to the selector.
+    pub fn item<T: ToString>(&mut self, item: T) -> &mut FuzzySelect<'a> {
+        self.items.push(item.to_string());
+        self
+    }
+
+    /// Adds multiple items to the selector.
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut FuzzySelect<'a> {
+        for item in items {
+            self.items.push(item.to_string());
+        }
+        self
+    }
+
+    /// Prefaces the menu with a prompt.
+    ///
+    /// When a prompt is set the system also prints out a confirmation after
+    /// the selection.
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut FuzzySelect<'a> {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Overrides the prompt prefix of the theme for this prompt.
+    ///
+    /// Themes without a prompt prefix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_prefix(&mut self, prefix: StyledObject<String>) -> &mut FuzzySelect<'a> {
+        self.options.prompt_prefix = Some(prefix);
+        self
+    }
+
+    /// Overrides the prompt suffix of the theme for this prompt.
+    ///
+    /// Themes without a prompt suffix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_suffix(&mut self, suffix: StyledObject<String>) -> &mut FuzzySelect<'a> {
+        self.options.prompt_suffix = Some(suffix);
+        self
+    }
+
+    /// Overrides the separator between the prompt and its answer once the
+    /// prompt is done, for example `" → "`.
+    pub fn with_prompt_separator(
+        &mut self,
+        separator: StyledObject<String>,
+    ) -> &mut FuzzySelect<'a> {
+        self.options.prompt_separator = Some(separator);
+        self
+    }
+
+    /// Shows the step of this prompt in a multi step flow, such as
+    /// `(step 2/5)`.
+    ///
+    /// `current` starts at 1.  The theme decides how the badge looks.
+    pub fn with_step(&mut self, current: usize, total: usize) -> &mut FuzzySelect<'a> {
+        self.options.step = Some((current, total));
+        self
+    }
+
+    /// Indents every line of the prompt by `indent` spaces.
+    ///
+    /// This nests the prompt in surrounding output.  The default is no
+    /// indent.
+    pub fn with_indent(&mut self, indent: usize) -> &mut FuzzySelect<'a> {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Indicates whether to report the answered prompt once it is done.
+    ///
+    /// The interactive prompt is still cleared as usual.  The default is
+    /// `true`.
+    pub fn report(&mut self, val: bool) -> &mut FuzzySelect<'a> {
+        self.options.report = val;
+        self
+    }
+
+    /// Sets where [interact](#method.interact) and the other methods which
+    /// do not take a terminal render the prompt.
+    ///
+    /// The default is stderr, which keeps the prompt out of the output when
+    /// stdout is piped into another program.  Prompts rendered on stdout
+    /// become part of that output instead, and they fail with
+    /// [Error::NotATerminal](enum.Error.html#variant.NotATerminal) if
+    /// stdout is not a terminal, unless answers come from
+    /// [set_non_interactive_input](fn.set_non_interactive_input.html).
+    pub fn with_output(&mut self, output: OutputStream) -> &mut FuzzySelect<'a> {
+        self.output = output;
+        self
+    }
+
+    /// Echoes a plain text transcript of the prompt to `writer`.
+    ///
+    /// The answered prompt and any error lines are written without styles,
+    /// for example to keep an audit log, while the interactive prompt
+    /// still goes to the terminal.
+    pub fn with_mirror<W: io::Write + 'a>(&mut self, writer: W) -> &mut FuzzySelect<'a> {
+        self.options.mirror = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Calls `f` with the new number of rows and columns after the terminal
+    /// was resized.
+    ///
+    /// The prompt redraws itself at the new size, so this is only needed for
+    /// output of your own.  console does not report resizes, so they are
+    /// noticed when the prompt redraws after the next key press.
+    pub fn on_resize<F: FnMut(u16, u16) + 'a>(&mut self, f: F) -> &mut FuzzySelect<'a> {
+        self.options.on_resize = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    /// Sets the keys the prompt reacts to.
+    ///
+    /// The default are the [default bindings](struct.KeyBindings.html).
+    /// Printable characters always go to the search query.
+    pub fn with_keys(&mut self, keys: &KeyBindings) -> &mut FuzzySelect<'a> {
+        self.keys = keys.clone();
+        self
+    }
+
+    /// Truncates items wider than the terminal.
+    ///
+    /// Truncated items end with the ellipsis of the theme.  The default is
+    /// to let long items wrap.
+    pub fn truncate_items(&mut self, val: bool) -> &mut FuzzySelect<'a> {
+        self.truncate_items = val;
+        self
+    }
+
+    /// Lays the prompt out for right-to-left languages.
+    ///
+    /// Lines are aligned to the right and the symbols of the theme are put
+    /// on the other side of the prompt and the items.  The default is
+    /// left-to-right.
+    pub fn right_to_left(&mut self, val: bool) -> &mut FuzzySelect<'a> {
+        self.right_to_left = val;
+        self
+    }
+
+    /// Prefills the query.
+    ///
+    /// The items are filtered by it right away and the cursor is placed at
+    /// its end, so the user can refine it.
+    pub fn with_initial_query<S: Into<String>>(&mut self, query: S) -> &mut FuzzySelect<'a> {
+        self.initial_query = query.into();
+        self
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// Typed characters are added to the query at the cursor, which can be
+    /// moved with the left and right arrows.  Up and down move through the
+    /// matches and enter selects the highlighted one.  The dialog is
+    /// rendered on stderr, unless [with_output](#method.with_output) says
+    /// otherwise.
+    pub fn interact(&self) -> Result<usize> {
+        self.interact_on(&self.output.term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// Like [interact](#method.interact), but the user can cancel with
+    /// 'Esc' or Ctrl+C, in which case `None` is returned.
+    pub fn interact_opt(&self) -> Result<Option<usize>> {
+        self.interact_on_opt(&self.output.term())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<usize> {
+        Ok(self.interact_on_with_query(term)?.0)
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        cancel_on_interrupt(self.interact_on_step(term))
+    }
+
+    /// Like [interact_on_opt](#method.interact_on_opt), but Ctrl+C and
+    /// [Action::Cancel](enum.Action.html) fail with `Error::Interrupted`, so
+    /// that a [Form](struct.Form.html) stops instead of going back.
+    pub(crate) fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        block_on(self.interact_on_opt_with(&KeyReader::Blocking(term), true))
+    }
+
+    /// Async variant of [interact](#method.interact).
+    ///
+    /// Key presses are read on a helper thread, so waiting for the user
+    /// does not block the executor.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_async(&self) -> Result<usize> {
+        self.interact_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_opt](#method.interact_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_opt_async(&self) -> Result<Option<usize>> {
+        self.interact_on_opt_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_on](#method.interact_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_async(&self, term: &Term) -> Result<usize> {
+        self.interact_on_opt_with(&KeyReader::Threaded(term), false)
+            .await?
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Quit not allowed in this case",
+                ))
+            })
+    }
+
+    /// Async variant of [interact_on_opt](#method.interact_on_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_opt_async(&self, term: &Term) -> Result<Option<usize>> {
+        cancel_on_interrupt(
+            self.interact_on_opt_with(&KeyReader::Threaded(term), true)
+                .await,
+        )
+    }
+
+    /// Enables user interaction and returns the result and the query.
+    ///
+    /// Like [interact](#method.interact), but also returns the query as
+    /// the user typed it when selecting the item.
+    pub fn interact_with_query(&self) -> Result<(usize, String)> {
+        self.interact_on_with_query(&self.output.term())
+    }
+
+    /// Like [interact_with_query](#method.interact_with_query) but allows a specific terminal to be set.
+    pub fn interact_on_with_query(&self, term: &dyn Backend) -> Result<(usize, String)> {
+        let (selected, query) =
+            block_on(self._interact_on(&KeyReader::Blocking(term), false, false))?.ok_or_else(
+                || {
+                    Error::Io(io::Error::new(
+                        io::ErrorKind::Other,
+                        "Quit not allowed in this case",
+                    ))
+                },
+            )?;
+        Ok((selected[0], query))
+    }
+
+    /// Enables user interaction for selecting multiple items.
+    ///
+    /// Works like [interact](#method.interact), except that tab checks or
+    /// unchecks the highlighted match.  Changing the query keeps the checked
+    /// items, even if they are filtered out.  Enter returns the indices of
+    /// all checked items in the order they were added.  The option to
+    /// create an item is not offered in this mode.
+    pub fn interact_multi(&self) -> Result<Vec<usize>> {
+        self.interact_multi_on(&self.output.term())
+    }
+
+    /// Like [interact_multi](#method.interact_multi) but allows a specific terminal to be set.
+    pub fn interact_multi_on(&self, term: &dyn Backend) -> Result<Vec<usize>> {
+        block_on(self._interact_on(&KeyReader::Blocking(term), true, false))?
+            .map(|(selected, _)| selected)
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Quit not allowed in this case",
+                ))
+            })
+    }
+
+    async fn interact_on_opt_with(
+        &self,
+        keys: &KeyReader<'_>,
+        allow_quit: bool,
+    ) -> Result<Option<usize>> {
+        let selected = self._interact_on(keys, false, allow_quit).await?;
+        Ok(selected.map(|(selected, _)| selected[0]))
+    }
+
+    /// Returns the items matching `query`, best match first.
+    ///
+    /// `candidates` holds the items which matched the previous query and is
+    /// updated for the next call.
+    fn filter(&self, query: &str, candidates: &mut Option<Candidates>) -> Vec<Match> {
+        let indices: Vec<usize> = match candidates.take() {
+            // a custom scorer may match anything, so there is nothing to narrow down
+            _ if self.scorer.is_some() => (0..self.items.len()).collect(),
+            Some(prev) if query.starts_with(&prev.query) => prev
+                .indices
+                .into_iter()
+                .filter(|&idx| is_subsequence(&self.items[idx], query, self.case_sensitive))
+                .collect(),
+            _ => (0..self.items.len())
+                .filter(|&idx| is_subsequence(&self.items[idx], query, self.case_sensitive))
+                .collect(),
+        };
+
+        let mut matches: Vec<_> = indices
+            .iter()
+            .filter_map(|&index| {
+                self.match_item(&self.items[index], query)
+                    .map(|(score, positions)| Match {
+                        index,
+                        score,
+                        positions,
+                    })
+            })
+            .take(self.max_scored.unwrap_or(usize::MAX))
+            .collect();
+
+        if self.scorer.is_none() {
+            *candidates = Some(Candidates {
+                query: query.to_string(),
+                indices,
+            });
+        }
+
+        // items with the same score keep the order they were added in
+        matches.sort_unstable_by_key(|m| (Reverse(m.score), m.index));
+        matches
+    }
+
+    /// Checks whether an item is named exactly like `query`.
+    fn has_item(&self, query: &str) -> bool {
+        self.items.iter().any(|item| {
+            if self.case_sensitive {
+                item == query
+            } else {
+                item.to_lowercase() == query.to_lowercase()
+            }
+        })
+    }
+
+    /// Scores a single item and finds the characters to highlight.
+    fn match_item(&self, item: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+        let found = fuzzy_match(item, query, self.case_sensitive);
+
+        match self.scorer {
+            Some(ref scorer) => scorer(item, query).map(|score| {
+                (
+                    score,
+                    found.map_or_else(Vec::new, |(_, positions)| positions),
+                )
+            }),
+            None => found,
+        }
+    }
+
+    async fn _interact_on(
+        &self,
+        keys: &KeyReader<'_>,
+        multi: bool,
+        allow_quit: bool,
+    ) -> Result<Option<(Vec<usize>, String)>> {
+        let term = keys.term();
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        self.options.apply(&mut render);
+        render.set_truncate_items(self.truncate_items);
+        render.set_right_to_left(self.right_to_left);
+        let prompt = self.prompt.as_deref().unwrap_or("");
+        let mut query: Vec<char> = self.initial_query.chars().collect();
+        let mut cursor = query.len();
+        let mut sel = self.default;
+        let mut offset = 0;
+        let mut candidates = None;
+        let mut checked = vec![false; self.items.len()];
+
+        if non_interactive::is_active(term) {
+            let (selected, query) = non_interactive::answer(&mut render, |answer| {
+                let selected = if multi {
+                    non_interactive::find_items(&self.items, answer)?
+                } else {
+                    match non_interactive::find_item(&self.items, answer) {
+                        Ok(idx) => vec![idx],
+                        Err(_) if answer.trim().is_empty() && self.default < self.items.len() => {
+                            vec![self.default]
+                        }
+                        Err(_) if self.allow_create && !answer.trim().is_empty() => {
+                            vec![self.items.len()]
+                        }
+                        Err(err) => return Err(err),
+                    }
+                };
+
+                Ok((selected, answer.to_string()))
+            })?;
+
+            if let Some(ref prompt) = self.prompt {
+                if multi {
+                    let names: Vec<_> = selected.iter().map(|&i| self.items[i].as_str()).collect();
+                    render.multi_select_prompt_selection(prompt, &names[..])?;
+                } else {
+                    let sel = self.items.get(selected[0]).unwrap_or(&query);
+                    render.select_prompt_selection(prompt, sel)?;
+                }
+            }
+
+            term.flush()?;
+
+            return Ok(Some((selected, query)));
+        }
+
+        let _cursor = HiddenCursor::new(term)?;
+
+        loop {
+            let query_str: String = query.iter().collect();
+            let matches = self.filter(&query_str, &mut candidates);
+            let create =
+                self.allow_create && !multi && !query_str.is_empty() && !self.has_item(&query_str);
+            // the create entry, if any, comes first
+            let skip = create as usize;
+            let entries = matches.len() + skip;
+
+            if sel >= entries {
+                sel = 0;
+            }
+
+            let capacity = self.max_length.unwrap_or(entries).max(1);
+
+            if sel < offset {
+                offset = sel;
+            } else if sel >= offset + capacity {
+                offset = sel + 1 - capacity;
+            }
+
+            render.fuzzy_select_prompt(prompt, &query_str, cursor)?;
+
+            for idx in (offset..entries).take(capacity) {
+                let m = match idx.checked_sub(skip) {
+                    Some(pos) => &matches[pos],
+                    None => {
+                        render.fuzzy_select_prompt_create_item(&query_str, sel == idx)?;
+                        continue;
+                    }
+                };
+                let item = &self.items[m.index];
+                let positions = if self.highlight_matches {
+                    &m.positions[..]
+                } else {
+                    &[]
+                };
+
+                if multi {
+                    render.fuzzy_multi_select_prompt_item(
+                        item,
+                        checked[m.index],
+                        sel == idx,
+                        positions,
+                    )?;
+                } else {
+                    render.fuzzy_select_prompt_item(item, sel == idx, positions)?;
+                }
+            }
+
+            term.flush()?;
+
+            match self.keys.translate_query(keys.read_key().await?) {
+                Key::ArrowUp if entries > 0 => {
+                    sel = (sel + entries - 1) % entries;
+                }
+                Key::ArrowDown if entries > 0 => {
+                    sel = (sel + 1) % entries;
+                }
+                Key::ArrowLeft if cursor > 0 => cursor -= 1,
+                Key::ArrowRight if cursor < query.len() => cursor += 1,
+                Key::Home => cursor = 0,
+                Key::End => cursor = query.len(),
+                Key::Backspace if cursor > 0 => {
+                    cursor -= 1;
+                    query.remove(cursor);
+                    sel = 0;
+                }
+                Key::Del if cursor < query.len() => {
+                    query.remove(cursor);
+                    sel = 0;
+                }
+                key @ (Key::Escape | CANCEL) if allow_quit => {
+                    if self.clear {
+                        render.clear_preserve_prompt()?;
+                    }
+
+                    term.show_cursor()?;
+                    term.flush()?;
+
+                    return quit(key);
+                }
+                Key::Tab if multi && entries > 0 => {
+                    let index = matches[sel].index;
+                    checked[index] = !checked[index];
+                }
+                Key::Enter if multi => {
+                    let selected: Vec<_> = (0..self.items.len()).filter(|&i| checked[i]).collect();
+
+                    if self.clear {
+                        render.clear_preserve_prompt()?;
+                    }
+
+                    if let Some(ref prompt) = self.prompt {
+                        let names: Vec<_> =
+                            selected.iter().map(|&i| self.items[i].as_str()).collect();
+                        render.multi_select_prompt_selection(prompt, &names[..])?;
+                    }
+
+                    term.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some((selected, query_str)));
+                }
+                Key::Enter if entries > 0 => {
+                    let index = match sel.checked_sub(skip) {
+                        Some(sel) => matches[sel].index,
+                        None => self.items.len(),
+                    };
+
+                    if self.clear {
+                        render.clear_preserve_prompt()?;
+                    }
+
+                    if let Some(ref prompt) = self.prompt {
+                        let sel = self.items.get(index).unwrap_or(&query_str);
+                        render.select_prompt_selection(prompt, sel)?;
+                    }
+
+                    term.show_cursor()?;
+                    term.flush()?;
+
+                    return Ok(Some((vec![index], query_str)));
+                }
+                Key::Char(chr) if !chr.is_control() => {
+                    query.insert(cursor, chr);
+                    cursor += 1;
+                    sel = 0;
+                }
+                _ => {}
+            }
+
+            render.clear_preserve_prompt()?;
+        }
+    }
+}
+
+/// Matches `query` as a subsequence of `text`.
+///
+/// Returns the score of the match, higher is better, and the positions of
+/// the matched characters in `text`.  An empty query matches everything.
+fn fuzzy_match(text: &str, query: &str, case_sensitive: bool) -> Option<(i64, Vec<usize>)> {
+    let text: Vec<char> = text.chars().collect();
+    let query: Vec<char> = query.chars().collect();
+    let eq = |a, b| chars_eq(a, b, case_sensitive);
+
+    if query.is_empty() {
+        return Some((0, vec![]));
+    }
+
+    // find where the first complete match ends, then walk back from there
+    // to find the shortest match ending at the same position
+    let mut remaining = query.iter().peekable();
+    let end = text.iter().position(|&chr| match remaining.peek() {
+        Some(&&next) if eq(chr, next) => {
+            remaining.next();
+            remaining.peek().is_none()
+        }
+        _ => false,
+    })?;
+
+    let mut positions = Vec::with_capacity(query.len());
+    let mut remaining = query.iter().rev().peekable();
+
+    for idx in (0..=end).rev() {
+        match remaining.peek() {
+            Some(&&next) if eq(text[idx], next) => {
+                positions.push(idx);
+                remaining.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    positions.reverse();
+
+    Some((score(&text, &positions), positions))
+}
+
+/// Checks whether `query` is a subsequence of `text` without scoring it.
+fn is_subsequence(text: &str, query: &str, case_sensitive: bool) -> bool {
+    let mut remaining = query.chars().peekable();
+
+    for chr in text.chars() {
+        match remaining.peek() {
+            Some(&next) if chars_eq(chr, next, case_sensitive) => {
+                remaining.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    remaining.peek().is_none()
+}
+
+fn chars_eq(a: char, b: char, case_sensitive: bool) -> bool {
+    if case_sensitive {
+        a == b
+    } else {
+        a.to_lowercase().eq(b.to_lowercase())
+    }
+}
+
+/// Scores matched positions, preferring consecutive matches and matches at
+/// the start of words.
+fn score(text: &[char], positions: &[usize]) -> i64 {
+    let mut score = 0;
+    let mut prev: Option<usize> = None;
+
+    for &pos in positions {
+        score += 16;
+
+        let word_start = pos == 0
+            || !text[pos - 1].is_alphanumeric()
+            || (text[pos - 1].is_lowercase() && text[pos].is_uppercase());
+
+        if word_start {
+            score += 8;
+        }
+
+        match prev {
+            Some(prev) if pos == prev + 1 => score += 8,
+            Some(prev) => score -= (pos - prev - 1) as i64,
+            None => {}
+        }
+
+        prev = Some(pos);
+    }
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match_positions() {
+        assert_eq!(fuzzy_match("foobar", "fb", false), Some((38, vec![0, 3])));
+        assert_eq!(fuzzy_match("foobar", "", false), Some((0, vec![])));
+        assert_eq!(fuzzy_match("foobar", "bf", false), None);
+        // the shortest match ending at the first complete match is used
+        assert_eq!(fuzzy_match("abab", "ab", false).unwrap().1, vec![0, 1]);
+        assert_eq!(fuzzy_match("aXab", "ab", false).unwrap().1, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_fuzzy_match_case_sensitivity() {
+        assert!(fuzzy_match("HttpClient", "hc", false).is_some());
+        assert!(fuzzy_match("HttpClient", "hc", true).is_none());
+        assert_eq!(fuzzy_match("httpClient", "C", true).unwrap().1, vec![4]);
+        assert_eq!(fuzzy_match("cHttpClient", "c", false).unwrap().1, vec![0]);
+    }
+
+    #[test]
+    fn test_filter_ranks_better_matches_first() {
+        let mut select = FuzzySelect::new();
+        select.items(&["a_x_b", "ab", "xyz"]);
+
+        let matches: Vec<_> = select
+            .filter("ab", &mut None)
+            .iter()
+            .map(|m| m.index)
+            .collect();
+        assert_eq!(matches, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_filter_uses_custom_scorer() {
+        let mut select = FuzzySelect::new();
+        select
+            .items(&["address", "add", "remove"])
+            .with_scorer(|item, query| {
+                if item.starts_with(query) {
+                    Some(-(item.len() as i64))
+                } else {
+                    None
+                }
+            });
+
+        let matches = select.filter("ad", &mut None);
+        let indices: Vec<_> = matches.iter().map(|m| m.index).collect();
+        assert_eq!(indices, vec![1, 0]);
+        assert_eq!(matches[0].score, -3);
+        assert_eq!(matches[0].positions, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_filter_orders_ties_by_index() {
+        let mut select = FuzzySelect::new();
+        select.items(&["xa", "ya", "abc", "za"]);
+
+        let ties: Vec<_> = select
+            .filter("a", &mut None)
+            .iter()
+            .map(|m| m.index)
+            .collect();
+        assert_eq!(ties, vec![2, 0, 1, 3]);
+
+        let mut candidates = None;
+        for query in &["a", "a", "", "a"] {
+            let matches: Vec<_> = select
+                .filter(query, &mut candidates)
+                .iter()
+                .filter(|m| m.index != 2)
+                .map(|m| m.index)
+                .collect();
+            assert_eq!(matches, vec![0, 1, 3]);
+        }
+    }
+
+    #[test]
+    fn test_filter_narrows_previous_matches() {
+        let mut select = FuzzySelect::new();
+        select.items(&["abc", "axc", "xyz"]);
+
+        let mut candidates = None;
+        assert_eq!(select.filter("a", &mut candidates).len(), 2);
+        assert_eq!(candidates.as_ref().unwrap().indices, vec![0, 1]);
+
+        let matches = select.filter("ab", &mut candidates);
+        assert_eq!(matches.iter().map(|m| m.index).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(candidates.as_ref().unwrap().indices, vec![0]);
+
+        // a query which does not extend the previous one starts over
+        assert_eq!(select.filter("x", &mut candidates).len(), 2);
+    }
+
+    #[test]
+    fn test_filter_limits_scored_items() {
+        let mut select = FuzzySelect::new();
+        select.items(&["a1", "b", "a2", "a3"]).with_max_scored(2);
+
+        let matches = select.filter("a", &mut None);
+        let mut indices: Vec<_> = matches.iter().map(|m| m.index).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, vec![0, 2]);
+        assert_eq!(select.filter("a3", &mut None)[0].index, 3);
+    }
+}
@@ -1,15 +1,34 @@
 use std::{
+    cell::RefCell,
     fmt::{Debug, Display},
-    io, iter,
+    io, iter, mem,
     str::FromStr,
 };
 
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
 use crate::{
+    backend::Backend,
+    completion::Completion,
+    error::{cancel_on_interrupt, Error, Result},
+    history::History,
+    keys::{quit, KeyBindings, CANCEL},
+    non_interactive,
+    prompts::{OutputStream, PromptOptions},
+    reader::{block_on, KeyReader},
     theme::{SimpleTheme, TermThemeRenderer, Theme},
     validate::Validator,
 };
 
-use console::{Key, Term};
+#[cfg(feature = "async")]
+use console::Term;
+use console::{Key, StyledObject};
+
+type ValidatorCallback<'a, T> = Box<dyn Fn(&T) -> Option<String> + 'a>;
+#[cfg(feature = "async")]
+type AsyncValidatorCallback<'a, T> =
+    Box<dyn Fn(&T) -> Pin<Box<dyn Future<Output = Option<String>> + 'a>> + 'a>;
 
 /// Renders an input prompt.
 ///
@@ -42,9 +61,20 @@ pub struct Input<'a, T> {
     default: Option<T>,
     show_default: bool,
     initial_text: Option<String>,
+    options: PromptOptions<'a>,
+    keys: KeyBindings,
+    output: OutputStream,
     theme: &'a dyn Theme,
     permit_empty: bool,
-    validator: Option<Box<dyn Fn(&T) -> Option<String> + 'a>>,
+    validators: Vec<ValidatorCallback<'a, T>>,
+    report_all_errors: bool,
+    max_attempts: Option<usize>,
+    spinner_on_wait: bool,
+    history: Option<RefCell<&'a mut dyn History<T>>>,
+    completion: Option<&'a dyn Completion>,
+    ghost_suggestions: bool,
+    #[cfg(feature = "async")]
+    async_validator: Option<AsyncValidatorCallback<'a, T>>,
 }
 
 impl<'a, T> Default for Input<'a, T>
@@ -74,18 +104,140 @@ where
             default: None,
             show_default: true,
             initial_text: None,
+            options: PromptOptions::default(),
+            keys: KeyBindings::default(),
+            output: OutputStream::Stderr,
             theme,
             permit_empty: false,
-            validator: None,
+            validators: Vec::new(),
+            report_all_errors: false,
+            max_attempts: None,
+            spinner_on_wait: false,
+            history: None,
+            completion: None,
+            ghost_suggestions: false,
+            #[cfg(feature = "async")]
+            async_validator: None,
         }
     }
 
+    /// Returns the configured prompt, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a prompt that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::Input;
+    ///
+    /// let input = Input::<String>::new().with_prompt("Name").build();
+    /// ```
+    pub fn build(&mut self) -> Input<'a, T> {
+        mem::take(self)
+    }
+
     /// Sets the input prompt.
     pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Input<'a, T> {
         self.prompt = prompt.into();
         self
     }
 
+    /// Overrides the prompt prefix of the theme for this prompt.
+    ///
+    /// Themes without a prompt prefix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_prefix(&mut self, prefix: StyledObject<String>) -> &mut Input<'a, T> {
+        self.options.prompt_prefix = Some(prefix);
+        self
+    }
+
+    /// Overrides the prompt suffix of the theme for this prompt.
+    ///
+    /// Themes without a prompt suffix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_suffix(&mut self, suffix: StyledObject<String>) -> &mut Input<'a, T> {
+        self.options.prompt_suffix = Some(suffix);
+        self
+    }
+
+    /// Overrides the separator between the prompt and its answer once the
+    /// prompt is done, for example `" → "`.
+    pub fn with_prompt_separator(&mut self, separator: StyledObject<String>) -> &mut Input<'a, T> {
+        self.options.prompt_separator = Some(separator);
+        self
+    }
+
+    /// Shows the step of this prompt in a multi step flow, such as
+    /// `(step 2/5)`.
+    ///
+    /// `current` starts at 1.  The theme decides how the badge looks.
+    pub fn with_step(&mut self, current: usize, total: usize) -> &mut Input<'a, T> {
+        self.options.step = Some((current, total));
+        self
+    }
+
+    /// Indents every line of the prompt by `indent` spaces.
+    ///
+    /// This nests the prompt in surrounding output.  The default is no
+    /// indent.
+    pub fn with_indent(&mut self, indent: usize) -> &mut Input<'a, T> {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Indicates whether to report the answered prompt once it is done.
+    ///
+    /// The interactive prompt is still cleared as usual.  The default is
+    /// `true`.
+    pub fn report(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.options.report = val;
+        self
+    }
+
+    /// Sets where [interact](#method.interact) and the other methods which
+    /// do not take a terminal render the prompt.
+    ///
+    /// The default is stderr, which keeps the prompt out of the output when
+    /// stdout is piped into another program.  Prompts rendered on stdout
+    /// become part of that output instead, and they fail with
+    /// [Error::NotATerminal](enum.Error.html#variant.NotATerminal) if
+    /// stdout is not a terminal, unless answers come from
+    /// [set_non_interactive_input](fn.set_non_interactive_input.html).
+    pub fn with_output(&mut self, output: OutputStream) -> &mut Input<'a, T> {
+        self.output = output;
+        self
+    }
+
+    /// Echoes a plain text transcript of the prompt to `writer`.
+    ///
+    /// The answered prompt and any error lines are written without styles,
+    /// for example to keep an audit log, while the interactive prompt
+    /// still goes to the terminal.
+    pub fn with_mirror<W: io::Write + 'a>(&mut self, writer: W) -> &mut Input<'a, T> {
+        self.options.mirror = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Calls `f` with the new number of rows and columns after the terminal
+    /// was resized.
+    ///
+    /// The prompt redraws itself at the new size, so this is only needed for
+    /// output of your own.  console does not report resizes, so they are
+    /// noticed when the prompt redraws after the next key press.
+    pub fn on_resize<F: FnMut(u16, u16) + 'a>(&mut self, f: F) -> &mut Input<'a, T> {
+        self.options.on_resize = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    /// Sets the keys the prompt reacts to.
+    ///
+    /// The default are the [default bindings](struct.KeyBindings.html).
+    /// Printable characters always go to the text, and the keys are only
+    /// used by [interact_text](#method.interact_text).
+    pub fn with_keys(&mut self, keys: &KeyBindings) -> &mut Input<'a, T> {
+        self.keys = keys.clone();
+        self
+    }
+
     /// Sets initial text that user can accept or erase.
     pub fn with_initial_text<S: Into<String>>(&mut self, val: S) -> &mut Input<'a, T> {
         self.initial_text = Some(val.into());
@@ -123,6 +275,9 @@ where
 
     /// Registers a validator.
     ///
+    /// This can be called multiple times to register several validators,
+    /// see [report_all_errors](#method.report_all_errors).
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -144,38 +299,286 @@ where
         V: Validator<T> + 'a,
         T: 'a,
     {
-        let old_validator_func = self.validator.take();
+        self.validators
+            .push(Box::new(move |value: &T| -> Option<String> {
+                match validator.validate(value) {
+                    Ok(()) => None,
+                    Err(err) => Some(err.to_string()),
+                }
+            }));
+
+        self
+    }
 
-        self.validator = Some(Box::new(move |value: &T| -> Option<String> {
-            if let Some(old) = old_validator_func.as_ref() {
-                if let Some(err) = old(value) {
-                    return Some(err);
+    /// Registers a boxed validator.
+    ///
+    /// This works like [validate_with](#method.validate_with) but takes
+    /// validators that are only known at runtime, for example from a list.
+    ///
+    /// ```no_run
+    /// # use dialoguer::{validators, Input, Validator};
+    /// let checks: Vec<Box<dyn Validator<String, Err = String>>> = vec![
+    ///     Box::new(validators::non_empty()),
+    ///     Box::new(validators::email()),
+    /// ];
+    ///
+    /// let mut input = Input::<String>::new();
+    /// for check in checks {
+    ///     input.add_validator(check);
+    /// }
+    /// ```
+    pub fn add_validator<E>(
+        &mut self,
+        validator: Box<dyn Validator<T, Err = E> + 'a>,
+    ) -> &mut Input<'a, T>
+    where
+        E: Debug + Display + 'a,
+        T: 'a,
+    {
+        self.validators
+            .push(Box::new(move |value: &T| -> Option<String> {
+                validator.validate(value).err().map(|err| err.to_string())
+            }));
+
+        self
+    }
+
+    /// Indicates whether to report the errors of all failing validators.
+    ///
+    /// Validators run in the order they were registered.  By default the
+    /// first failing validator stops validation and only its error is
+    /// shown.  With this set every validator runs and each error is shown
+    /// on its own line.  Async validators still only run if all others
+    /// pass.
+    pub fn report_all_errors(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.report_all_errors = val;
+        self
+    }
+
+    /// Registers a validator which returns a future, for example to ask a
+    /// server whether a user name is taken.
+    ///
+    /// The prompt shows a spinner while the future runs and asks again if
+    /// it fails.  Async validators run after the ones registered with
+    /// [validate_with](#method.validate_with) and only in
+    /// [interact_text_async](#method.interact_text_async) and
+    /// [interact_async](#method.interact_async); the blocking methods
+    /// return an error instead.  This requires the `async` feature.
+    ///
+    /// ```rust,no_run
+    /// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn is_taken(name: String) -> bool { false }
+    /// use dialoguer::Input;
+    ///
+    /// let name: String = Input::new()
+    ///     .with_prompt("User name")
+    ///     .validate_with_async(|name: &String| {
+    ///         let name = name.clone();
+    ///         async move {
+    ///             if is_taken(name).await {
+    ///                 Err("This name is taken")
+    ///             } else {
+    ///                 Ok(())
+    ///             }
+    ///         }
+    ///     })
+    ///     .interact_text_async()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn validate_with_async<V, F, E>(&mut self, validator: V) -> &mut Input<'a, T>
+    where
+        V: Fn(&T) -> F + 'a,
+        F: Future<Output = Result<(), E>> + 'a,
+        E: Display,
+        T: 'a,
+    {
+        let old_validator_func = self.async_validator.take();
+
+        self.async_validator = Some(Box::new(move |value: &T| {
+            let old = old_validator_func.as_ref().map(|old| old(value));
+            let new = validator(value);
+
+            Box::pin(async move {
+                if let Some(old) = old {
+                    if let Some(err) = old.await {
+                        return Some(err);
+                    }
                 }
-            }
 
-            match validator.validate(value) {
-                Ok(()) => None,
-                Err(err) => Some(err.to_string()),
-            }
+                new.await.err().map(|err| err.to_string())
+            })
         }));
 
         self
     }
 
+    /// Sets how often the user may enter an invalid value.
+    ///
+    /// Once that many inputs failed to parse or validate, the prompt
+    /// returns [Error::ValidationExhausted](enum.Error.html#variant.ValidationExhausted)
+    /// instead of asking again.  By default the prompt asks until the input
+    /// is valid.
+    pub fn max_attempts(&mut self, val: usize) -> &mut Input<'a, T> {
+        self.max_attempts = Some(val);
+        self
+    }
+
+    /// Shows a spinner with the prompt while a slow validator runs.
+    ///
+    /// The spinner only appears if validating takes longer than a moment.
+    /// The default is `false`.
+    pub fn with_spinner_on_wait(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.spinner_on_wait = val;
+        self
+    }
+
+    /// Enables a history of the submitted values.
+    ///
+    /// Every value the user enters is written to the history.  In
+    /// [interact_text](#method.interact_text) the up and down arrow keys
+    /// go through the earlier entries.
+    pub fn with_history<H: History<T>>(&mut self, history: &'a mut H) -> &mut Input<'a, T> {
+        self.history = Some(RefCell::new(history));
+        self
+    }
+
+    /// Enables completion of the input.
+    ///
+    /// In [interact_text](#method.interact_text) the tab key completes the
+    /// input.  If that does not get any further, pressing tab twice lists
+    /// the candidates of the completion below the input, and pressing it
+    /// again cycles through them.
+    pub fn with_completion<C: Completion>(&mut self, completion: &'a C) -> &mut Input<'a, T> {
+        self.completion = Some(completion);
+        self
+    }
+
+    /// Indicates whether to show the only completion of the input as ghost
+    /// text after the cursor.
+    ///
+    /// The ghost text is dimmed and only shown with the cursor at the end
+    /// of the input.  Pressing tab or the right arrow key accepts it.
+    /// This has no effect without [with_completion](#method.with_completion).
+    /// The default is `false`.
+    pub fn ghost_suggestions(&mut self, val: bool) -> &mut Input<'a, T> {
+        self.ghost_suggestions = val;
+        self
+    }
+
     /// Enables the user to enter a printable ascii sequence and returns the result.
     ///
     /// Its difference from [`interact`](#method.interact) is that it only allows ascii characters, backspace and enter keys,
     /// while [`interact`](#method.interact) allows virtually any character to be used e.g arrow keys.
     ///
-    /// The dialog is rendered on stderr.
-    pub fn interact_text(&self) -> io::Result<T> {
-        self.interact_text_on(&Term::stderr())
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    pub fn interact_text(&self) -> Result<T> {
+        self.interact_text_on(&self.output.term())
     }
 
     /// Like [`interact_text`](#method.interact_text) but allows a specific terminal to be set.
-    pub fn interact_text_on(&self, term: &Term) -> io::Result<T> {
+    pub fn interact_text_on(&self, term: &dyn Backend) -> Result<T> {
+        #[cfg(feature = "async")]
+        {
+            if self.async_validator.is_some() {
+                return Err(async_validator_error());
+            }
+        }
+
+        block_on(self.interact_text_with(&KeyReader::Blocking(term), false))?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::Other,
+                "Quit not allowed in this case",
+            ))
+        })
+    }
+
+    /// Like [interact_text](#method.interact_text), but the user can cancel
+    /// the prompt with 'Esc' or Ctrl+C, in which case `None` is returned.
+    ///
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    pub fn interact_text_opt(&self) -> Result<Option<T>> {
+        self.interact_text_on_opt(&self.output.term())
+    }
+
+    /// Like [interact_text_opt](#method.interact_text_opt) but allows a specific terminal to be set.
+    pub fn interact_text_on_opt(&self, term: &dyn Backend) -> Result<Option<T>> {
+        cancel_on_interrupt(self.interact_text_on_step(term))
+    }
+
+    /// Like [interact_text_on_opt](#method.interact_text_on_opt), but
+    /// Ctrl+C and [Action::Cancel](enum.Action.html) fail with
+    /// `Error::Interrupted`, so that a [Form](struct.Form.html) stops
+    /// instead of going back.
+    pub(crate) fn interact_text_on_step(&self, term: &dyn Backend) -> Result<Option<T>> {
+        #[cfg(feature = "async")]
+        {
+            if self.async_validator.is_some() {
+                return Err(async_validator_error());
+            }
+        }
+
+        block_on(self.interact_text_with(&KeyReader::Blocking(term), true))
+    }
+
+    /// Async variant of [interact_text](#method.interact_text).
+    ///
+    /// Key presses are read on a helper thread, so waiting for the user
+    /// does not block the executor.  Validators registered with
+    /// [validate_with_async](#method.validate_with_async) only run here
+    /// and in [interact_async](#method.interact_async).  This requires the
+    /// `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_text_async(&self) -> Result<T> {
+        self.interact_text_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_text_on](#method.interact_text_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_text_on_async(&self, term: &Term) -> Result<T> {
+        self.interact_text_with(&KeyReader::Threaded(term), false)
+            .await?
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Quit not allowed in this case",
+                ))
+            })
+    }
+
+    /// Async variant of [interact_text_opt](#method.interact_text_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_text_opt_async(&self) -> Result<Option<T>> {
+        self.interact_text_on_opt_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_text_on_opt](#method.interact_text_on_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_text_on_opt_async(&self, term: &Term) -> Result<Option<T>> {
+        cancel_on_interrupt(
+            self.interact_text_with(&KeyReader::Threaded(term), true)
+                .await,
+        )
+    }
+
+    async fn interact_text_with(
+        &self,
+        keys: &KeyReader<'_>,
+        allow_quit: bool,
+    ) -> Result<Option<T>> {
+        let term = keys.term();
+        if non_interactive::is_active(term) {
+            return self.interact_with(keys).await.map(Some);
+        }
+
         let mut render = TermThemeRenderer::new(term, self.theme);
+        self.options.apply(&mut render);
 
+        let mut attempts = 0;
         loop {
             let default_string = self.default.as_ref().map(|x| x.to_string());
 
@@ -191,7 +594,7 @@ where
 
             // Read input by keystroke so that we can suppress ascii control characters
             if !term.is_term() {
-                return Ok("".to_owned().parse::<T>().unwrap());
+                return Err(Error::NotATerminal);
             }
 
             let mut chars: Vec<char> = Vec::new();
@@ -199,9 +602,40 @@ where
                 term.write_str(initial)?;
                 chars = initial.chars().collect();
             }
-            let mut position = 0;
+            let mut position = chars.len();
+            let mut history_pos = None;
+            let mut draft = Vec::new();
+            let mut cycle: Option<(Vec<String>, usize)> = None;
+            let mut stuck_tabs = 0;
             loop {
-                match term.read_key()? {
+                let input: String = chars.iter().collect();
+                let ghost = if self.ghost_suggestions && cycle.is_none() && position == chars.len()
+                {
+                    self.ghost_suggestion(&input)
+                } else {
+                    None
+                };
+                let ghost_width = match ghost {
+                    Some(ref text) => {
+                        let width = render.ghost_suggestion(&text[input.len()..])?;
+                        term.flush()?;
+                        width
+                    }
+                    None => 0,
+                };
+
+                let key = match keys.read_key().await? {
+                    Key::Unknown => return Err(Error::NotATerminal),
+                    key => self.keys.translate_query(key),
+                };
+                render.clear_ghost_suggestion(ghost_width)?;
+                if key != Key::Tab {
+                    render.clear_completion_candidates()?;
+                    cycle = None;
+                    stuck_tabs = 0;
+                }
+
+                match key {
                     Key::Backspace => {
                         if chars.pop().is_some() {
                             term.clear_chars(1)?;
@@ -223,17 +657,87 @@ where
                         position -= 1;
                         term.flush()?;
                     }
+                    Key::ArrowRight | Key::Tab if ghost.is_some() => {
+                        let text = ghost.unwrap().chars().collect();
+                        replace_line(term, &mut chars, &mut position, text)?;
+                    }
                     Key::ArrowRight if position < chars.len() => {
                         term.move_cursor_right(1)?;
                         position += 1;
                         term.flush()?;
                     }
+                    key @ (Key::ArrowUp | Key::ArrowDown) if self.history.is_some() => {
+                        let next = match (key, history_pos) {
+                            (Key::ArrowUp, None) => Some(0),
+                            (Key::ArrowUp, Some(pos)) => Some(pos + 1),
+                            (_, Some(0)) | (_, None) => None,
+                            (_, Some(pos)) => Some(pos - 1),
+                        };
+                        let entry = match next {
+                            Some(pos) => match self.history.as_ref().unwrap().borrow().read(pos) {
+                                Some(entry) => entry.chars().collect(),
+                                None => continue,
+                            },
+                            None if history_pos.is_none() => continue,
+                            None => draft.clone(),
+                        };
+
+                        if history_pos.is_none() {
+                            draft = chars.clone();
+                        }
+                        history_pos = next;
+
+                        replace_line(term, &mut chars, &mut position, entry)?;
+                    }
+                    Key::Tab if self.completion.is_some() => {
+                        let text = match cycle {
+                            Some((ref candidates, ref mut idx)) => {
+                                *idx = (*idx + 1) % candidates.len();
+                                candidates[*idx].clone()
+                            }
+                            None => {
+                                let completion = self.completion.unwrap();
+                                match completion.get(&input) {
+                                    Some(text) => {
+                                        stuck_tabs = 0;
+                                        text
+                                    }
+                                    None => {
+                                        let candidates = completion.candidates(&input);
+                                        if candidates.len() < 2 {
+                                            continue;
+                                        }
+
+                                        // Like in shells, the second tab
+                                        // lists the candidates.
+                                        stuck_tabs += 1;
+                                        if stuck_tabs == 1 {
+                                            continue;
+                                        } else if stuck_tabs == 2 {
+                                            term.move_cursor_right(chars.len() - position)?;
+                                            position = chars.len();
+                                            render.completion_candidates(&candidates, &input)?;
+                                            term.flush()?;
+                                            continue;
+                                        }
+
+                                        let text = candidates[0].clone();
+                                        cycle = Some((candidates, 0));
+                                        text
+                                    }
+                                }
+                            }
+                        };
+
+                        replace_line(term, &mut chars, &mut position, text.chars().collect())?;
+                    }
                     Key::Enter => break,
-                    Key::Unknown => {
-                        return Err(io::Error::new(
-                            io::ErrorKind::NotConnected,
-                            "Not a terminal",
-                        ))
+                    key @ (Key::Escape | CANCEL) if allow_quit => {
+                        term.clear_line()?;
+                        render.clear()?;
+                        term.flush()?;
+
+                        return quit(key);
                     }
                     _ => (),
                 }
@@ -247,7 +751,7 @@ where
                 if let Some(ref default) = self.default {
                     render.input_prompt_selection(&self.prompt, &default.to_string())?;
                     term.flush()?;
-                    return Ok(default.clone());
+                    return Ok(Some(default.clone()));
                 } else if !self.permit_empty {
                     continue;
                 }
@@ -255,20 +759,47 @@ where
 
             match input.parse::<T>() {
                 Ok(value) => {
-                    if let Some(ref validator) = self.validator {
-                        if let Some(err) = validator(&value) {
-                            render.error(&err)?;
-                            continue;
+                    let errors = if self.spinner_on_wait && !self.validators.is_empty() {
+                        render.wait_with_spinner(&self.prompt, || self.validate(&value))?
+                    } else {
+                        self.validate(&value)
+                    };
+
+                    if !errors.is_empty() {
+                        for err in &errors {
+                            render.error(err)?;
+                        }
+                        self.reject(&mut attempts, errors.join("; "))?;
+                        continue;
+                    }
+
+                    #[cfg(feature = "async")]
+                    {
+                        if let Some(ref validator) = self.async_validator {
+                            let err = render
+                                .wait_with_spinner_async(&self.prompt, validator(&value))
+                                .await?;
+
+                            if let Some(err) = err {
+                                render.error(&err)?;
+                                self.reject(&mut attempts, err)?;
+                                continue;
+                            }
                         }
                     }
 
+                    if let Some(ref history) = self.history {
+                        history.borrow_mut().write(&value);
+                    }
+
                     render.input_prompt_selection(&self.prompt, &input)?;
                     term.flush()?;
 
-                    return Ok(value);
+                    return Ok(Some(value));
                 }
                 Err(err) => {
                     render.error(&err.to_string())?;
+                    self.reject(&mut attempts, err.to_string())?;
                     continue;
                 }
             }
@@ -282,37 +813,103 @@ where
     /// For more limited version, see [`interact_text`](#method.interact_text).
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<T> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    pub fn interact(&self) -> Result<T> {
+        self.interact_on(&self.output.term())
+    }
+
+    /// Like [interact](#method.interact), but the user can cancel the
+    /// prompt with Ctrl+C, in which case `None` is returned.
+    ///
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    pub fn interact_opt(&self) -> Result<Option<T>> {
+        self.interact_on_opt(&self.output.term())
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<T>> {
+        cancel_on_interrupt(self.interact_on(term).map(Some))
+    }
+
+    /// Async variant of [interact](#method.interact).
+    ///
+    /// The line is read on a helper thread, so waiting for the user does
+    /// not block the executor.  Validators registered with
+    /// [validate_with_async](#method.validate_with_async) run here as
+    /// well.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_async(&self) -> Result<T> {
+        self.interact_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_opt](#method.interact_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_opt_async(&self) -> Result<Option<T>> {
+        self.interact_on_opt_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_on](#method.interact_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_async(&self, term: &Term) -> Result<T> {
+        self.interact_with(&KeyReader::Threaded(term)).await
+    }
+
+    /// Async variant of [interact_on_opt](#method.interact_on_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_opt_async(&self, term: &Term) -> Result<Option<T>> {
+        cancel_on_interrupt(self.interact_on_async(term).await.map(Some))
     }
 
     /// Like [`interact`](#method.interact) but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<T> {
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<T> {
+        #[cfg(feature = "async")]
+        {
+            if self.async_validator.is_some() {
+                return Err(async_validator_error());
+            }
+        }
+
+        block_on(self.interact_with(&KeyReader::Blocking(term)))
+    }
+
+    async fn interact_with(&self, keys: &KeyReader<'_>) -> Result<T> {
+        let term = keys.term();
         let mut render = TermThemeRenderer::new(term, self.theme);
+        self.options.apply(&mut render);
 
+        let scripted = non_interactive::is_active(term);
+        let mut attempts = 0;
+        let mut asked = false;
         loop {
-            let default_string = self.default.as_ref().map(|x| x.to_string());
+            let input = if scripted {
+                // the default is the only answer, so there is no second try
+                if asked && non_interactive::assumes_default() {
+                    return Err(Error::NoDefault);
+                }
+                asked = true;
+                non_interactive::read_answer()?
+            } else {
+                let default_string = self.default.as_ref().map(|x| x.to_string());
 
-            render.input_prompt(
-                &self.prompt,
-                if self.show_default {
-                    default_string.as_deref()
-                } else {
-                    None
-                },
-            )?;
-            term.flush()?;
+                render.input_prompt(
+                    &self.prompt,
+                    if self.show_default {
+                        default_string.as_deref()
+                    } else {
+                        None
+                    },
+                )?;
+                term.flush()?;
 
-            let input = if let Some(initial_text) = self.initial_text.as_ref() {
-                term.read_line_initial_text(initial_text)?
-            } else {
-                term.read_line()?
-            };
+                let input = keys.read_line(self.initial_text.as_deref()).await?;
 
-            render.add_line();
-            term.clear_line()?;
-            render.clear()?;
+                render.add_line();
+                term.clear_line()?;
+                render.clear()?;
+                input
+            };
 
             if input.is_empty() {
                 if let Some(ref default) = self.default {
@@ -326,11 +923,37 @@ where
 
             match input.parse::<T>() {
                 Ok(value) => {
-                    if let Some(ref validator) = self.validator {
-                        if let Some(err) = validator(&value) {
-                            render.error(&err)?;
-                            continue;
+                    let errors = if self.spinner_on_wait && !self.validators.is_empty() {
+                        render.wait_with_spinner(&self.prompt, || self.validate(&value))?
+                    } else {
+                        self.validate(&value)
+                    };
+
+                    if !errors.is_empty() {
+                        for err in &errors {
+                            render.error(err)?;
                         }
+                        self.reject(&mut attempts, errors.join("; "))?;
+                        continue;
+                    }
+
+                    #[cfg(feature = "async")]
+                    {
+                        if let Some(ref validator) = self.async_validator {
+                            let err = render
+                                .wait_with_spinner_async(&self.prompt, validator(&value))
+                                .await?;
+
+                            if let Some(err) = err {
+                                render.error(&err)?;
+                                self.reject(&mut attempts, err)?;
+                                continue;
+                            }
+                        }
+                    }
+
+                    if let Some(ref history) = self.history {
+                        history.borrow_mut().write(&value);
                     }
 
                     render.input_prompt_selection(&self.prompt, &input)?;
@@ -340,9 +963,171 @@ where
                 }
                 Err(err) => {
                     render.error(&err.to_string())?;
+                    self.reject(&mut attempts, err.to_string())?;
                     continue;
                 }
             }
         }
     }
 }
+
+impl<'a, T> Input<'a, T> {
+    /// Runs the validators and returns the errors of the failing ones.
+    fn validate(&self, value: &T) -> Vec<String> {
+        let mut errors = Vec::new();
+        for validator in &self.validators {
+            if let Some(err) = validator(value) {
+                errors.push(err);
+                if !self.report_all_errors {
+                    break;
+                }
+            }
+        }
+        errors
+    }
+
+    /// Counts a rejected input and fails once there were too many.
+    fn reject(&self, attempts: &mut usize, err: String) -> Result<()> {
+        *attempts += 1;
+        match self.max_attempts {
+            Some(max) if *attempts >= max => Err(Error::ValidationExhausted(err)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the only completion of `input`, if it extends the input.
+    fn ghost_suggestion(&self, input: &str) -> Option<String> {
+        let candidates = self.completion?.candidates(input);
+        match candidates.as_slice() {
+            [text] if text.len() > input.len() && text.starts_with(input) => Some(text.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Replaces the text of the line being edited and moves the cursor to its
+/// end.
+fn replace_line(
+    term: &dyn Backend,
+    chars: &mut Vec<char>,
+    position: &mut usize,
+    text: Vec<char>,
+) -> io::Result<()> {
+    term.move_cursor_right(chars.len() - *position)?;
+    term.clear_chars(chars.len())?;
+    *chars = text;
+    *position = chars.len();
+    term.write_str(&chars.iter().collect::<String>())?;
+    term.flush()
+}
+
+/// Error for the blocking methods of an input prompt with async validators.
+#[cfg(feature = "async")]
+fn async_validator_error() -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "async validators need interact_async or interact_text_async",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{completion::StringListCompletion, history::BufferedHistory, test::TestTerm};
+
+    #[test]
+    fn test_initial_text_completion() {
+        let completion = StringListCompletion::new(vec!["build", "check"]);
+        let term = TestTerm::new();
+        term.push_key(Key::Tab);
+
+        // the keys run out before enter, which leaves the line on screen
+        let rv = Input::<String>::new()
+            .with_prompt("Cmd")
+            .with_initial_text("bu")
+            .with_completion(&completion)
+            .interact_text_on(&term);
+        assert!(rv.is_err());
+        assert_eq!(term.contents(), "Cmd: build");
+    }
+
+    #[test]
+    fn test_initial_text_history() {
+        let mut history = BufferedHistory::with_capacity(10);
+        history.prepopulate(vec!["ls"]);
+        let term = TestTerm::new();
+        term.push_key(Key::ArrowUp);
+
+        let mut input = Input::<String>::new();
+        input
+            .with_prompt("Cmd")
+            .with_initial_text("git")
+            .with_history(&mut history);
+        assert!(input.interact_text_on(&term).is_err());
+        assert_eq!(term.contents(), "Cmd: ls");
+
+        let term = TestTerm::new();
+        term.push_keys(vec![
+            Key::ArrowUp,
+            Key::ArrowDown,
+            Key::Char('x'),
+            Key::Enter,
+        ]);
+        assert_eq!(input.interact_text_on(&term).unwrap(), "gitx");
+    }
+
+    #[test]
+    fn test_completion_list() {
+        let completion = StringListCompletion::new(vec!["bench", "build"]);
+        let mut input = Input::<String>::new();
+        input.with_prompt("Cmd").with_completion(&completion);
+
+        // the first tab cannot complete, the second one lists the candidates
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char('b'), Key::Tab, Key::Tab]);
+        assert!(input.interact_text_on(&term).is_err());
+        assert_eq!(term.contents(), "Cmd: b\nbench  build");
+
+        // typing clears the list again
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char('b'), Key::Tab, Key::Tab, Key::Char('u')]);
+        assert!(input.interact_text_on(&term).is_err());
+        assert_eq!(term.contents(), "Cmd: bu");
+
+        // further tabs cycle through the candidates
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char('b'), Key::Tab, Key::Tab, Key::Tab]);
+        assert!(input.interact_text_on(&term).is_err());
+        assert!(term.contents().starts_with("Cmd: bench\n"));
+
+        term.push_keys(vec![Key::Char('b'), Key::Tab, Key::Tab, Key::Tab, Key::Tab])
+            .push_key(Key::Enter);
+        assert_eq!(input.interact_text_on(&term).unwrap(), "build");
+    }
+
+    #[test]
+    fn test_ghost_suggestion() {
+        let completion = StringListCompletion::new(vec!["build", "check"]);
+        let mut input = Input::<String>::new();
+        input
+            .with_prompt("Cmd")
+            .with_completion(&completion)
+            .ghost_suggestions(true);
+
+        let term = TestTerm::new();
+        term.push_key(Key::Char('b'));
+        assert!(input.interact_text_on(&term).is_err());
+        assert_eq!(term.contents(), "Cmd: build");
+
+        // the right arrow key accepts the suggestion and moves to its end
+        let term = TestTerm::new();
+        term.push_keys(vec![
+            Key::Char('b'),
+            Key::ArrowRight,
+            Key::Char('s'),
+            Key::Enter,
+        ]);
+        assert_eq!(input.interact_text_on(&term).unwrap(), "builds");
+        assert_eq!(term.contents(), "Cmd: builds");
+    }
+}
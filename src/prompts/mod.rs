@@ -1,6 +1,394 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use console::{StyledObject, Term};
+
+use crate::{
+    backend::Backend,
+    error::Result,
+    session::Session,
+    theme::{MirrorWriter, ResizeCallback, TermThemeRenderer},
+};
+
 pub mod confirm;
+pub mod fuzzy_select;
 pub mod input;
 pub mod multi_select;
 pub mod password;
 pub mod select;
 pub mod sort;
+
+/// Where prompts render when they are not given a terminal.
+///
+/// See for example [Confirm::with_output](struct.Confirm.html#method.with_output).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputStream {
+    /// Renders on stdout.
+    Stdout,
+    /// Renders on stderr, which is the default.
+    #[default]
+    Stderr,
+}
+
+impl OutputStream {
+    pub(crate) fn term(self) -> Term {
+        match self {
+            OutputStream::Stdout => Term::stdout(),
+            OutputStream::Stderr => Term::stderr(),
+        }
+    }
+}
+
+/// Rendering options which all prompts share.
+///
+/// Prompts keep them in one field, so an option added here reaches every
+/// prompt through [apply](#method.apply).
+pub(crate) struct PromptOptions<'a> {
+    pub prompt_prefix: Option<StyledObject<String>>,
+    pub prompt_suffix: Option<StyledObject<String>>,
+    pub prompt_separator: Option<StyledObject<String>>,
+    pub report: bool,
+    pub mirror: Option<MirrorWriter<'a>>,
+    pub on_resize: Option<ResizeCallback<'a>>,
+    pub indent: usize,
+    pub step: Option<(usize, usize)>,
+}
+
+impl Default for PromptOptions<'_> {
+    fn default() -> Self {
+        PromptOptions {
+            prompt_prefix: None,
+            prompt_suffix: None,
+            prompt_separator: None,
+            report: true,
+            mirror: None,
+            on_resize: None,
+            indent: 0,
+            step: None,
+        }
+    }
+}
+
+impl PromptOptions<'_> {
+    /// Sets the options on a renderer.
+    pub fn apply<'r>(&'r self, render: &mut TermThemeRenderer<'r>) {
+        render.set_prompt_affixes(self.prompt_prefix.as_ref(), self.prompt_suffix.as_ref());
+        render.set_report(self.report);
+        render.set_prompt_separator(self.prompt_separator.as_ref());
+        render.set_mirror(self.mirror.as_ref());
+        render.set_on_resize(self.on_resize.as_ref());
+        render.set_indent(self.indent);
+        render.set_step(self.step);
+    }
+}
+
+/// Trait implemented by all prompts.
+///
+/// It allows code to run prompts without knowing which one it runs, for
+/// example to ask a list of questions one after the other:
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{FuzzySelect, Prompt, Select};
+///
+/// let mut color = Select::new();
+/// color.with_prompt("Color").items(&["red", "green"]);
+/// let mut fruit = FuzzySelect::new();
+/// fruit.with_prompt("Fruit").items(&["apple", "banana"]);
+///
+/// let prompts: Vec<&dyn Prompt<Output = usize>> = vec![&color, &fruit];
+/// for prompt in prompts {
+///     println!("Picked item {}", prompt.interact()?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The methods do the same as the `interact` and `interact_on` methods of
+/// the prompts, except for [Input](struct.Input.html), which reads the
+/// value like [interact_text](struct.Input.html#method.interact_text).
+pub trait Prompt {
+    /// The value the prompt returns.
+    type Output;
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on stderr, unless the `with_output` method of
+    /// the prompt says otherwise.
+    fn interact(&self) -> Result<Self::Output>;
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    fn interact_on(&self, term: &dyn Backend) -> Result<Self::Output>;
+
+    /// Like [interact_on](#method.interact_on), but returns `None` if the
+    /// user cancelled the prompt.
+    ///
+    /// Only prompts with an `interact_on_opt` method can be cancelled, the
+    /// others always return `Some`.
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<Self::Output>> {
+        self.interact_on(term).map(Some)
+    }
+
+    /// Like [interact_on_opt](#method.interact_on_opt), but Ctrl+C and
+    /// [Action::Cancel](enum.Action.html) fail with `Error::Interrupted`.
+    /// Forms use it to go back on [Action::Back](enum.Action.html) and stop
+    /// on the others.
+    #[doc(hidden)]
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<Self::Output>> {
+        self.interact_on_opt(term)
+    }
+
+    /// Like [interact_on](#method.interact_on) but runs the prompt in a
+    /// [Session](struct.Session.html) shared with other prompts.
+    fn interact_in(&self, session: &Session) -> Result<Self::Output> {
+        self.interact_on(session)
+    }
+}
+
+impl<P: Prompt + ?Sized> Prompt for &P {
+    type Output = P::Output;
+
+    fn interact(&self) -> Result<P::Output> {
+        (**self).interact()
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<P::Output> {
+        (**self).interact_on(term)
+    }
+
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<P::Output>> {
+        (**self).interact_on_opt(term)
+    }
+
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<P::Output>> {
+        (**self).interact_on_step(term)
+    }
+}
+
+impl Prompt for confirm::Confirm<'_> {
+    type Output = bool;
+
+    fn interact(&self) -> Result<bool> {
+        confirm::Confirm::interact(self)
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<bool> {
+        confirm::Confirm::interact_on(self, term)
+    }
+
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<bool>> {
+        confirm::Confirm::interact_on_opt(self, term)
+    }
+
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<bool>> {
+        confirm::Confirm::interact_on_step(self, term)
+    }
+}
+
+impl Prompt for fuzzy_select::FuzzySelect<'_> {
+    type Output = usize;
+
+    fn interact(&self) -> Result<usize> {
+        fuzzy_select::FuzzySelect::interact(self)
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<usize> {
+        fuzzy_select::FuzzySelect::interact_on(self, term)
+    }
+
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        fuzzy_select::FuzzySelect::interact_on_opt(self, term)
+    }
+
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        fuzzy_select::FuzzySelect::interact_on_step(self, term)
+    }
+}
+
+impl<T> Prompt for input::Input<'_, T>
+where
+    T: Clone + FromStr + Display,
+    T::Err: Display + Debug,
+{
+    type Output = T;
+
+    fn interact(&self) -> Result<T> {
+        self.interact_text()
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<T> {
+        self.interact_text_on(term)
+    }
+
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<T>> {
+        self.interact_text_on_opt(term)
+    }
+
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<T>> {
+        self.interact_text_on_step(term)
+    }
+}
+
+impl Prompt for multi_select::MultiSelect<'_> {
+    type Output = Vec<usize>;
+
+    fn interact(&self) -> Result<Vec<usize>> {
+        multi_select::MultiSelect::interact(self)
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<Vec<usize>> {
+        multi_select::MultiSelect::interact_on(self, term)
+    }
+
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<Vec<usize>>> {
+        multi_select::MultiSelect::interact_on_opt(self, term)
+    }
+
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<Vec<usize>>> {
+        multi_select::MultiSelect::interact_on_step(self, term)
+    }
+}
+
+impl Prompt for password::Password<'_> {
+    type Output = String;
+
+    fn interact(&self) -> Result<String> {
+        password::Password::interact(self)
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<String> {
+        password::Password::interact_on(self, term)
+    }
+}
+
+impl Prompt for select::Select<'_> {
+    type Output = usize;
+
+    fn interact(&self) -> Result<usize> {
+        select::Select::interact(self)
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<usize> {
+        select::Select::interact_on(self, term)
+    }
+
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        select::Select::interact_on_opt(self, term)
+    }
+
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        select::Select::interact_on_step(self, term)
+    }
+}
+
+impl<T: Clone> Prompt for sort::Sort<'_, T> {
+    type Output = Vec<T>;
+
+    fn interact(&self) -> Result<Vec<T>> {
+        sort::Sort::interact(self)
+    }
+
+    fn interact_on(&self, term: &dyn Backend) -> Result<Vec<T>> {
+        sort::Sort::interact_on(self, term)
+    }
+
+    fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<Vec<T>>> {
+        sort::Sort::interact_on_opt(self, term)
+    }
+
+    fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<Vec<T>>> {
+        sort::Sort::interact_on_step(self, term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::TestTerm, Confirm, FuzzySelect, Input, Select};
+
+    #[test]
+    fn test_prompt_objects() {
+        let term = TestTerm::new();
+        term.push_keys(vec![console::Key::ArrowDown, console::Key::Enter])
+            .push_line("ban");
+
+        let mut color = Select::new();
+        color.items(&["red", "green"]).default(0);
+        let mut fruit = FuzzySelect::new();
+        fruit.items(&["apple", "banana"]);
+
+        let prompts: Vec<&dyn Prompt<Output = usize>> = vec![&color, &fruit];
+        let answers: Vec<usize> = prompts
+            .iter()
+            .map(|prompt| prompt.interact_on(&term).unwrap())
+            .collect();
+        assert_eq!(answers, vec![1, 1]);
+    }
+
+    #[test]
+    fn test_build() {
+        let prompts: Vec<Box<dyn Prompt<Output = usize>>> = vec![
+            Box::new(Select::new().items(&["red", "green"]).default(1).build()),
+            Box::new(FuzzySelect::new().items(&["apple", "banana"]).build()),
+        ];
+
+        let term = TestTerm::new();
+        term.push_key(console::Key::Enter).push_line("app");
+
+        let answers: Vec<usize> = prompts
+            .iter()
+            .map(|prompt| prompt.interact_on(&term).unwrap())
+            .collect();
+        assert_eq!(answers, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_generic_prompt() {
+        fn ask<P: Prompt>(prompt: &P, term: &dyn Backend) -> P::Output {
+            prompt.interact_on(term).unwrap()
+        }
+
+        let term = TestTerm::new();
+        term.push_str("y").push_line("42");
+
+        assert!(ask(&Confirm::new(), &term));
+        assert_eq!(ask(&Input::<u32>::new(), &term), 42);
+    }
+
+    #[test]
+    fn test_interact_opt() {
+        let term = TestTerm::new();
+        term.push_key(console::Key::Escape)
+            .push_str("ab")
+            .push_key(console::Key::Escape)
+            .push_str("n")
+            .push_line("7");
+
+        let confirm = Confirm::new();
+        let input = Input::<u32>::new();
+        assert_eq!(confirm.interact_on_opt(&term).unwrap(), None);
+        assert_eq!(input.interact_text_on_opt(&term).unwrap(), None);
+        assert_eq!(confirm.interact_on_opt(&term).unwrap(), Some(false));
+        assert_eq!(input.interact_text_on_opt(&term).unwrap(), Some(7));
+    }
+
+    #[test]
+    fn test_interact_opt_interrupt() {
+        let term = TestTerm::new();
+        term.push_interrupt()
+            .push_interrupt()
+            .push_interrupt()
+            .push_interrupt();
+
+        let input = Input::<u32>::new();
+        let mut color = Select::new();
+        color.items(&["red"]);
+        assert_eq!(Confirm::new().interact_on_opt(&term).unwrap(), None);
+        assert_eq!(input.interact_text_on_opt(&term).unwrap(), None);
+        assert_eq!(input.interact_on_opt(&term).unwrap(), None);
+        assert_eq!(color.interact_on_opt(&term).unwrap(), None);
+        assert!(!term.is_cursor_hidden());
+    }
+}
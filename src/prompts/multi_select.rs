@@ -1,8 +1,81 @@
-use std::{io, iter::repeat, ops::Rem};
+use std::{cell::RefCell, io, iter::repeat, mem, ops::Rem};
+
+use crate::{
+    backend::{term_size, Backend, HiddenCursor},
+    error::{cancel_on_interrupt, Error, Result},
+    keys::{quit, KeyBindings, CANCEL},
+    non_interactive,
+    prompts::{OutputStream, PromptOptions},
+    reader::{block_on, KeyReader},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
+
+#[cfg(feature = "async")]
+use console::Term;
+use console::{Key, StyledObject};
+
+type ToggleCallback<'a> = RefCell<Box<dyn FnMut(usize, bool) + 'a>>;
+
+/// Controls what happens when the user presses escape in a multi select prompt.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EscapeBehavior {
+    /// Discards the changes and returns the default selection, like earlier
+    /// versions did.
+    ///
+    /// The defaults are returned even if fewer of them are checked than
+    /// [min_selections](struct.MultiSelect.html#method.min_selections)
+    /// requires.
+    SubmitDefaults,
+    /// Returns the currently checked items, like enter does.
+    ///
+    /// Like enter this only submits once at least
+    /// [min_selections](struct.MultiSelect.html#method.min_selections)
+    /// items are checked.
+    SubmitCurrent,
+    /// Cancels the prompt.
+    ///
+    /// [interact_opt](struct.MultiSelect.html#method.interact_opt) returns
+    /// `None` in this case, regardless of the minimum number of selections.
+    /// For [interact](struct.MultiSelect.html#method.interact) escape is
+    /// ignored as it has to produce a result.
+    Cancel,
+    /// Escape is ignored.
+    ///
+    /// This is the default.
+    #[default]
+    Ignore,
+}
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+/// A selection of a multi select prompt split up by its exclusive groups.
+///
+/// See [MultiSelect::group_selection](struct.MultiSelect.html#method.group_selection).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GroupedSelection {
+    groups: Vec<Option<usize>>,
+    checked: Vec<usize>,
+}
 
-use console::{Key, Term};
+impl GroupedSelection {
+    /// Returns the item picked in an exclusive group.
+    ///
+    /// Groups are numbered in the order they were registered with
+    /// [exclusive_group](struct.MultiSelect.html#method.exclusive_group).
+    /// Returns `None` if no item of the group is checked or there is no
+    /// such group.
+    pub fn group(&self, group: usize) -> Option<usize> {
+        self.groups.get(group).cloned().flatten()
+    }
+
+    /// Returns the items picked in the exclusive groups.
+    pub fn groups(&self) -> &[Option<usize>] {
+        &self.groups
+    }
+
+    /// Returns the checked items which are not part of an exclusive group.
+    pub fn checked(&self) -> &[usize] {
+        &self.checked
+    }
+}
 
 /// Renders a multi select prompt.
 ///
@@ -20,11 +93,22 @@ use console::{Key, Term};
 /// ```
 pub struct MultiSelect<'a> {
     defaults: Vec<bool>,
+    required: Vec<usize>,
+    exclusive_groups: Vec<Vec<usize>>,
+    min_selections: usize,
     items: Vec<String>,
     prompt: Option<String>,
     clear: bool,
+    truncate_items: bool,
+    right_to_left: bool,
+    options: PromptOptions<'a>,
+    keys: KeyBindings,
+    output: OutputStream,
     theme: &'a dyn Theme,
     paged: bool,
+    max_length: Option<usize>,
+    escape_behavior: EscapeBehavior,
+    on_toggle: Option<ToggleCallback<'a>>,
 }
 
 impl<'a> Default for MultiSelect<'a> {
@@ -35,7 +119,7 @@ impl<'a> Default for MultiSelect<'a> {
 
 impl<'a> MultiSelect<'a> {
     /// Creates a multi select prompt.
-    pub fn new() -> MultiSelect<'static> {
+    pub fn new() -> MultiSelect<'a> {
         MultiSelect::with_theme(&SimpleTheme)
     }
 
@@ -44,19 +128,65 @@ impl<'a> MultiSelect<'a> {
         MultiSelect {
             items: vec![],
             defaults: vec![],
+            required: vec![],
+            exclusive_groups: vec![],
+            min_selections: 0,
             clear: true,
             prompt: None,
+            truncate_items: false,
+            right_to_left: false,
+            options: PromptOptions::default(),
+            keys: KeyBindings::default(),
+            output: OutputStream::Stderr,
             theme,
             paged: false,
+            max_length: None,
+            escape_behavior: EscapeBehavior::default(),
+            on_toggle: None,
         }
     }
 
+    /// Returns the configured prompt, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a prompt that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::MultiSelect;
+    ///
+    /// let select = MultiSelect::new().items(&["red", "green"]).build();
+    /// ```
+    pub fn build(&mut self) -> MultiSelect<'a> {
+        mem::take(self)
+    }
+
     /// Enables or disables paging
+    ///
+    /// The left and right arrow keys switch between the pages, and the
+    /// current page is shown below the items.  Paging is disabled by default.
     pub fn paged(&mut self, val: bool) -> &mut MultiSelect<'a> {
         self.paged = val;
         self
     }
 
+    /// Sets the maximum number of visible items.
+    ///
+    /// Longer lists scroll so that the cursor always stays visible.  When
+    /// paging is enabled this is used as the page size, the default page
+    /// size fills the terminal.
+    pub fn max_length(&mut self, val: usize) -> &mut MultiSelect<'a> {
+        self.max_length = Some(val);
+        self
+    }
+
+    /// Sets what happens when the user presses escape.
+    ///
+    /// The default is [EscapeBehavior::Ignore](enum.EscapeBehavior.html).
+    pub fn escape_behavior(&mut self, val: EscapeBehavior) -> &mut MultiSelect<'a> {
+        self.escape_behavior = val;
+        self
+    }
+
     /// Sets the clear behavior of the menu.
     ///
     /// The default is to clear the menu.
@@ -66,6 +196,9 @@ impl<'a> MultiSelect<'a> {
     }
 
     /// Sets a defaults for the menu.
+    ///
+    /// The checked states apply to the items added so far, so call this
+    /// after [items](#method.items).  Missing states are unchecked.
     pub fn defaults(&mut self, val: &[bool]) -> &mut MultiSelect<'a> {
         self.defaults = val
             .to_vec()
@@ -77,6 +210,80 @@ impl<'a> MultiSelect<'a> {
         self
     }
 
+    /// Marks items as required.
+    ///
+    /// Required items are always checked, cannot be unchecked by the user
+    /// and are always part of the result.  They are rendered with a
+    /// distinct marker.
+    pub fn with_required(&mut self, val: &[usize]) -> &mut MultiSelect<'a> {
+        self.required = val.to_vec();
+        self
+    }
+
+    /// Marks a group of items as exclusive.
+    ///
+    /// Only one item of an exclusive group can be checked at a time, so
+    /// checking an item unchecks its siblings, like a radio button group.
+    /// Items of exclusive groups are rendered with radio markers while
+    /// all other items keep behaving like checkboxes.  This can be called
+    /// multiple times to register several groups.  If a group contains a
+    /// required item the other items of that group cannot be checked.
+    ///
+    /// The prompt returns the indices of all checked items,
+    /// [group_selection](#method.group_selection) tells the picks of the
+    /// groups apart from the checked items.
+    pub fn exclusive_group(&mut self, val: &[usize]) -> &mut MultiSelect<'a> {
+        self.exclusive_groups.push(val.to_vec());
+        self
+    }
+
+    /// Sets the minimum number of items the user has to check.
+    ///
+    /// Enter does not submit fewer checked items and shows an error
+    /// instead.  See [EscapeBehavior](enum.EscapeBehavior.html) for how
+    /// escape handles the minimum.  The default is `0`.
+    pub fn min_selections(&mut self, val: usize) -> &mut MultiSelect<'a> {
+        self.min_selections = val;
+        self
+    }
+
+    /// Splits a selection returned by this prompt into the picks of the
+    /// exclusive groups and the other checked items.
+    ///
+    /// ```rust,no_run
+    /// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+    /// use dialoguer::MultiSelect;
+    ///
+    /// let mut select = MultiSelect::new();
+    /// select
+    ///     .items(&["small", "large", "cheese", "olives"])
+    ///     .exclusive_group(&[0, 1]);
+    ///
+    /// let selection = select.group_selection(&select.interact()?);
+    /// let size = selection.group(0);
+    /// let toppings = selection.checked();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn group_selection(&self, selection: &[usize]) -> GroupedSelection {
+        let groups = self
+            .exclusive_groups
+            .iter()
+            .map(|group| selection.iter().cloned().find(|idx| group.contains(idx)))
+            .collect();
+        let checked = selection
+            .iter()
+            .cloned()
+            .filter(|idx| {
+                !self
+                    .exclusive_groups
+                    .iter()
+                    .any(|group| group.contains(idx))
+            })
+            .collect();
+        GroupedSelection { groups, checked }
+    }
+
     /// Add a single item to the selector.
     #[inline]
     pub fn item<T: ToString>(&mut self, item: T) -> &mut MultiSelect<'a> {
@@ -108,6 +315,96 @@ impl<'a> MultiSelect<'a> {
         self
     }
 
+    /// Registers a callback that is invoked whenever an item is toggled.
+    ///
+    /// The callback receives the index of the item and its new checked
+    /// state.  It may borrow mutably from the caller for as long as the
+    /// prompt lives, which makes it possible to keep running totals.
+    pub fn on_toggle<F: FnMut(usize, bool) + 'a>(&mut self, f: F) -> &mut MultiSelect<'a> {
+        self.on_toggle = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    fn toggle(&self, checked: &mut [bool], required: &[bool], idx: usize) {
+        if let Some(group) = self
+            .exclusive_groups
+            .iter()
+            .find(|group| group.contains(&idx))
+        {
+            if !checked[idx] {
+                if group
+                    .iter()
+                    .any(|&other| other != idx && required.get(other) == Some(&true))
+                {
+                    return;
+                }
+
+                for &other in group {
+                    if other != idx && checked.get(other) == Some(&true) {
+                        checked[other] = false;
+                        self.notify_toggle(other, false);
+                    }
+                }
+            }
+        }
+
+        checked[idx] = !checked[idx];
+        self.notify_toggle(idx, checked[idx]);
+    }
+
+    /// Finds the items checked by a non-interactive answer.
+    ///
+    /// An empty answer picks the defaults, required items are always
+    /// checked.
+    fn parse_answer(
+        &self,
+        answer: &str,
+        required: &[bool],
+        defaults: &[bool],
+    ) -> Result<Vec<usize>, String> {
+        let mut checked = required.to_vec();
+        if answer.trim().is_empty() {
+            checked = defaults.to_vec();
+        } else {
+            for idx in non_interactive::find_items(&self.items, answer)? {
+                checked[idx] = true;
+            }
+        }
+
+        for group in &self.exclusive_groups {
+            if group
+                .iter()
+                .filter(|&&idx| checked.get(idx) == Some(&true))
+                .count()
+                > 1
+            {
+                return Err("Only one item of a group can be selected".to_string());
+            }
+        }
+
+        if let Some(err) = self.check_min_selections(&checked) {
+            return Err(err);
+        }
+
+        Ok((0..self.items.len()).filter(|&i| checked[i]).collect())
+    }
+
+    /// Returns the error to show if `checked` has fewer checked items than
+    /// required.
+    fn check_min_selections(&self, checked: &[bool]) -> Option<String> {
+        if checked.iter().filter(|&&checked| checked).count() < self.min_selections {
+            Some(format!("Select at least {} items", self.min_selections))
+        } else {
+            None
+        }
+    }
+
+    fn notify_toggle(&self, idx: usize, checked: bool) {
+        if let Some(ref on_toggle) = self.on_toggle {
+            (on_toggle.borrow_mut())(idx, checked);
+        }
+    }
+
     /// Prefaces the menu with a prompt.
     ///
     /// When a prompt is set the system also prints out a confirmation after
@@ -117,74 +414,308 @@ impl<'a> MultiSelect<'a> {
         self
     }
 
+    /// Overrides the prompt prefix of the theme for this prompt.
+    ///
+    /// Themes without a prompt prefix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_prefix(&mut self, prefix: StyledObject<String>) -> &mut MultiSelect<'a> {
+        self.options.prompt_prefix = Some(prefix);
+        self
+    }
+
+    /// Overrides the prompt suffix of the theme for this prompt.
+    ///
+    /// Themes without a prompt suffix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_suffix(&mut self, suffix: StyledObject<String>) -> &mut MultiSelect<'a> {
+        self.options.prompt_suffix = Some(suffix);
+        self
+    }
+
+    /// Overrides the separator between the prompt and its answer once the
+    /// prompt is done, for example `" → "`.
+    pub fn with_prompt_separator(
+        &mut self,
+        separator: StyledObject<String>,
+    ) -> &mut MultiSelect<'a> {
+        self.options.prompt_separator = Some(separator);
+        self
+    }
+
+    /// Shows the step of this prompt in a multi step flow, such as
+    /// `(step 2/5)`.
+    ///
+    /// `current` starts at 1.  The theme decides how the badge looks.
+    pub fn with_step(&mut self, current: usize, total: usize) -> &mut MultiSelect<'a> {
+        self.options.step = Some((current, total));
+        self
+    }
+
+    /// Indents every line of the prompt by `indent` spaces.
+    ///
+    /// This nests the prompt in surrounding output.  The default is no
+    /// indent.
+    pub fn with_indent(&mut self, indent: usize) -> &mut MultiSelect<'a> {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Indicates whether to report the answered prompt once it is done.
+    ///
+    /// The interactive prompt is still cleared as usual.  The default is
+    /// `true`.
+    pub fn report(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.options.report = val;
+        self
+    }
+
+    /// Sets where [interact](#method.interact) and the other methods which
+    /// do not take a terminal render the prompt.
+    ///
+    /// The default is stderr, which keeps the prompt out of the output when
+    /// stdout is piped into another program.  Prompts rendered on stdout
+    /// become part of that output instead, and they fail with
+    /// [Error::NotATerminal](enum.Error.html#variant.NotATerminal) if
+    /// stdout is not a terminal, unless answers come from
+    /// [set_non_interactive_input](fn.set_non_interactive_input.html).
+    pub fn with_output(&mut self, output: OutputStream) -> &mut MultiSelect<'a> {
+        self.output = output;
+        self
+    }
+
+    /// Echoes a plain text transcript of the prompt to `writer`.
+    ///
+    /// The answered prompt and any error lines are written without styles,
+    /// for example to keep an audit log, while the interactive prompt
+    /// still goes to the terminal.
+    pub fn with_mirror<W: io::Write + 'a>(&mut self, writer: W) -> &mut MultiSelect<'a> {
+        self.options.mirror = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Calls `f` with the new number of rows and columns after the terminal
+    /// was resized.
+    ///
+    /// The prompt redraws itself at the new size, so this is only needed for
+    /// output of your own.  console does not report resizes, so they are
+    /// noticed when the prompt redraws after the next key press.
+    pub fn on_resize<F: FnMut(u16, u16) + 'a>(&mut self, f: F) -> &mut MultiSelect<'a> {
+        self.options.on_resize = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    /// Sets the keys the prompt reacts to.
+    ///
+    /// The default are the [default bindings](struct.KeyBindings.html).
+    pub fn with_keys(&mut self, keys: &KeyBindings) -> &mut MultiSelect<'a> {
+        self.keys = keys.clone();
+        self
+    }
+
+    /// Truncates items wider than the terminal.
+    ///
+    /// Truncated items end with the ellipsis of the theme.  The default is
+    /// to let long items wrap.
+    pub fn truncate_items(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.truncate_items = val;
+        self
+    }
+
+    /// Lays the prompt out for right-to-left languages.
+    ///
+    /// Lines are aligned to the right and the symbols of the theme are put
+    /// on the other side of the prompt and the items.  The default is
+    /// left-to-right.
+    pub fn right_to_left(&mut self, val: bool) -> &mut MultiSelect<'a> {
+        self.right_to_left = val;
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// The user can select the items with the space bar and on enter
     /// the selected items will be returned.
-    pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+    pub fn interact(&self) -> Result<Vec<usize>> {
+        self.interact_on(&self.output.term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// Like [interact](#method.interact), but returns `None` if the user
+    /// pressed Ctrl+C, or escape while the escape behavior is
+    /// [EscapeBehavior::Cancel](enum.EscapeBehavior.html).
+    pub fn interact_opt(&self) -> Result<Option<Vec<usize>>> {
+        self.interact_on_opt(&self.output.term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<Vec<usize>> {
+        block_on(self._interact_on(&KeyReader::Blocking(term), false))?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::Other,
+                "Quit not allowed in this case",
+            ))
+        })
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<Vec<usize>>> {
+        cancel_on_interrupt(self.interact_on_step(term))
+    }
+
+    /// Like [interact_on_opt](#method.interact_on_opt), but Ctrl+C and
+    /// [Action::Cancel](enum.Action.html) fail with `Error::Interrupted`, so
+    /// that a [Form](struct.Form.html) stops instead of going back.
+    pub(crate) fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<Vec<usize>>> {
+        block_on(self._interact_on(&KeyReader::Blocking(term), true))
+    }
+
+    /// Async variant of [interact](#method.interact).
+    ///
+    /// Key presses are read on a helper thread, so waiting for the user
+    /// does not block the executor.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_async(&self) -> Result<Vec<usize>> {
+        self.interact_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_opt](#method.interact_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_opt_async(&self) -> Result<Option<Vec<usize>>> {
+        self.interact_on_opt_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_on](#method.interact_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_async(&self, term: &Term) -> Result<Vec<usize>> {
+        self._interact_on(&KeyReader::Threaded(term), false)
+            .await?
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Quit not allowed in this case",
+                ))
+            })
+    }
+
+    /// Async variant of [interact_on_opt](#method.interact_on_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_opt_async(&self, term: &Term) -> Result<Option<Vec<usize>>> {
+        cancel_on_interrupt(self._interact_on(&KeyReader::Threaded(term), true).await)
+    }
+
+    async fn _interact_on(
+        &self,
+        keys: &KeyReader<'_>,
+        allow_quit: bool,
+    ) -> Result<Option<Vec<usize>>> {
+        let term = keys.term();
         let mut page = 0;
 
-        let capacity = if self.paged {
-            term.size().0 as usize - 1
-        } else {
-            self.items.len()
-        };
+        if self.items.is_empty() {
+            return Ok(Some(vec![]));
+        }
 
-        let pages = if capacity == 0 {
-            return Ok(vec![]);
-        } else {
-            (self.items.len() / capacity) + 1
+        let capacity = match self.max_length {
+            Some(max_length) => max_length.clamp(1, self.items.len()),
+            // leaves room for the prompt and the page indicator
+            None if self.paged => (term_size(term).0 as usize).saturating_sub(2).max(1),
+            None => self.items.len(),
         };
 
+        let pages = (self.items.len() as f64 / capacity as f64).ceil() as usize;
+
         let mut render = TermThemeRenderer::new(term, self.theme);
+        self.options.apply(&mut render);
+        render.set_truncate_items(self.truncate_items);
+        render.set_right_to_left(self.right_to_left);
         let mut sel = 0;
 
+        let required: Vec<bool> = (0..self.items.len())
+            .map(|idx| self.required.contains(&idx))
+            .collect();
+        let defaults: Vec<bool> = self
+            .defaults
+            .iter()
+            .zip(required.iter())
+            .map(|(&default, &required)| default || required)
+            .collect();
+        let mut checked: Vec<bool> = defaults.clone();
+        let exclusive: Vec<bool> = (0..self.items.len())
+            .map(|idx| {
+                self.exclusive_groups
+                    .iter()
+                    .any(|group| group.contains(&idx))
+            })
+            .collect();
+
+        if non_interactive::is_active(term) {
+            let selected: Vec<usize> = non_interactive::answer(&mut render, |answer| {
+                self.parse_answer(answer, &required, &defaults)
+            })?;
+
+            if let Some(ref prompt) = self.prompt {
+                let names: Vec<_> = selected.iter().map(|&i| self.items[i].as_str()).collect();
+                render.multi_select_prompt_selection(prompt, &names[..])?;
+            }
+
+            term.flush()?;
+
+            return Ok(Some(selected));
+        }
+
         if let Some(ref prompt) = self.prompt {
             render.multi_select_prompt(prompt)?;
         }
 
-        let mut size_vec = Vec::new();
+        let _cursor = HiddenCursor::new(term)?;
 
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(*size);
-        }
+        let mut offset = 0;
+        let mut error: Option<String> = None;
+        loop {
+            if self.paged {
+                offset = page * capacity;
+            } else if sel < offset {
+                offset = sel;
+            } else if sel >= offset + capacity {
+                offset = sel + 1 - capacity;
+            }
 
-        let mut checked: Vec<bool> = self.defaults.clone();
+            for (idx, item) in self.items.iter().enumerate().skip(offset).take(capacity) {
+                if required[idx] {
+                    render.multi_select_prompt_required_item(item, sel == idx)?;
+                } else if exclusive[idx] {
+                    render.multi_select_prompt_radio_item(item, checked[idx], sel == idx)?;
+                } else {
+                    render.multi_select_prompt_item(item, checked[idx], sel == idx)?;
+                }
+            }
 
-        loop {
-            for (idx, item) in self
-                .items
-                .iter()
-                .enumerate()
-                .skip(page * capacity)
-                .take(capacity)
-            {
-                render.multi_select_prompt_item(item, checked[idx], sel == idx)?;
+            if self.paged && pages > 1 {
+                render.page_indicator(page + 1, pages)?;
+            }
+
+            if let Some(err) = error.take() {
+                render.error(&err)?;
             }
 
-            term.hide_cursor()?;
             term.flush()?;
 
-            match term.read_key()? {
-                Key::ArrowDown | Key::Char('j') => {
+            let key = match self.keys.translate(keys.read_key().await?) {
+                Key::Escape if self.escape_behavior == EscapeBehavior::SubmitCurrent => Key::Enter,
+                key => key,
+            };
+
+            match key {
+                Key::ArrowDown => {
                     if sel == !0 {
                         sel = 0;
                     } else {
                         sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
                     }
                 }
-                Key::ArrowUp | Key::Char('k') => {
+                Key::ArrowUp => {
                     if sel == !0 {
                         sel = self.items.len() - 1;
                     } else {
@@ -192,32 +723,41 @@ impl<'a> MultiSelect<'a> {
                             % (self.items.len() as i64)) as usize;
                     }
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
-
-                        sel = page * capacity;
+                Key::ArrowLeft if self.paged => {
+                    if page == 0 {
+                        page = pages - 1;
+                    } else {
+                        page -= 1;
                     }
+
+                    sel = page * capacity;
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
-
-                        sel = page * capacity;
+                Key::ArrowRight if self.paged => {
+                    if page == pages - 1 {
+                        page = 0;
+                    } else {
+                        page += 1;
                     }
+
+                    sel = page * capacity;
+                }
+                Key::Char(' ') if !required[sel] => {
+                    self.toggle(&mut checked, &required, sel);
                 }
-                Key::Char(' ') => {
-                    checked[sel] = !checked[sel];
+                key @ (Key::Escape | CANCEL)
+                    if allow_quit
+                        && (key == CANCEL || self.escape_behavior == EscapeBehavior::Cancel) =>
+                {
+                    if self.clear {
+                        render.clear()?;
+                    }
+
+                    term.show_cursor()?;
+                    term.flush()?;
+
+                    return quit(key);
                 }
-                Key::Escape => {
+                Key::Escape if self.escape_behavior == EscapeBehavior::SubmitDefaults => {
                     if self.clear {
                         render.clear()?;
                     }
@@ -229,15 +769,21 @@ impl<'a> MultiSelect<'a> {
                     term.show_cursor()?;
                     term.flush()?;
 
-                    return Ok(self
-                        .defaults
-                        .clone()
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
-                        .collect());
+                    return Ok(Some(
+                        defaults
+                            .into_iter()
+                            .enumerate()
+                            .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
+                            .collect(),
+                    ));
                 }
                 Key::Enter => {
+                    if let Some(err) = self.check_min_selections(&checked) {
+                        error = Some(err);
+                        render.clear_preserve_prompt()?;
+                        continue;
+                    }
+
                     if self.clear {
                         render.clear()?;
                     }
@@ -261,11 +807,13 @@ impl<'a> MultiSelect<'a> {
                     term.show_cursor()?;
                     term.flush()?;
 
-                    return Ok(checked
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
-                        .collect());
+                    return Ok(Some(
+                        checked
+                            .into_iter()
+                            .enumerate()
+                            .filter_map(|(idx, checked)| if checked { Some(idx) } else { None })
+                            .collect(),
+                    ));
                 }
                 _ => {}
             }
@@ -274,7 +822,132 @@ impl<'a> MultiSelect<'a> {
                 page = sel / capacity;
             }
 
-            render.clear_preserve_prompt(&size_vec)?;
+            render.clear_preserve_prompt()?;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestTerm;
+
+    #[test]
+    fn test_exclusive_group_unchecks_siblings() {
+        let mut select = MultiSelect::new();
+        select.items(&["a", "b", "c", "d"]).exclusive_group(&[1, 2]);
+
+        let required = vec![false; 4];
+        let mut checked = vec![true, true, false, false];
+
+        select.toggle(&mut checked, &required, 2);
+        assert_eq!(checked, vec![true, false, true, false]);
+
+        select.toggle(&mut checked, &required, 3);
+        assert_eq!(checked, vec![true, false, true, true]);
+    }
+
+    #[test]
+    fn test_exclusive_group_keeps_required() {
+        let mut select = MultiSelect::new();
+        select
+            .items(&["a", "b"])
+            .exclusive_group(&[0, 1])
+            .with_required(&[0]);
+
+        let required = vec![true, false];
+        let mut checked = vec![true, false];
+
+        select.toggle(&mut checked, &required, 1);
+        assert_eq!(checked, vec![true, false]);
+    }
+
+    #[test]
+    fn test_defaults() {
+        let mut select = MultiSelect::new();
+        select.items(&["a", "b", "c"]).defaults(&[false, true]);
+        assert_eq!(select.defaults, vec![false, true, false]);
+
+        select.defaults(&[true, true, true, true]);
+        assert_eq!(select.defaults, vec![true, true, true]);
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        let mut select = MultiSelect::new();
+        select
+            .items(&["red", "green", "blue"])
+            .exclusive_group(&[0, 1, 7]);
+
+        let none = [false; 3];
+        let defaults = [false, false, true];
+        assert_eq!(select.parse_answer("red", &none, &defaults), Ok(vec![0]));
+        assert_eq!(select.parse_answer("", &none, &defaults), Ok(vec![2]));
+        assert!(select.parse_answer("red, green", &none, &defaults).is_err());
+    }
+
+    #[test]
+    fn test_group_selection() {
+        let mut select = MultiSelect::new();
+        select
+            .items(&["a", "b", "c", "d", "e"])
+            .exclusive_group(&[0, 1])
+            .exclusive_group(&[2, 3]);
+
+        let selection = select.group_selection(&[1, 4]);
+        assert_eq!(selection.group(0), Some(1));
+        assert_eq!(selection.group(1), None);
+        assert_eq!(selection.group(2), None);
+        assert_eq!(selection.groups(), &[Some(1), None][..]);
+        assert_eq!(selection.checked(), &[4][..]);
+    }
+
+    #[test]
+    fn test_q_keeps_checks() {
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char(' '), Key::Char('q'), Key::Enter]);
+
+        let answer = MultiSelect::new()
+            .items(&["red", "green"])
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn test_min_selections() {
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Escape, Key::Enter, Key::Char(' '), Key::Enter]);
+
+        let answer = MultiSelect::new()
+            .items(&["red", "green"])
+            .min_selections(1)
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), vec![0]);
+        assert!(term.transcript().contains("Select at least 1 items"));
+    }
+
+    #[test]
+    fn test_escape_behavior() {
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char(' '), Key::Escape, Key::Escape]);
+
+        let answer = MultiSelect::new()
+            .items(&["red", "green"])
+            .escape_behavior(EscapeBehavior::SubmitDefaults)
+            .defaults(&[false, true])
+            .min_selections(2)
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), vec![1]);
+
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Escape, Key::Char(' '), Key::Enter]);
+
+        let answer = MultiSelect::new()
+            .items(&["red", "green"])
+            .escape_behavior(EscapeBehavior::SubmitCurrent)
+            .min_selections(1)
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), vec![0]);
+        assert!(term.transcript().contains("Select at least 1 items"));
+    }
+}
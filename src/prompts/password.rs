@@ -1,7 +1,16 @@
-use std::io;
+use std::{cell::RefCell, io, mem};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    backend::Backend,
+    error::{Error, Result},
+    non_interactive,
+    prompts::{OutputStream, PromptOptions},
+    reader::{block_on, KeyReader},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
 
+use console::StyledObject;
+#[cfg(feature = "async")]
 use console::Term;
 use zeroize::Zeroizing;
 
@@ -21,9 +30,12 @@ use zeroize::Zeroizing;
 /// ```
 pub struct Password<'a> {
     prompt: String,
+    options: PromptOptions<'a>,
+    output: OutputStream,
     theme: &'a dyn Theme,
     allow_empty_password: bool,
     confirmation_prompt: Option<(String, String)>,
+    max_attempts: Option<usize>,
 }
 
 impl<'a> Default for Password<'a> {
@@ -34,7 +46,7 @@ impl<'a> Default for Password<'a> {
 
 impl<'a> Password<'a> {
     /// Creates a password input prompt.
-    pub fn new() -> Password<'static> {
+    pub fn new() -> Password<'a> {
         Password::with_theme(&SimpleTheme)
     }
 
@@ -42,19 +54,127 @@ impl<'a> Password<'a> {
     pub fn with_theme(theme: &'a dyn Theme) -> Password<'a> {
         Password {
             prompt: "".into(),
+            options: PromptOptions::default(),
+            output: OutputStream::Stderr,
             theme,
             allow_empty_password: false,
             confirmation_prompt: None,
+            max_attempts: None,
         }
     }
 
+    /// Returns the configured prompt, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a prompt that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::Password;
+    ///
+    /// let password = Password::new().with_prompt("Password").build();
+    /// ```
+    pub fn build(&mut self) -> Password<'a> {
+        mem::take(self)
+    }
+
     /// Sets the password input prompt.
     pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Password<'a> {
         self.prompt = prompt.into();
         self
     }
 
+    /// Overrides the prompt prefix of the theme for this prompt.
+    ///
+    /// Themes without a prompt prefix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_prefix(&mut self, prefix: StyledObject<String>) -> &mut Password<'a> {
+        self.options.prompt_prefix = Some(prefix);
+        self
+    }
+
+    /// Overrides the prompt suffix of the theme for this prompt.
+    ///
+    /// Themes without a prompt suffix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_suffix(&mut self, suffix: StyledObject<String>) -> &mut Password<'a> {
+        self.options.prompt_suffix = Some(suffix);
+        self
+    }
+
+    /// Overrides the separator between the prompt and its answer once the
+    /// prompt is done, for example `" → "`.
+    pub fn with_prompt_separator(&mut self, separator: StyledObject<String>) -> &mut Password<'a> {
+        self.options.prompt_separator = Some(separator);
+        self
+    }
+
+    /// Shows the step of this prompt in a multi step flow, such as
+    /// `(step 2/5)`.
+    ///
+    /// `current` starts at 1.  The theme decides how the badge looks.
+    pub fn with_step(&mut self, current: usize, total: usize) -> &mut Password<'a> {
+        self.options.step = Some((current, total));
+        self
+    }
+
+    /// Indents every line of the prompt by `indent` spaces.
+    ///
+    /// This nests the prompt in surrounding output.  The default is no
+    /// indent.
+    pub fn with_indent(&mut self, indent: usize) -> &mut Password<'a> {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Indicates whether to report the answered prompt once it is done.
+    ///
+    /// The interactive prompt is still cleared as usual.  The default is
+    /// `true`.
+    pub fn report(&mut self, val: bool) -> &mut Password<'a> {
+        self.options.report = val;
+        self
+    }
+
+    /// Sets where [interact](#method.interact) and the other methods which
+    /// do not take a terminal render the prompt.
+    ///
+    /// The default is stderr, which keeps the prompt out of the output when
+    /// stdout is piped into another program.  Prompts rendered on stdout
+    /// become part of that output instead, and they fail with
+    /// [Error::NotATerminal](enum.Error.html#variant.NotATerminal) if
+    /// stdout is not a terminal, unless answers come from
+    /// [set_non_interactive_input](fn.set_non_interactive_input.html).
+    pub fn with_output(&mut self, output: OutputStream) -> &mut Password<'a> {
+        self.output = output;
+        self
+    }
+
+    /// Echoes a plain text transcript of the prompt to `writer`.
+    ///
+    /// The answered prompt and any error lines are written without styles,
+    /// for example to keep an audit log, while the interactive prompt
+    /// still goes to the terminal.
+    pub fn with_mirror<W: io::Write + 'a>(&mut self, writer: W) -> &mut Password<'a> {
+        self.options.mirror = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Calls `f` with the new number of rows and columns after the terminal
+    /// was resized.
+    ///
+    /// The prompt redraws itself at the new size, so this is only needed for
+    /// output of your own.  console does not report resizes, so they are
+    /// noticed when the prompt redraws after the next key press.
+    pub fn on_resize<F: FnMut(u16, u16) + 'a>(&mut self, f: F) -> &mut Password<'a> {
+        self.options.on_resize = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
     /// Enables confirmation prompting.
+    ///
+    /// The password is asked for a second time with `prompt`.  If the two
+    /// entries differ, `mismatch_err` is shown as an error and both are
+    /// asked for again.
     pub fn with_confirmation<A, B>(&mut self, prompt: A, mismatch_err: B) -> &mut Password<'a>
     where
         A: Into<String>,
@@ -64,6 +184,17 @@ impl<'a> Password<'a> {
         self
     }
 
+    /// Sets how often the confirmation may not match the password.
+    ///
+    /// Once that many confirmations failed, the prompt returns
+    /// [Error::ValidationExhausted](enum.Error.html#variant.ValidationExhausted)
+    /// with the mismatch error instead of asking again.  By default the
+    /// prompt asks until both entries match.
+    pub fn max_attempts(&mut self, val: usize) -> &mut Password<'a> {
+        self.max_attempts = Some(val);
+        self
+    }
+
     /// Allows/Disables empty password.
     ///
     /// By default this setting is set to false (i.e. password is not empty).
@@ -75,21 +206,47 @@ impl<'a> Password<'a> {
     /// Enables user interaction and returns the result.
     ///
     /// If the user confirms the result is `true`, `false` otherwise.
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<String> {
-        self.interact_on(&Term::stderr())
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    pub fn interact(&self) -> Result<String> {
+        self.interact_on(&self.output.term())
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<String> {
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<String> {
+        block_on(self.interact_with(&KeyReader::Blocking(term)))
+    }
+
+    /// Async variant of [interact](#method.interact).
+    ///
+    /// The password is read on a helper thread, so waiting for the user
+    /// does not block the executor.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_async(&self) -> Result<String> {
+        self.interact_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_on](#method.interact_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_async(&self, term: &Term) -> Result<String> {
+        self.interact_with(&KeyReader::Threaded(term)).await
+    }
+
+    async fn interact_with(&self, keys: &KeyReader<'_>) -> Result<String> {
+        let term = keys.term();
         let mut render = TermThemeRenderer::new(term, self.theme);
+        self.options.apply(&mut render);
         render.set_prompts_reset_height(false);
 
+        let mut attempts = 0;
         loop {
-            let password = Zeroizing::new(self.prompt_password(&mut render, &self.prompt)?);
+            let password = Zeroizing::new(
+                self.prompt_password(keys, &mut render, &self.prompt)
+                    .await?,
+            );
 
             if let Some((ref prompt, ref err)) = self.confirmation_prompt {
-                let pw2 = Zeroizing::new(self.prompt_password(&mut render, &prompt)?);
+                let pw2 = Zeroizing::new(self.prompt_password(keys, &mut render, prompt).await?);
 
                 if *password == *pw2 {
                     render.clear()?;
@@ -99,6 +256,12 @@ impl<'a> Password<'a> {
                 }
 
                 render.error(err)?;
+
+                attempts += 1;
+                if matches!(self.max_attempts, Some(max) if attempts >= max) {
+                    term.flush()?;
+                    return Err(Error::ValidationExhausted(err.clone()));
+                }
             } else {
                 render.clear()?;
                 render.password_prompt_selection(&self.prompt)?;
@@ -109,12 +272,24 @@ impl<'a> Password<'a> {
         }
     }
 
-    fn prompt_password(&self, render: &mut TermThemeRenderer, prompt: &str) -> io::Result<String> {
+    async fn prompt_password(
+        &self,
+        keys: &KeyReader<'_>,
+        render: &mut TermThemeRenderer<'_>,
+        prompt: &str,
+    ) -> Result<String> {
+        if non_interactive::is_active(render.term()) {
+            if non_interactive::assumes_default() {
+                return Err(Error::NoDefault);
+            }
+            return Ok(non_interactive::read_answer()?);
+        }
+
         loop {
             render.password_prompt(prompt)?;
             render.term().flush()?;
 
-            let input = render.term().read_secure_line()?;
+            let input = keys.read_secure_line().await?;
 
             render.add_line();
 
@@ -124,3 +299,33 @@ impl<'a> Password<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestTerm;
+
+    #[test]
+    fn test_confirmation() {
+        let term = TestTerm::new();
+        term.push_line("secret")
+            .push_line("typo")
+            .push_line("secret")
+            .push_line("secret");
+
+        let password = Password::new()
+            .with_prompt("Password")
+            .with_confirmation("Repeat", "Mismatch")
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(password, "secret");
+
+        term.push_line("secret").push_line("typo");
+        let err = Password::new()
+            .with_confirmation("Repeat", "Mismatch")
+            .max_attempts(1)
+            .interact_on(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::ValidationExhausted(ref err) if err == "Mismatch"));
+    }
+}
@@ -1,8 +1,18 @@
-use std::{io, ops::Rem};
-
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
-
-use console::{Key, Term};
+use std::{cell::RefCell, io, mem, ops::Rem};
+
+use crate::{
+    backend::{term_size, Backend, HiddenCursor},
+    error::{cancel_on_interrupt, Error, Result},
+    keys::{quit, Action, KeyBindings, CANCEL},
+    non_interactive,
+    prompts::{OutputStream, PromptOptions},
+    reader::{block_on, KeyReader},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
+
+#[cfg(feature = "async")]
+use console::Term;
+use console::{Key, StyledObject};
 
 /// Renders a select prompt.
 ///
@@ -38,8 +48,15 @@ pub struct Select<'a> {
     items: Vec<String>,
     prompt: Option<String>,
     clear: bool,
+    truncate_items: bool,
+    right_to_left: bool,
+    options: PromptOptions<'a>,
+    horizontal: bool,
+    keys: KeyBindings,
+    output: OutputStream,
     theme: &'a dyn Theme,
     paged: bool,
+    max_length: Option<usize>,
 }
 
 impl<'a> Default for Select<'a> {
@@ -50,7 +67,7 @@ impl<'a> Default for Select<'a> {
 
 impl<'a> Select<'a> {
     /// Creates a select prompt builder with default theme.
-    pub fn new() -> Select<'static> {
+    pub fn new() -> Select<'a> {
         Select::with_theme(&SimpleTheme)
     }
 
@@ -78,19 +95,51 @@ impl<'a> Select<'a> {
             items: vec![],
             prompt: None,
             clear: true,
+            truncate_items: false,
+            right_to_left: false,
+            options: PromptOptions::default(),
+            horizontal: false,
+            keys: select_keys(),
+            output: OutputStream::Stderr,
             theme,
             paged: false,
+            max_length: None,
         }
     }
 
+    /// Returns the configured prompt, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a prompt that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::Select;
+    ///
+    /// let select = Select::new().items(&["red", "green"]).default(0).build();
+    /// ```
+    pub fn build(&mut self) -> Select<'a> {
+        mem::take(self)
+    }
+
     /// Enables or disables paging
     ///
-    /// Paging is disabled by default
+    /// The left and right arrow keys switch between the pages, and the
+    /// current page is shown below the items.  Paging is disabled by default
     pub fn paged(&mut self, val: bool) -> &mut Select<'a> {
         self.paged = val;
         self
     }
 
+    /// Sets the maximum number of visible items.
+    ///
+    /// Longer lists scroll so that the cursor always stays visible.  When
+    /// paging is enabled this is used as the page size, the default page
+    /// size fills the terminal.
+    pub fn max_length(&mut self, val: usize) -> &mut Select<'a> {
+        self.max_length = Some(val);
+        self
+    }
+
     /// Indicates whether select menu should be ereased from the screen after interaction.
     ///
     /// The default is to clear the menu.
@@ -175,22 +224,149 @@ impl<'a> Select<'a> {
         self
     }
 
+    /// Overrides the prompt prefix of the theme for this prompt.
+    ///
+    /// Themes without a prompt prefix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_prefix(&mut self, prefix: StyledObject<String>) -> &mut Select<'a> {
+        self.options.prompt_prefix = Some(prefix);
+        self
+    }
+
+    /// Overrides the prompt suffix of the theme for this prompt.
+    ///
+    /// Themes without a prompt suffix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_suffix(&mut self, suffix: StyledObject<String>) -> &mut Select<'a> {
+        self.options.prompt_suffix = Some(suffix);
+        self
+    }
+
+    /// Overrides the separator between the prompt and its answer once the
+    /// prompt is done, for example `" → "`.
+    pub fn with_prompt_separator(&mut self, separator: StyledObject<String>) -> &mut Select<'a> {
+        self.options.prompt_separator = Some(separator);
+        self
+    }
+
+    /// Shows the step of this prompt in a multi step flow, such as
+    /// `(step 2/5)`.
+    ///
+    /// `current` starts at 1.  The theme decides how the badge looks.
+    pub fn with_step(&mut self, current: usize, total: usize) -> &mut Select<'a> {
+        self.options.step = Some((current, total));
+        self
+    }
+
+    /// Indents every line of the prompt by `indent` spaces.
+    ///
+    /// This nests the prompt in surrounding output.  The default is no
+    /// indent.
+    pub fn with_indent(&mut self, indent: usize) -> &mut Select<'a> {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Indicates whether to report the answered prompt once it is done.
+    ///
+    /// The interactive prompt is still cleared as usual.  The default is
+    /// `true`.
+    pub fn report(&mut self, val: bool) -> &mut Select<'a> {
+        self.options.report = val;
+        self
+    }
+
+    /// Sets where [interact](#method.interact) and the other methods which
+    /// do not take a terminal render the prompt.
+    ///
+    /// The default is stderr, which keeps the prompt out of the output when
+    /// stdout is piped into another program.  Prompts rendered on stdout
+    /// become part of that output instead, and they fail with
+    /// [Error::NotATerminal](enum.Error.html#variant.NotATerminal) if
+    /// stdout is not a terminal, unless answers come from
+    /// [set_non_interactive_input](fn.set_non_interactive_input.html).
+    pub fn with_output(&mut self, output: OutputStream) -> &mut Select<'a> {
+        self.output = output;
+        self
+    }
+
+    /// Echoes a plain text transcript of the prompt to `writer`.
+    ///
+    /// The answered prompt and any error lines are written without styles,
+    /// for example to keep an audit log, while the interactive prompt
+    /// still goes to the terminal.
+    pub fn with_mirror<W: io::Write + 'a>(&mut self, writer: W) -> &mut Select<'a> {
+        self.options.mirror = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Calls `f` with the new number of rows and columns after the terminal
+    /// was resized.
+    ///
+    /// The prompt redraws itself at the new size, so this is only needed for
+    /// output of your own.  console does not report resizes, so they are
+    /// noticed when the prompt redraws after the next key press.
+    pub fn on_resize<F: FnMut(u16, u16) + 'a>(&mut self, f: F) -> &mut Select<'a> {
+        self.options.on_resize = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    /// Sets the keys the prompt reacts to.
+    ///
+    /// The default are the [default bindings](struct.KeyBindings.html) and
+    /// `q` to cancel.
+    pub fn with_keys(&mut self, keys: &KeyBindings) -> &mut Select<'a> {
+        self.keys = keys.clone();
+        self
+    }
+
+    /// Truncates items wider than the terminal.
+    ///
+    /// Truncated items end with the ellipsis of the theme.  The default is
+    /// to let long items wrap.
+    pub fn truncate_items(&mut self, val: bool) -> &mut Select<'a> {
+        self.truncate_items = val;
+        self
+    }
+
+    /// Lays the prompt out for right-to-left languages.
+    ///
+    /// Lines are aligned to the right and the symbols of the theme are put
+    /// on the other side of the prompt and the items.  The default is
+    /// left-to-right.
+    pub fn right_to_left(&mut self, val: bool) -> &mut Select<'a> {
+        self.right_to_left = val;
+        self
+    }
+
+    /// Lays the items out on a single line after the prompt.
+    ///
+    /// The left and right arrow keys move the selection. Themes such as
+    /// [InlineTheme](theme/struct.InlineTheme.html) turn this on by
+    /// default.
+    pub fn horizontal(&mut self, val: bool) -> &mut Select<'a> {
+        self.horizontal = val;
+        self
+    }
+
     /// Enables user interaction and returns the result.
     ///
     /// Similar to [interact_on](#method.interact_on) except for the fact that it does not allow selection of the terminal.
-    /// The dialog is rendered on stderr.
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
     /// Result contains index of a selected item.
-    pub fn interact(&self) -> io::Result<usize> {
-        self.interact_on(&Term::stderr())
+    pub fn interact(&self) -> Result<usize> {
+        self.interact_on(&self.output.term())
     }
 
     /// Enables user interaction and returns the result.
     ///
     /// This method is similar to [interact_on_opt](#method.interact_on_opt) except for the fact that it does not allow selection of the terminal.
-    /// The dialog is rendered on stderr.
-    /// Result contains `Some(index)` if user selected one of items or `None` if user cancelled with 'Esc' or 'q'.
-    pub fn interact_opt(&self) -> io::Result<Option<usize>> {
-        self.interact_on_opt(&Term::stderr())
+    /// The dialog is rendered on stderr, unless
+    /// [with_output](#method.with_output) says otherwise.
+    /// Result contains `Some(index)` if user selected one of items or `None` if user cancelled with 'Esc', 'q' or Ctrl+C.
+    pub fn interact_opt(&self) -> Result<Option<usize>> {
+        self.interact_on_opt(&self.output.term())
     }
 
     /// Like [interact](#method.interact) but allows a specific terminal to be set.
@@ -211,9 +387,13 @@ impl<'a> Select<'a> {
     ///     Ok(())
     /// }
     ///```
-    pub fn interact_on(&self, term: &Term) -> io::Result<usize> {
-        self._interact_on(term, false)?
-            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<usize> {
+        block_on(self._interact_on(&KeyReader::Blocking(term), false))?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::Other,
+                "Quit not allowed in this case",
+            ))
+        })
     }
 
     /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
@@ -238,74 +418,153 @@ impl<'a> Select<'a> {
     /// }
     /// ```
     #[inline]
-    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<usize>> {
-        self._interact_on(term, true)
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        cancel_on_interrupt(self.interact_on_step(term))
+    }
+
+    /// Like [interact_on_opt](#method.interact_on_opt), but Ctrl+C and
+    /// [Action::Cancel](enum.Action.html) fail with `Error::Interrupted`, so
+    /// that a [Form](struct.Form.html) stops instead of going back.
+    pub(crate) fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<usize>> {
+        block_on(self._interact_on(&KeyReader::Blocking(term), true))
+    }
+
+    /// Async variant of [interact](#method.interact).
+    ///
+    /// Key presses are read on a helper thread, so waiting for the user
+    /// does not block the executor.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_async(&self) -> Result<usize> {
+        self.interact_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_opt](#method.interact_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_opt_async(&self) -> Result<Option<usize>> {
+        self.interact_on_opt_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_on](#method.interact_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_async(&self, term: &Term) -> Result<usize> {
+        self._interact_on(&KeyReader::Threaded(term), false)
+            .await?
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Quit not allowed in this case",
+                ))
+            })
+    }
+
+    /// Async variant of [interact_on_opt](#method.interact_on_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_opt_async(&self, term: &Term) -> Result<Option<usize>> {
+        cancel_on_interrupt(self._interact_on(&KeyReader::Threaded(term), true).await)
     }
 
     /// Like `interact` but allows a specific terminal to be set.
-    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<usize>> {
+    async fn _interact_on(&self, keys: &KeyReader<'_>, allow_quit: bool) -> Result<Option<usize>> {
+        let term = keys.term();
         let mut page = 0;
+        let mut render = TermThemeRenderer::new(term, self.theme);
+        self.options.apply(&mut render);
+        render.set_horizontal(self.horizontal);
+        render.set_truncate_items(self.truncate_items);
+        render.set_right_to_left(self.right_to_left);
+        let horizontal = render.horizontal();
+
+        if non_interactive::is_active(term) {
+            let sel = non_interactive::answer(&mut render, |answer| {
+                if answer.trim().is_empty() && self.default < self.items.len() {
+                    Ok(self.default)
+                } else {
+                    non_interactive::find_item(&self.items, answer)
+                }
+            })?;
 
-        let capacity = if self.paged {
-            term.size().0 as usize - 1
-        } else {
-            self.items.len()
-        };
+            if let Some(ref prompt) = self.prompt {
+                render.select_prompt_selection(prompt, &self.items[sel])?;
+            }
 
-        let pages = (self.items.len() / capacity) + 1;
-        let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = self.default;
+            term.flush()?;
 
-        if let Some(ref prompt) = self.prompt {
-            render.select_prompt(prompt)?;
+            return Ok(Some(sel));
         }
 
-        let mut size_vec = Vec::new();
+        let capacity = match self.max_length {
+            _ if horizontal => self.items.len(),
+            Some(max_length) => max_length.clamp(1, self.items.len().max(1)),
+            // leaves room for the prompt and the page indicator
+            None if self.paged => (term_size(term).0 as usize).saturating_sub(2).max(1),
+            None => self.items.len(),
+        };
 
-        for items in self
-            .items
-            .iter()
-            .flat_map(|i| i.split('\n'))
-            .collect::<Vec<_>>()
-        {
-            let size = &items.len();
-            size_vec.push(*size);
+        let pages = ((self.items.len() as f64 / capacity as f64).ceil() as usize).max(1);
+        let mut sel = self.default;
+        let mut offset = 0;
+
+        if !horizontal {
+            if let Some(ref prompt) = self.prompt {
+                render.select_prompt(prompt)?;
+            }
         }
 
+        let _cursor = HiddenCursor::new(term)?;
+
         loop {
-            for (idx, item) in self
-                .items
-                .iter()
-                .enumerate()
-                .skip(page * capacity)
-                .take(capacity)
-            {
-                render.select_prompt_item(item, sel == idx)?;
+            if horizontal {
+                let items: Vec<&str> = self.items.iter().map(String::as_str).collect();
+                let prompt = self.prompt.as_deref().unwrap_or("");
+                render.select_prompt_inline(prompt, &items, sel)?;
+            } else {
+                if self.paged {
+                    offset = page * capacity;
+                } else if sel != !0 && sel < offset {
+                    offset = sel;
+                } else if sel != !0 && sel >= offset + capacity {
+                    offset = sel + 1 - capacity;
+                }
+
+                for (idx, item) in self.items.iter().enumerate().skip(offset).take(capacity) {
+                    render.select_prompt_item(item, sel == idx)?;
+                }
+
+                if self.paged && pages > 1 {
+                    render.page_indicator(page + 1, pages)?;
+                }
             }
 
-            term.hide_cursor()?;
             term.flush()?;
 
-            match term.read_key()? {
-                Key::ArrowDown | Key::Char('j') => {
+            let key = match self.keys.translate(keys.read_key().await?) {
+                Key::ArrowRight if horizontal => Key::ArrowDown,
+                Key::ArrowLeft if horizontal => Key::ArrowUp,
+                key => key,
+            };
+
+            match key {
+                Key::ArrowDown => {
                     if sel == !0 {
                         sel = 0;
                     } else {
                         sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
                     }
                 }
-                Key::Escape | Key::Char('q') => {
-                    if allow_quit {
-                        if self.clear {
-                            term.clear_last_lines(self.items.len())?;
-                            term.show_cursor()?;
-                            term.flush()?;
+                key @ (Key::Escape | CANCEL) if allow_quit => {
+                    if self.clear {
+                        if horizontal {
+                            render.clear()?;
+                        } else {
+                            render.clear_preserve_prompt()?;
                         }
-
-                        return Ok(None);
+                        term.show_cursor()?;
+                        term.flush()?;
                     }
+
+                    return quit(key);
                 }
-                Key::ArrowUp | Key::Char('k') => {
+                Key::ArrowUp => {
                     if sel == !0 {
                         sel = self.items.len() - 1;
                     } else {
@@ -313,27 +572,23 @@ impl<'a> Select<'a> {
                             % (self.items.len() as i64)) as usize;
                     }
                 }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        if page == 0 {
-                            page = pages - 1;
-                        } else {
-                            page -= 1;
-                        }
-
-                        sel = page * capacity;
+                Key::ArrowLeft if self.paged => {
+                    if page == 0 {
+                        page = pages - 1;
+                    } else {
+                        page -= 1;
                     }
-                }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
 
-                        sel = page * capacity;
+                    sel = page * capacity;
+                }
+                Key::ArrowRight if self.paged => {
+                    if page == pages - 1 {
+                        page = 0;
+                    } else {
+                        page += 1;
                     }
+
+                    sel = page * capacity;
                 }
 
                 Key::Enter | Key::Char(' ') if sel != !0 => {
@@ -357,14 +612,27 @@ impl<'a> Select<'a> {
                 page = sel / capacity;
             }
 
-            render.clear_preserve_prompt(&size_vec)?;
+            if horizontal {
+                render.clear()?;
+            } else {
+                render.clear_preserve_prompt()?;
+            }
         }
     }
 }
 
+/// Returns the default key bindings of a select prompt, where `q` cancels
+/// as well.
+fn select_keys() -> KeyBindings {
+    let mut keys = KeyBindings::default();
+    keys.bind(Action::Cancel, Key::Char('q'));
+    keys
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test::TestTerm;
 
     #[test]
     fn test_str() {
@@ -403,4 +671,50 @@ mod tests {
             selections
         );
     }
+
+    #[test]
+    fn test_with_keys() {
+        let mut keys = KeyBindings::default();
+        keys.unbind(Key::Char('j'))
+            .bind(Action::Down, Key::Char('\u{e}'));
+
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char('j'), Key::Char('\u{e}'), Key::Enter]);
+        let selection = Select::new()
+            .items(&["red", "green", "blue"])
+            .default(0)
+            .with_keys(&keys)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(selection, 1);
+    }
+
+    #[test]
+    fn test_quit_with_q() {
+        let term = TestTerm::new();
+        term.push_key(Key::Char('q'));
+
+        let selection = Select::new()
+            .items(&["red", "green"])
+            .default(0)
+            .interact_on_opt(&term)
+            .unwrap();
+        assert_eq!(selection, None);
+    }
+
+    #[test]
+    fn test_paged_max_length() {
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::ArrowRight, Key::ArrowDown]);
+
+        let selection = Select::new()
+            .items(&["a", "b", "c", "d", "e"])
+            .default(0)
+            .paged(true)
+            .max_length(2)
+            .clear(false)
+            .interact_on(&term);
+        assert!(selection.is_err());
+        assert_eq!(term.contents(), "  c\n> d\n  Page 2/3");
+    }
 }
@@ -1,12 +1,30 @@
-use std::{io, ops::Rem};
+use std::{cell::RefCell, fmt::Display, io, mem};
 
-use crate::theme::{SimpleTheme, TermThemeRenderer, Theme};
+use crate::{
+    backend::{term_size, Backend, HiddenCursor},
+    error::{cancel_on_interrupt, Error, Result},
+    keys::{quit, KeyBindings, CANCEL},
+    non_interactive,
+    prompts::{OutputStream, PromptOptions},
+    reader::{block_on, KeyReader},
+    theme::{SimpleTheme, TermThemeRenderer, Theme},
+};
 
-use console::{Key, Term};
+#[cfg(feature = "async")]
+use console::Term;
+use console::{Key, StyledObject};
+
+type OrderValidator<'a> = Box<dyn Fn(&[usize]) -> Option<String> + 'a>;
 
 /// Renders a sort prompt.
 ///
 /// Returns list of indices in original items list sorted according to user input.
+/// The entry at position `i` of the result is the index of the original item
+/// which the user moved to position `i`.
+///
+/// Items can also carry a value of any type, in which case the values are
+/// returned in the chosen order.  A plain `Sort` uses the index of each item
+/// as its value.
 ///
 /// ## Example usage
 /// ```rust,no_run
@@ -21,238 +39,935 @@ use console::{Key, Term};
 /// # Ok(())
 /// # }
 /// ```
-pub struct Sort<'a> {
+///
+/// With values:
+/// ```rust,no_run
+/// use dialoguer::Sort;
+///
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let steps: Vec<u32> = Sort::new()
+///     .with_prompt("Order the build steps")
+///     .items_with_values(vec![("Compile", 1), ("Test", 2), ("Package", 3)])
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Sort<'a, T = usize> {
     items: Vec<String>,
+    values: Vec<T>,
+    initial_order: Option<Vec<usize>>,
+    locked: Vec<usize>,
     prompt: Option<String>,
     clear: bool,
+    truncate_items: bool,
+    right_to_left: bool,
+    options: PromptOptions<'a>,
+    keys: KeyBindings,
+    output: OutputStream,
     theme: &'a dyn Theme,
     paged: bool,
+    max_length: Option<usize>,
+    show_positions: bool,
+    pairwise: bool,
+    grab_symbol: Option<String>,
+    validator: Option<OrderValidator<'a>>,
 }
 
-impl<'a> Default for Sort<'a> {
-    fn default() -> Sort<'a> {
+impl<'a, T> Default for Sort<'a, T> {
+    fn default() -> Sort<'a, T> {
         Sort::new()
     }
 }
 
 impl<'a> Sort<'a> {
+    /// Add a single item to the selector.
+    pub fn item<T: ToString>(&mut self, item: T) -> &mut Sort<'a> {
+        self.values.push(self.items.len());
+        self.items.push(item.to_string());
+        self
+    }
+
+    /// Adds multiple items to the selector.
+    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Sort<'a> {
+        for item in items {
+            self.item(item.to_string());
+        }
+        self
+    }
+}
+
+impl<'a, T> Sort<'a, T> {
     /// Creates a sort prompt.
-    pub fn new() -> Sort<'static> {
+    pub fn new() -> Sort<'a, T> {
         Sort::with_theme(&SimpleTheme)
     }
 
     /// Creates a sort prompt with a specific theme.
-    pub fn with_theme(theme: &'a dyn Theme) -> Sort<'a> {
+    pub fn with_theme(theme: &'a dyn Theme) -> Sort<'a, T> {
         Sort {
             items: vec![],
+            values: vec![],
+            initial_order: None,
+            locked: vec![],
             clear: true,
             prompt: None,
+            truncate_items: false,
+            right_to_left: false,
+            options: PromptOptions::default(),
+            keys: KeyBindings::default(),
+            output: OutputStream::Stderr,
             theme,
             paged: false,
+            max_length: None,
+            show_positions: false,
+            pairwise: false,
+            grab_symbol: None,
+            validator: None,
         }
     }
 
+    /// Returns the configured prompt, leaving a default one behind.
+    ///
+    /// The builder methods return `&mut` references, so this ends a chain
+    /// of them with a prompt that can be stored or moved:
+    ///
+    /// ```rust
+    /// use dialoguer::Sort;
+    ///
+    /// let sort = Sort::new().items(&["first", "second"]).build();
+    /// ```
+    pub fn build(&mut self) -> Sort<'a, T> {
+        mem::take(self)
+    }
+
     /// Enables or disables paging
-    pub fn paged(&mut self, val: bool) -> &mut Sort<'a> {
+    ///
+    /// The left and right arrow keys switch between the pages, and the
+    /// current page is shown below the items.  Paging is disabled by default.
+    pub fn paged(&mut self, val: bool) -> &mut Sort<'a, T> {
         self.paged = val;
         self
     }
 
+    /// Sets the maximum number of visible items.
+    ///
+    /// Longer lists scroll so that the cursor, and with it a picked up
+    /// item, always stays visible.  Moving a picked up item past the edge
+    /// of the visible area scrolls the list along.  When paging is enabled
+    /// this is used as the page size.
+    pub fn max_length(&mut self, val: usize) -> &mut Sort<'a, T> {
+        self.max_length = Some(val);
+        self
+    }
+
+    /// Enables or disables the display of item positions.
+    ///
+    /// When enabled every item is prefixed with its current position,
+    /// which is updated as items are moved.  This is disabled by default.
+    pub fn show_positions(&mut self, val: bool) -> &mut Sort<'a, T> {
+        self.show_positions = val;
+        self
+    }
+
+    /// Enables or disables pairwise ranking.
+    ///
+    /// Instead of moving items around, the user is repeatedly shown two
+    /// items and picks the one that ranks higher with the left or right
+    /// arrow.  The order is built by binary insertion, which needs far fewer
+    /// decisions than comparing every pair.  Locked items keep their
+    /// position and are never compared.  If a validator rejects the result
+    /// the user continues with manual reordering.
+    pub fn pairwise(&mut self, val: bool) -> &mut Sort<'a, T> {
+        self.pairwise = val;
+        self
+    }
+
+    /// Sets the marker for the item that is currently picked up.
+    ///
+    /// This overrides the marker of the theme for this prompt only.  All
+    /// other items are rendered as usual.
+    pub fn with_grab_symbol<S: Into<String>>(&mut self, symbol: S) -> &mut Sort<'a, T> {
+        self.grab_symbol = Some(symbol.into());
+        self
+    }
+
     /// Sets the clear behavior of the menu.
     ///
     /// The default is to clear the menu after user interaction.
-    pub fn clear(&mut self, val: bool) -> &mut Sort<'a> {
+    pub fn clear(&mut self, val: bool) -> &mut Sort<'a, T> {
         self.clear = val;
         self
     }
 
-    /// Add a single item to the selector.
-    pub fn item<T: ToString>(&mut self, item: T) -> &mut Sort<'a> {
+    /// Add a single item with an associated value to the selector.
+    pub fn item_with_value<S: ToString>(&mut self, item: S, value: T) -> &mut Sort<'a, T> {
         self.items.push(item.to_string());
+        self.values.push(value);
         self
     }
 
-    /// Adds multiple items to the selector.
-    pub fn items<T: ToString>(&mut self, items: &[T]) -> &mut Sort<'a> {
-        for item in items {
-            self.items.push(item.to_string());
+    /// Adds multiple items with associated values to the selector.
+    ///
+    /// The first element of each pair is displayed, the second one is what
+    /// [interact](#method.interact) returns.
+    pub fn items_with_values<S: ToString>(&mut self, items: Vec<(S, T)>) -> &mut Sort<'a, T> {
+        for (item, value) in items {
+            self.item_with_value(item, value);
         }
         self
     }
 
+    /// Sets the order in which the items are initially presented.
+    ///
+    /// The order is given as a list of item indices where the first entry
+    /// is the index of the item shown at the top.  This makes it possible
+    /// to restore a previously saved arrangement.  It must be a
+    /// permutation of the items, otherwise the interaction fails with an
+    /// error of kind `InvalidInput`.
+    pub fn initial_order(&mut self, indices: &[usize]) -> &mut Sort<'a, T> {
+        self.initial_order = Some(indices.to_vec());
+        self
+    }
+
+    /// Locks items in place.
+    ///
+    /// Locked items are referenced by their original index.  They keep
+    /// their position, cannot be picked up and are never displaced by a
+    /// moving item.  The cursor skips over them.
+    pub fn with_locked(&mut self, indices: &[usize]) -> &mut Sort<'a, T> {
+        self.locked = indices.to_vec();
+        self
+    }
+
+    /// Registers a validator for the final order.
+    ///
+    /// The validator is invoked with the current order, given as item
+    /// indices like [initial_order](#method.initial_order), when the user
+    /// submits.  If it fails the error is shown below the list and the user
+    /// can continue to reorder the items.
+    pub fn validate_with<F, E>(&mut self, validator: F) -> &mut Sort<'a, T>
+    where
+        F: Fn(&[usize]) -> Result<(), E> + 'a,
+        E: Display,
+    {
+        self.validator = Some(Box::new(move |order: &[usize]| {
+            validator(order).err().map(|err| err.to_string())
+        }));
+        self
+    }
+
     /// Prefaces the menu with a prompt.
     ///
     /// When a prompt is set the system also prints out a confirmation after
     /// the selection.
-    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Sort<'a> {
+    pub fn with_prompt<S: Into<String>>(&mut self, prompt: S) -> &mut Sort<'a, T> {
         self.prompt = Some(prompt.into());
         self
     }
 
-    /// Enables user interaction and returns the result.
+    /// Overrides the prompt prefix of the theme for this prompt.
     ///
-    /// The user can order the items with the space bar and the arrows.
-    /// On enter the ordered list will be returned.
-    pub fn interact(&self) -> io::Result<Vec<usize>> {
-        self.interact_on(&Term::stderr())
+    /// Themes without a prompt prefix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_prefix(&mut self, prefix: StyledObject<String>) -> &mut Sort<'a, T> {
+        self.options.prompt_prefix = Some(prefix);
+        self
     }
 
-    /// Like [interact](#method.interact) but allows a specific terminal to be set.
-    pub fn interact_on(&self, term: &Term) -> io::Result<Vec<usize>> {
-        let mut page = 0;
+    /// Overrides the prompt suffix of the theme for this prompt.
+    ///
+    /// Themes without a prompt suffix, such as
+    /// [SimpleTheme](theme/struct.SimpleTheme.html), ignore this.
+    pub fn with_prompt_suffix(&mut self, suffix: StyledObject<String>) -> &mut Sort<'a, T> {
+        self.options.prompt_suffix = Some(suffix);
+        self
+    }
 
-        let capacity = if self.paged {
-            term.size().0 as usize - 1
-        } else {
-            self.items.len()
+    /// Overrides the separator between the prompt and its answer once the
+    /// prompt is done, for example `" → "`.
+    pub fn with_prompt_separator(&mut self, separator: StyledObject<String>) -> &mut Sort<'a, T> {
+        self.options.prompt_separator = Some(separator);
+        self
+    }
+
+    /// Shows the step of this prompt in a multi step flow, such as
+    /// `(step 2/5)`.
+    ///
+    /// `current` starts at 1.  The theme decides how the badge looks.
+    pub fn with_step(&mut self, current: usize, total: usize) -> &mut Sort<'a, T> {
+        self.options.step = Some((current, total));
+        self
+    }
+
+    /// Indents every line of the prompt by `indent` spaces.
+    ///
+    /// This nests the prompt in surrounding output.  The default is no
+    /// indent.
+    pub fn with_indent(&mut self, indent: usize) -> &mut Sort<'a, T> {
+        self.options.indent = indent;
+        self
+    }
+
+    /// Indicates whether to report the answered prompt once it is done.
+    ///
+    /// The interactive prompt is still cleared as usual.  The default is
+    /// `true`.
+    pub fn report(&mut self, val: bool) -> &mut Sort<'a, T> {
+        self.options.report = val;
+        self
+    }
+
+    /// Sets where [interact](#method.interact) and the other methods which
+    /// do not take a terminal render the prompt.
+    ///
+    /// The default is stderr, which keeps the prompt out of the output when
+    /// stdout is piped into another program.  Prompts rendered on stdout
+    /// become part of that output instead, and they fail with
+    /// [Error::NotATerminal](enum.Error.html#variant.NotATerminal) if
+    /// stdout is not a terminal, unless answers come from
+    /// [set_non_interactive_input](fn.set_non_interactive_input.html).
+    pub fn with_output(&mut self, output: OutputStream) -> &mut Sort<'a, T> {
+        self.output = output;
+        self
+    }
+
+    /// Echoes a plain text transcript of the prompt to `writer`.
+    ///
+    /// The answered prompt and any error lines are written without styles,
+    /// for example to keep an audit log, while the interactive prompt
+    /// still goes to the terminal.
+    pub fn with_mirror<W: io::Write + 'a>(&mut self, writer: W) -> &mut Sort<'a, T> {
+        self.options.mirror = Some(RefCell::new(Box::new(writer)));
+        self
+    }
+
+    /// Calls `f` with the new number of rows and columns after the terminal
+    /// was resized.
+    ///
+    /// The prompt redraws itself at the new size, so this is only needed for
+    /// output of your own.  console does not report resizes, so they are
+    /// noticed when the prompt redraws after the next key press.
+    pub fn on_resize<F: FnMut(u16, u16) + 'a>(&mut self, f: F) -> &mut Sort<'a, T> {
+        self.options.on_resize = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    /// Sets the keys the prompt reacts to.
+    ///
+    /// The default are the [default bindings](struct.KeyBindings.html).
+    pub fn with_keys(&mut self, keys: &KeyBindings) -> &mut Sort<'a, T> {
+        self.keys = keys.clone();
+        self
+    }
+
+    /// Truncates items wider than the terminal.
+    ///
+    /// Truncated items end with the ellipsis of the theme.  The default is
+    /// to let long items wrap.
+    pub fn truncate_items(&mut self, val: bool) -> &mut Sort<'a, T> {
+        self.truncate_items = val;
+        self
+    }
+
+    /// Lays the prompt out for right-to-left languages.
+    ///
+    /// Lines are aligned to the right and the symbols of the theme are put
+    /// on the other side of the prompt and the items.  The default is
+    /// left-to-right.
+    pub fn right_to_left(&mut self, val: bool) -> &mut Sort<'a, T> {
+        self.right_to_left = val;
+        self
+    }
+
+    /// Returns the items in the order described by `order`.
+    fn ordered_items(&self, order: &[usize]) -> Vec<&str> {
+        order.iter().map(|&idx| self.items[idx].as_str()).collect()
+    }
+
+    async fn _interact_on(
+        &self,
+        keys: &KeyReader<'_>,
+        allow_quit: bool,
+    ) -> Result<Option<Vec<usize>>> {
+        let term = keys.term();
+
+        if self.items.is_empty() {
+            return Ok(Some(vec![]));
+        }
+
+        let order: Vec<_> = match self.initial_order {
+            Some(ref order) if is_permutation(order, self.items.len()) => order.clone(),
+            Some(_) => {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "initial order is not a permutation of the items",
+                )))
+            }
+            None => (0..self.items.len()).collect(),
         };
 
-        let pages = (self.items.len() as f64 / capacity as f64).ceil() as usize;
+        if non_interactive::is_active(term) {
+            return self.answer_order(term, order).map(Some);
+        }
+
+        // One renderer for all stages, so the prompt is shown only once.
+        let mut render = self.renderer(term);
+
+        if !self.pairwise {
+            return self
+                .reorder(keys, &mut render, order, None, allow_quit)
+                .await;
+        }
+
+        let order = match self
+            .rank_pairwise(keys, &mut render, order, allow_quit)
+            .await?
+        {
+            Some(order) => order,
+            None => return Ok(None),
+        };
+
+        match self.validator.as_ref().and_then(|v| v(&order)) {
+            Some(err) => {
+                self.reorder(keys, &mut render, order, Some(err), allow_quit)
+                    .await
+            }
+            None => {
+                if let Some(ref prompt) = self.prompt {
+                    render.sort_prompt_selection(prompt, &self.ordered_items(&order)[..])?;
+                }
+
+                term.flush()?;
+
+                Ok(Some(order))
+            }
+        }
+    }
+
+    /// Creates a renderer with the options of this prompt.
+    fn renderer<'r>(&'r self, term: &'r dyn Backend) -> TermThemeRenderer<'r> {
         let mut render = TermThemeRenderer::new(term, self.theme);
-        let mut sel = 0;
+        self.options.apply(&mut render);
+        render.set_truncate_items(self.truncate_items);
+        render.set_right_to_left(self.right_to_left);
+        render
+    }
+
+    /// Reads the order from the input set with
+    /// [set_non_interactive_input](../fn.set_non_interactive_input.html).
+    fn answer_order(&self, term: &dyn Backend, initial: Vec<usize>) -> Result<Vec<usize>> {
+        let mut render = self.renderer(term);
+
+        let order = non_interactive::answer(&mut render, |answer| {
+            let order = match non_interactive::find_items(&self.items, answer)? {
+                order if order.is_empty() => initial.clone(),
+                order if is_permutation(&order, self.items.len()) => order,
+                _ => return Err("Every item must be listed once".to_string()),
+            };
+
+            let moved_locked = order
+                .iter()
+                .zip(initial.iter())
+                .any(|(idx, prev)| idx != prev && self.locked.contains(idx));
+            if moved_locked {
+                return Err("Locked items cannot be moved".to_string());
+            }
+
+            match self.validator.as_ref().and_then(|v| v(&order)) {
+                Some(err) => Err(err),
+                None => Ok(order),
+            }
+        })?;
+
+        if let Some(ref prompt) = self.prompt {
+            render.sort_prompt_selection(prompt, &self.ordered_items(&order)[..])?;
+        }
+
+        term.flush()?;
+
+        Ok(order)
+    }
+
+    /// Builds an order by asking the user to compare pairs of items.
+    async fn rank_pairwise(
+        &self,
+        keys: &KeyReader<'_>,
+        render: &mut TermThemeRenderer<'_>,
+        order: Vec<usize>,
+        allow_quit: bool,
+    ) -> Result<Option<Vec<usize>>> {
+        let term = keys.term();
 
         if let Some(ref prompt) = self.prompt {
             render.sort_prompt(prompt)?;
         }
 
-        let mut size_vec = Vec::new();
+        let (locked, unlocked): (Vec<_>, Vec<_>) = order
+            .iter()
+            .enumerate()
+            .partition(|(_, idx)| self.locked.contains(idx));
+        let mut ranked: Vec<usize> = Vec::with_capacity(order.len());
+
+        let _cursor = HiddenCursor::new(term)?;
+
+        for (_, &candidate) in unlocked {
+            let (mut low, mut high) = (0, ranked.len());
+
+            while low < high {
+                let mid = (low + high) / 2;
+                let (left, right) = (&self.items[candidate], &self.items[ranked[mid]]);
+
+                render.sort_prompt_pair(left, right)?;
+                term.flush()?;
+
+                let candidate_higher = loop {
+                    match self.keys.translate(keys.read_key().await?) {
+                        Key::ArrowLeft => break Ok(true),
+                        Key::ArrowRight => break Ok(false),
+                        key @ (Key::Escape | CANCEL) if allow_quit => break Err(key),
+                        _ => {}
+                    }
+                };
 
-        for items in self.items.iter().as_slice() {
-            let size = &items.len();
-            size_vec.push(*size);
+                render.clear_preserve_prompt()?;
+
+                match candidate_higher {
+                    Ok(true) => high = mid,
+                    Ok(false) => low = mid + 1,
+                    Err(key) => {
+                        if self.clear {
+                            render.clear()?;
+                        }
+
+                        term.show_cursor()?;
+                        term.flush()?;
+
+                        return quit(key);
+                    }
+                }
+            }
+
+            ranked.insert(low, candidate);
         }
 
-        let mut order: Vec<_> = (0..self.items.len()).collect();
+        for (pos, &idx) in locked {
+            ranked.insert(pos, idx);
+        }
+
+        render.clear()?;
+        term.show_cursor()?;
+        term.flush()?;
+
+        Ok(Some(ranked))
+    }
+
+    /// Lets the user reorder the items manually.
+    async fn reorder(
+        &self,
+        keys: &KeyReader<'_>,
+        render: &mut TermThemeRenderer<'_>,
+        mut order: Vec<usize>,
+        mut error: Option<String>,
+        allow_quit: bool,
+    ) -> Result<Option<Vec<usize>>> {
+        let term = keys.term();
+        let mut page = 0;
+
+        let capacity = match self.max_length {
+            Some(max_length) => max_length.clamp(1, self.items.len().max(1)),
+            // leaves room for the prompt and the page indicator
+            None if self.paged => (term_size(term).0 as usize).saturating_sub(2).max(1),
+            None => self.items.len(),
+        };
+
+        let pages = (self.items.len() as f64 / capacity as f64).ceil() as usize;
+
+        if let Some(ref prompt) = self.prompt {
+            render.sort_prompt(prompt)?;
+        }
+
+        // locked items never move, so this can be computed up front
+        let locked: Vec<bool> = order.iter().map(|idx| self.locked.contains(idx)).collect();
+        let mut sel = if locked.first() == Some(&true) {
+            step(0, true, &locked)
+        } else {
+            0
+        };
         let mut checked: bool = false;
+        let mut offset = 0;
+        let mut count: usize = 0;
+        let _cursor = HiddenCursor::new(term)?;
 
         loop {
-            for (idx, item) in order
-                .iter()
-                .enumerate()
-                .skip(page * capacity)
-                .take(capacity)
-            {
-                render.sort_prompt_item(&self.items[*item], checked, sel == idx)?;
+            if self.paged {
+                if sel < page * capacity || sel >= (page + 1) * capacity {
+                    page = sel / capacity;
+                }
+
+                offset = page * capacity;
+            } else if sel < offset {
+                offset = sel;
+            } else if sel >= offset + capacity {
+                offset = sel + 1 - capacity;
+            }
+
+            for (idx, item) in order.iter().enumerate().skip(offset).take(capacity) {
+                let grab_symbol = self
+                    .grab_symbol
+                    .as_deref()
+                    .filter(|_| checked && sel == idx);
+
+                if let Some(symbol) = grab_symbol {
+                    if self.show_positions {
+                        render.sort_prompt_grabbed_item_with_position(
+                            &self.items[*item],
+                            symbol,
+                            idx + 1,
+                            order.len(),
+                        )?;
+                    } else {
+                        render.sort_prompt_grabbed_item(&self.items[*item], symbol)?;
+                    }
+                } else if self.show_positions {
+                    render.sort_prompt_item_with_position(
+                        &self.items[*item],
+                        idx + 1,
+                        order.len(),
+                        checked,
+                        sel == idx,
+                    )?;
+                } else {
+                    render.sort_prompt_item(&self.items[*item], checked, sel == idx)?;
+                }
+            }
+
+            if self.paged && pages > 1 {
+                render.page_indicator(page + 1, pages)?;
+            }
+
+            if let Some(err) = error.take() {
+                render.error(&err)?;
             }
 
-            term.hide_cursor()?;
             term.flush()?;
 
-            match term.read_key()? {
-                Key::ArrowDown | Key::Char('j') => {
-                    let old_sel = sel;
+            let key = self.keys.translate(keys.read_key().await?);
 
-                    if sel == !0 {
-                        sel = 0;
-                    } else {
-                        sel = (sel as u64 + 1).rem(self.items.len() as u64) as usize;
+            match key {
+                Key::Char(chr @ '0'..='9') => {
+                    let digit = chr.to_digit(10).unwrap() as usize;
+                    count = (count * 10 + digit).min(self.items.len());
+                }
+                Key::ArrowDown => {
+                    for _ in 0..count.max(1) {
+                        let old_sel = sel;
+                        sel = step(sel, true, &locked);
+
+                        if checked && old_sel != sel {
+                            order.swap(old_sel, sel);
+                        }
                     }
+                }
+                Key::ArrowUp => {
+                    for _ in 0..count.max(1) {
+                        let old_sel = sel;
+                        sel = step(sel, false, &locked);
 
-                    if checked && old_sel != sel {
-                        order.swap(old_sel, sel);
+                        if checked && old_sel != sel {
+                            order.swap(old_sel, sel);
+                        }
                     }
                 }
-                Key::ArrowUp | Key::Char('k') => {
+                Key::ArrowLeft if self.paged => {
                     let old_sel = sel;
+                    let old_page = page;
 
-                    if sel == !0 {
-                        sel = self.items.len() - 1;
+                    if page == 0 {
+                        page = pages - 1;
                     } else {
-                        sel = ((sel as i64 - 1 + self.items.len() as i64)
-                            % (self.items.len() as i64)) as usize;
+                        page -= 1;
                     }
 
-                    if checked && old_sel != sel {
-                        order.swap(old_sel, sel);
+                    sel = page * capacity;
+                    if locked[sel] {
+                        sel = step(sel, true, &locked);
                     }
-                }
-                Key::ArrowLeft | Key::Char('h') => {
-                    if self.paged {
-                        let old_sel = sel;
-                        let old_page = page;
 
-                        if page == 0 {
-                            page = pages - 1;
+                    if checked {
+                        let indexes: Vec<_> = if old_page == 0 {
+                            let indexes1: Vec<_> = (0..=old_sel).rev().collect();
+                            let indexes2: Vec<_> = (sel..self.items.len()).rev().collect();
+                            [indexes1, indexes2].concat()
                         } else {
-                            page -= 1;
-                        }
-
-                        sel = page * capacity;
-
-                        if checked {
-                            let indexes: Vec<_> = if old_page == 0 {
-                                let indexes1: Vec<_> = (0..=old_sel).rev().collect();
-                                let indexes2: Vec<_> = (sel..self.items.len()).rev().collect();
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (sel..=old_sel).rev().collect()
-                            };
+                            (sel..=old_sel).rev().collect()
+                        };
+                        let indexes: Vec<_> =
+                            indexes.into_iter().filter(|&idx| !locked[idx]).collect();
 
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
+                        for index in 0..indexes.len().saturating_sub(1) {
+                            order.swap(indexes[index], indexes[index + 1]);
                         }
                     }
                 }
-                Key::ArrowRight | Key::Char('l') => {
-                    if self.paged {
-                        let old_sel = sel;
-                        let old_page = page;
+                Key::ArrowRight if self.paged => {
+                    let old_sel = sel;
+                    let old_page = page;
 
-                        if page == pages - 1 {
-                            page = 0;
-                        } else {
-                            page += 1;
-                        }
+                    if page == pages - 1 {
+                        page = 0;
+                    } else {
+                        page += 1;
+                    }
 
-                        sel = page * capacity;
+                    sel = page * capacity;
+                    if locked[sel] {
+                        sel = step(sel, true, &locked);
+                    }
 
-                        if checked {
-                            let indexes: Vec<_> = if old_page == pages - 1 {
-                                let indexes1: Vec<_> = (old_sel..self.items.len()).collect();
-                                let indexes2: Vec<_> = vec![0];
-                                [indexes1, indexes2].concat()
-                            } else {
-                                (old_sel..=sel).collect()
-                            };
+                    if checked {
+                        let indexes: Vec<_> = if old_page == pages - 1 {
+                            let indexes1: Vec<_> = (old_sel..self.items.len()).collect();
+                            let indexes2: Vec<_> = vec![0];
+                            [indexes1, indexes2].concat()
+                        } else {
+                            (old_sel..=sel).collect()
+                        };
+                        let indexes: Vec<_> =
+                            indexes.into_iter().filter(|&idx| !locked[idx]).collect();
 
-                            for index in 0..(indexes.len() - 1) {
-                                order.swap(indexes[index], indexes[index + 1]);
-                            }
+                        for index in 0..indexes.len().saturating_sub(1) {
+                            order.swap(indexes[index], indexes[index + 1]);
                         }
                     }
                 }
-                Key::Char(' ') => {
+                Key::Char(' ') if !locked[sel] => {
                     checked = !checked;
                 }
-                // TODO: Key::Escape
-                Key::Enter => {
+                key @ (Key::Escape | CANCEL) if allow_quit => {
                     if self.clear {
                         render.clear()?;
                     }
 
-                    if let Some(ref prompt) = self.prompt {
-                        let list: Vec<_> = order
-                            .iter()
-                            .enumerate()
-                            .map(|(_, item)| self.items[*item].as_str())
-                            .collect();
-                        render.sort_prompt_selection(prompt, &list[..])?;
-                    }
-
                     term.show_cursor()?;
                     term.flush()?;
 
-                    return Ok(order);
+                    return quit(key);
                 }
+                Key::Enter => match self.validator.as_ref().and_then(|v| v(&order)) {
+                    Some(err) => error = Some(err),
+                    None => {
+                        if self.clear {
+                            render.clear()?;
+                        }
+
+                        if let Some(ref prompt) = self.prompt {
+                            render
+                                .sort_prompt_selection(prompt, &self.ordered_items(&order)[..])?;
+                        }
+
+                        term.show_cursor()?;
+                        term.flush()?;
+
+                        return Ok(Some(order));
+                    }
+                },
                 _ => {}
             }
 
-            if sel < page * capacity || sel >= (page + 1) * capacity {
-                page = sel / capacity;
+            if !matches!(key, Key::Char('0'..='9')) {
+                count = 0;
             }
 
-            render.clear_preserve_prompt(&size_vec)?;
+            render.clear_preserve_prompt()?;
         }
     }
 }
+
+impl<'a, T: Clone> Sort<'a, T> {
+    /// Enables user interaction and returns the result.
+    ///
+    /// The user can order the items with the space bar and the arrows.
+    /// Typing a number before an up or down movement repeats it that many
+    /// times, so `5` followed by down moves a picked up item by five
+    /// positions.  On enter the ordered list will be returned.
+    ///
+    /// The result contains the values of the items in the chosen order.
+    /// For a plain `Sort` the values are the indices of the items, so the
+    /// result maps new positions to original indices: `result[i]` is the
+    /// index (as passed to [item](#method.item) or [items](#method.items))
+    /// of the item that ended up at position `i`.
+    pub fn interact(&self) -> Result<Vec<T>> {
+        self.interact_on(&self.output.term())
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// Like [interact](#method.interact), but the user can cancel with
+    /// 'Esc' or Ctrl+C, in which case `None` is returned and the caller
+    /// keeps the original order.
+    pub fn interact_opt(&self) -> Result<Option<Vec<T>>> {
+        self.interact_on_opt(&self.output.term())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &dyn Backend) -> Result<Vec<T>> {
+        self.interact_on_opt_with(term, false)?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::Other,
+                "Quit not allowed in this case",
+            ))
+        })
+    }
+
+    /// Like [interact_opt](#method.interact_opt) but allows a specific terminal to be set.
+    #[inline]
+    pub fn interact_on_opt(&self, term: &dyn Backend) -> Result<Option<Vec<T>>> {
+        cancel_on_interrupt(self.interact_on_step(term))
+    }
+
+    /// Like [interact_on_opt](#method.interact_on_opt), but Ctrl+C and
+    /// [Action::Cancel](enum.Action.html) fail with `Error::Interrupted`, so
+    /// that a [Form](struct.Form.html) stops instead of going back.
+    pub(crate) fn interact_on_step(&self, term: &dyn Backend) -> Result<Option<Vec<T>>> {
+        self.interact_on_opt_with(term, true)
+    }
+
+    /// Async variant of [interact](#method.interact).
+    ///
+    /// Key presses are read on a helper thread, so waiting for the user
+    /// does not block the executor.  This requires the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn interact_async(&self) -> Result<Vec<T>> {
+        self.interact_on_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_opt](#method.interact_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_opt_async(&self) -> Result<Option<Vec<T>>> {
+        self.interact_on_opt_async(&self.output.term()).await
+    }
+
+    /// Async variant of [interact_on](#method.interact_on).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_async(&self, term: &Term) -> Result<Vec<T>> {
+        self.interact_with(&KeyReader::Threaded(term), false)
+            .await?
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Quit not allowed in this case",
+                ))
+            })
+    }
+
+    /// Async variant of [interact_on_opt](#method.interact_on_opt).
+    #[cfg(feature = "async")]
+    pub async fn interact_on_opt_async(&self, term: &Term) -> Result<Option<Vec<T>>> {
+        cancel_on_interrupt(self.interact_with(&KeyReader::Threaded(term), true).await)
+    }
+
+    fn interact_on_opt_with(&self, term: &dyn Backend, allow_quit: bool) -> Result<Option<Vec<T>>> {
+        block_on(self.interact_with(&KeyReader::Blocking(term), allow_quit))
+    }
+
+    async fn interact_with(
+        &self,
+        keys: &KeyReader<'_>,
+        allow_quit: bool,
+    ) -> Result<Option<Vec<T>>> {
+        Ok(self._interact_on(keys, allow_quit).await?.map(|order| {
+            order
+                .into_iter()
+                .map(|idx| self.values[idx].clone())
+                .collect()
+        }))
+    }
+}
+
+/// Returns the next position in the given direction which is not locked.
+///
+/// Wraps around at both ends and stays in place if every other position
+/// is locked.
+fn step(pos: usize, forward: bool, locked: &[bool]) -> usize {
+    let len = locked.len();
+    let mut next = pos;
+
+    for _ in 0..len {
+        next = if forward {
+            (next + 1) % len
+        } else {
+            (next + len - 1) % len
+        };
+
+        if !locked[next] {
+            return next;
+        }
+    }
+
+    pos
+}
+
+fn is_permutation(order: &[usize], len: usize) -> bool {
+    let mut seen = vec![false; len];
+
+    order.len() == len
+        && order
+            .iter()
+            .all(|&idx| idx < len && !std::mem::replace(&mut seen[idx], true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::TestTerm;
+
+    #[test]
+    fn test_order_maps_positions_to_original_indices() {
+        let term = TestTerm::new();
+        // moves the item originally at index 2 to the top
+        term.push_keys(vec![
+            Key::ArrowDown,
+            Key::ArrowDown,
+            Key::Char(' '),
+            Key::ArrowUp,
+            Key::ArrowUp,
+            Key::Char(' '),
+            Key::Enter,
+        ]);
+
+        let items = ["a", "b", "c"];
+        let result: Vec<usize> = Sort::new().items(&items).interact_on(&term).unwrap();
+        assert_eq!(result, vec![2, 0, 1]);
+
+        let moved: Vec<&str> = result.iter().map(|&idx| items[idx]).collect();
+        assert_eq!(moved, vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_step_skips_locked() {
+        let locked = [false, true, false, true];
+        assert_eq!(step(0, true, &locked), 2);
+        assert_eq!(step(2, true, &locked), 0);
+        assert_eq!(step(0, false, &locked), 2);
+        assert_eq!(step(0, true, &[false, true]), 0);
+        assert_eq!(step(0, true, &[]), 0);
+    }
+
+    #[test]
+    fn test_empty_items() {
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char(' '), Key::Enter]);
+
+        let answer: Result<Vec<usize>> = Sort::new().with_locked(&[0]).interact_on(&term);
+        assert!(answer.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_is_permutation() {
+        assert!(is_permutation(&[2, 0, 1], 3));
+        assert!(is_permutation(&[], 0));
+        assert!(!is_permutation(&[0, 1], 3));
+        assert!(!is_permutation(&[0, 0, 1], 3));
+        assert!(!is_permutation(&[0, 1, 3], 3));
+    }
+}
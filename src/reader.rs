@@ -0,0 +1,235 @@
+use std::{
+    future::Future,
+    io,
+    pin::pin,
+    ptr,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use console::Key;
+#[cfg(feature = "async")]
+use console::Term;
+
+use crate::{
+    backend::Backend,
+    events::{self, PromptEvent},
+};
+
+/// Source of key presses for the interaction loops of the prompts.
+///
+/// The loops are written as futures so that the same code backs the
+/// blocking and the async variants of a prompt.  A blocking reader never
+/// makes a future wait, which lets [block_on](fn.block_on.html) drive it
+/// without an executor.
+pub(crate) enum KeyReader<'a> {
+    /// Reads keys on the calling thread.
+    Blocking(&'a dyn Backend),
+    /// Reads keys on a helper thread and wakes the task once one arrived.
+    #[cfg(feature = "async")]
+    Threaded(&'a Term),
+}
+
+impl<'a> KeyReader<'a> {
+    /// Returns the terminal keys are read from.
+    pub(crate) fn term(&self) -> &'a dyn Backend {
+        match *self {
+            KeyReader::Blocking(term) => term,
+            #[cfg(feature = "async")]
+            KeyReader::Threaded(term) => term,
+        }
+    }
+
+    /// Reads a single key press.
+    ///
+    /// The key press is reported to the event listener from here, so that
+    /// it is called on the thread of the prompt even if a helper thread
+    /// read the key.
+    pub(crate) async fn read_key(&self) -> io::Result<Key> {
+        let key = match *self {
+            KeyReader::Blocking(term) => read_key(term),
+            #[cfg(feature = "async")]
+            KeyReader::Threaded(term) => threaded::read_key(term).await,
+        }?;
+        events::emit(PromptEvent::KeyPressed);
+        Ok(key)
+    }
+
+    /// Reads a line, which starts out as `initial` if given.
+    pub(crate) async fn read_line(&self, initial: Option<&str>) -> io::Result<String> {
+        let rv = match *self {
+            KeyReader::Blocking(term) => match initial {
+                Some(initial) => term.read_line_initial_text(initial),
+                None => term.read_line(),
+            },
+            #[cfg(feature = "async")]
+            KeyReader::Threaded(term) => threaded::read_line(term, initial).await,
+        };
+        rv.map_err(|err| check_interrupt(self.term(), err))
+    }
+
+    /// Reads a line without echoing it.
+    pub(crate) async fn read_secure_line(&self) -> io::Result<String> {
+        let rv = match *self {
+            KeyReader::Blocking(term) => term.read_secure_line(),
+            #[cfg(feature = "async")]
+            KeyReader::Threaded(term) => threaded::read_secure_line(term).await,
+        };
+        rv.map_err(|err| check_interrupt(self.term(), err))
+    }
+}
+
+/// Reads a single key press on the calling thread.
+///
+/// console reports every key as unknown if the terminal is not attended
+/// by a user, which would make the prompts wait forever.  This reports an
+/// error of kind `NotConnected` instead.
+pub(crate) fn read_key(term: &dyn Backend) -> io::Result<Key> {
+    match term.read_key() {
+        Ok(Key::Unknown) if !term.is_term() => Err(io::Error::new(
+            io::ErrorKind::NotConnected,
+            "Not a terminal",
+        )),
+        Ok(key) => Ok(key),
+        Err(err) => Err(check_interrupt(term, err)),
+    }
+}
+
+/// Shows the cursor again if `err` means that the user pressed Ctrl+C.
+///
+/// console leaves raw mode before it reports Ctrl+C as an error of kind
+/// `Interrupted`, but the prompts hide the cursor while waiting for keys.
+/// All reads of the prompts pass their errors through here, so that
+/// callers always get [Error::Interrupted](../enum.Error.html#variant.Interrupted).
+pub(crate) fn check_interrupt(term: &dyn Backend, err: io::Error) -> io::Error {
+    if err.kind() != io::ErrorKind::Interrupted {
+        return err;
+    }
+
+    // The original error is more useful than a failure to restore.
+    let _ = term.show_cursor().and_then(|_| term.flush());
+    io::Error::new(io::ErrorKind::Interrupted, "interrupted by the user")
+}
+
+/// Drives a future which never has to wait, such as one reading keys with
+/// a blocking [KeyReader](enum.KeyReader.html).
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    match future.as_mut().poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("blocking future had to wait"),
+    }
+}
+
+/// Returns a waker which does nothing, as futures driven by
+/// [block_on](fn.block_on.html) are never woken.
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+
+    fn noop(_: *const ()) {}
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    // The functions of the vtable never touch the data pointer.
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+#[cfg(feature = "async")]
+mod threaded {
+    use std::{
+        future::Future,
+        io,
+        pin::Pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll, Waker},
+        thread,
+    };
+
+    use console::{Key, Term};
+
+    struct State<T> {
+        result: Option<T>,
+        waker: Option<Waker>,
+    }
+
+    /// Future resolving to the result of a closure run on a helper thread.
+    ///
+    /// The thread keeps running if the future is dropped early.
+    pub(crate) struct Blocking<T> {
+        state: Arc<Mutex<State<T>>>,
+    }
+
+    /// Runs a blocking closure on a new thread.
+    pub(crate) fn spawn_blocking<T, F>(f: F) -> Blocking<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(State {
+            result: None,
+            waker: None,
+        }));
+        let thread_state = state.clone();
+
+        thread::spawn(move || {
+            let result = f();
+            let mut state = thread_state.lock().unwrap();
+            state.result = Some(result);
+
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Blocking { state }
+    }
+
+    impl<T> Future for Blocking<T> {
+        type Output = T;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+            let mut state = self.state.lock().unwrap();
+
+            match state.result.take() {
+                Some(result) => Poll::Ready(result),
+                None => {
+                    state.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// Reads the next key press on a helper thread.
+    ///
+    /// A thread is spawned for every key so that no read is left pending
+    /// once a prompt is done.  If the future is dropped early the thread
+    /// still consumes the next key press.
+    pub(super) fn read_key(term: &Term) -> Blocking<io::Result<Key>> {
+        let term = term.clone();
+        spawn_blocking(move || super::read_key(&term))
+    }
+
+    /// Reads a line on a helper thread.
+    pub(super) fn read_line(term: &Term, initial: Option<&str>) -> Blocking<io::Result<String>> {
+        let term = term.clone();
+        let initial = initial.map(str::to_string);
+        spawn_blocking(move || match initial {
+            Some(initial) => term.read_line_initial_text(&initial),
+            None => term.read_line(),
+        })
+    }
+
+    /// Reads a line without echoing it on a helper thread.
+    pub(super) fn read_secure_line(term: &Term) -> Blocking<io::Result<String>> {
+        let term = term.clone();
+        spawn_blocking(move || term.read_secure_line())
+    }
+}
+
+#[cfg(feature = "async")]
+pub(crate) use self::threaded::spawn_blocking;
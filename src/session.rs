@@ -0,0 +1,309 @@
+//! Provides a session to run several prompts on one terminal
+#[cfg(unix)]
+use std::{
+    fs::File,
+    io::IsTerminal,
+    os::unix::io::{AsRawFd, RawFd},
+};
+use std::{
+    io,
+    sync::{Mutex, MutexGuard},
+};
+
+use console::{Key, Term};
+
+use crate::backend::Backend;
+
+/// Whether the cursor is visible, and whether the prompts want it to be.
+struct Cursor {
+    visible: bool,
+    wanted: bool,
+}
+
+/// Raw mode of the terminal console reads keys from, kept for a session.
+#[cfg(unix)]
+struct RawMode {
+    fd: RawFd,
+    _tty: Option<File>,
+    original: termios::Termios,
+    raw: termios::Termios,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    /// Switches the terminal to raw mode.
+    ///
+    /// Output processing stays on, so line breaks written between reads
+    /// still return the cursor to the start of the line.
+    fn enter() -> io::Result<RawMode> {
+        let stdin = io::stdin();
+        let (fd, tty) = if stdin.is_terminal() {
+            (stdin.as_raw_fd(), None)
+        } else {
+            let tty = File::open("/dev/tty")?;
+            (tty.as_raw_fd(), Some(tty))
+        };
+
+        let original = termios::Termios::from_fd(fd)?;
+        let mut raw = original;
+        termios::cfmakeraw(&mut raw);
+        raw.c_oflag |= termios::OPOST;
+
+        let mode = RawMode {
+            fd,
+            _tty: tty,
+            original,
+            raw,
+        };
+        mode.resume()?;
+        Ok(mode)
+    }
+
+    /// Restores the mode the terminal had before the session.
+    fn suspend(&self) -> io::Result<()> {
+        termios::tcsetattr(self.fd, termios::TCSADRAIN, &self.original)
+    }
+
+    fn resume(&self) -> io::Result<()> {
+        termios::tcsetattr(self.fd, termios::TCSADRAIN, &self.raw)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // Errors cannot be reported from here.
+        let _ = self.suspend();
+    }
+}
+
+/// Terminal shared by several prompts in a row.
+///
+/// console switches the terminal to raw mode for every key it reads and
+/// back afterwards, and every prompt hides the cursor while it waits for
+/// keys and shows it again once it is done.  Between several prompts this
+/// flickers and costs round trips on slow terminals.  A session enters raw
+/// mode once when it starts, defers cursor changes until the next read and
+/// restores both once it is dropped.  Pass it to [Prompt::interact_in](trait.Prompt.html#method.interact_in) or to
+/// the `interact_on` methods of the prompts:
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use dialoguer::{console::Term, Prompt, Select, Session};
+///
+/// let term = Term::stderr();
+/// let session = Session::new(&term);
+///
+/// let mut color = Select::new();
+/// color.items(&["red", "green"]).default(0);
+/// let mut size = Select::new();
+/// size.items(&["small", "large"]).default(0);
+///
+/// let color = color.interact_in(&session)?;
+/// let size = size.interact_in(&session)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Raw mode is kept on Unix for console terminals attended by a user.
+/// Prompts which read whole lines, such as
+/// [Input::interact](struct.Input.html#method.interact) and
+/// [Password](struct.Password.html), leave it while they read.
+pub struct Session<'a> {
+    term: &'a dyn Backend,
+    cursor: Mutex<Cursor>,
+    #[cfg(unix)]
+    raw_mode: Option<RawMode>,
+}
+
+impl<'a> Session<'a> {
+    /// Starts a session on `term`.
+    ///
+    /// The terminal switches to raw mode if it is a console terminal
+    /// attended by a user.
+    pub fn new(term: &'a dyn Backend) -> Session<'a> {
+        Session {
+            term,
+            cursor: Mutex::new(Cursor {
+                visible: true,
+                wanted: true,
+            }),
+            #[cfg(unix)]
+            raw_mode: match term.as_term() {
+                Some(term) if term.is_term() => RawMode::enter().ok(),
+                _ => None,
+            },
+        }
+    }
+
+    /// Checks whether the session keeps the terminal in raw mode.
+    pub fn is_raw_mode(&self) -> bool {
+        #[cfg(unix)]
+        return self.raw_mode.is_some();
+        #[cfg(not(unix))]
+        return false;
+    }
+
+    fn cursor(&self) -> MutexGuard<'_, Cursor> {
+        self.cursor.lock().unwrap()
+    }
+
+    /// Runs `read` with the terminal out of raw mode, as lines are read
+    /// with the line editing of the terminal.
+    fn read_cooked<T, F: FnOnce() -> io::Result<T>>(&self, read: F) -> io::Result<T> {
+        #[cfg(unix)]
+        {
+            if let Some(ref raw_mode) = self.raw_mode {
+                raw_mode.suspend()?;
+                let rv = read();
+                raw_mode.resume()?;
+                return rv;
+            }
+        }
+
+        read()
+    }
+
+    /// Applies the cursor visibility the prompts asked for last.
+    fn sync_cursor(&self) -> io::Result<()> {
+        let mut cursor = self.cursor();
+        if cursor.visible != cursor.wanted {
+            if cursor.wanted {
+                self.term.show_cursor()?;
+            } else {
+                self.term.hide_cursor()?;
+            }
+            self.term.flush()?;
+            cursor.visible = cursor.wanted;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        self.cursor().wanted = true;
+        // Errors cannot be reported from here.
+        let _ = self.sync_cursor();
+    }
+}
+
+impl Backend for Session<'_> {
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.term.write_str(s)
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.term.write_line(s)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.term.flush()
+    }
+
+    fn read_key(&self) -> io::Result<Key> {
+        self.sync_cursor()?;
+        self.term.read_key()
+    }
+
+    fn read_line(&self) -> io::Result<String> {
+        self.sync_cursor()?;
+        self.read_cooked(|| self.term.read_line())
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        self.sync_cursor()?;
+        self.read_cooked(|| self.term.read_line_initial_text(initial))
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        self.sync_cursor()?;
+        self.read_cooked(|| self.term.read_secure_line())
+    }
+
+    fn is_term(&self) -> bool {
+        self.term.is_term()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        self.term.size()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_up(n)
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_down(n)
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_left(n)
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        self.term.move_cursor_right(n)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.term.clear_line()
+    }
+
+    fn clear_last_lines(&self, n: usize) -> io::Result<()> {
+        self.term.clear_last_lines(n)
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        self.term.clear_chars(n)
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        self.term.clear_to_end_of_screen()
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.cursor().wanted = true;
+        Ok(())
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.cursor().wanted = false;
+        Ok(())
+    }
+
+    fn as_term(&self) -> Option<&Term> {
+        self.term.as_term()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::TestTerm, Input, Prompt, Select};
+
+    #[test]
+    fn test_session() {
+        let term = TestTerm::new();
+        term.push_key(Key::Enter)
+            .push_key(Key::Enter)
+            .push_line("Ann");
+
+        let mut select = Select::new();
+        select.items(&["red", "green"]).default(0);
+
+        let session = Session::new(&term);
+        assert!(!session.is_raw_mode());
+        select.interact_in(&session).unwrap();
+        assert!(term.is_cursor_hidden());
+        select.interact_in(&session).unwrap();
+        assert!(term.is_cursor_hidden());
+
+        let name = Input::<String>::new().interact_in(&session).unwrap();
+        assert_eq!(name, "Ann");
+        assert!(!term.is_cursor_hidden());
+
+        select.interact_in(&session).unwrap_err();
+        drop(session);
+        assert!(!term.is_cursor_hidden());
+    }
+}
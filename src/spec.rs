@@ -0,0 +1,456 @@
+//! Provides prompts defined as data
+//!
+//! A [PromptSpec](enum.PromptSpec.html) describes a prompt without building
+//! it, so a list of questions can come from a configuration file and be
+//! run in one go with [interact_specs](fn.interact_specs.html):
+//!
+//! ```rust,no_run
+//! # fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! use dialoguer::{
+//!     spec::{interact_specs, Answer, PromptSpec},
+//!     theme::ColorfulTheme,
+//! };
+//!
+//! let specs = vec![
+//!     PromptSpec::Input {
+//!         name: "project".into(),
+//!         prompt: "Project name".into(),
+//!         default: None,
+//!     },
+//!     PromptSpec::Select {
+//!         name: "license".into(),
+//!         prompt: "License".into(),
+//!         items: vec!["MIT".into(), "Apache-2.0".into()],
+//!         default: Some(0),
+//!     },
+//! ];
+//!
+//! let answers = interact_specs(&specs, &ColorfulTheme::default())?;
+//! if let Some(Answer::Text(project)) = answers.get("project") {
+//!     println!("Creating {}", project);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `serde` feature the specs can be deserialized, for example from
+//! JSON, where the `type` field names the kind of prompt and optional
+//! fields may be left out.  [interact_specs_value](fn.interact_specs_value.html)
+//! returns the answers as a JSON object keyed by name:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "serde")]
+//! # fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! use dialoguer::{
+//!     spec::{interact_specs_value, PromptSpec},
+//!     theme::ColorfulTheme,
+//! };
+//!
+//! let specs: Vec<PromptSpec> = serde_json::from_str(
+//!     r#"[
+//!         {"type": "input", "name": "project", "prompt": "Project name"},
+//!         {"type": "confirm", "name": "git", "prompt": "Init git?", "default": true}
+//!     ]"#,
+//! )?;
+//!
+//! let answers = interact_specs_value(&specs, &ColorfulTheme::default())?;
+//! println!("{}", answers["project"]);
+//! # Ok(())
+//! # }
+//! ```
+use std::{collections::BTreeMap, io};
+
+use console::Term;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    backend::Backend,
+    error::{Error, Result},
+    theme::Theme,
+    Confirm, FuzzySelect, Input, MultiSelect, Password, Select, Sort,
+};
+
+/// Description of a prompt.
+///
+/// Every prompt has a `name`, which keys its answer in the result of
+/// [interact_specs](fn.interact_specs.html), and a `prompt` shown to the
+/// user.  Items are picked by their index, counted from `0`.
+///
+/// With the `serde` feature the kind of prompt is serialized in snake case
+/// as the `type` field, next to the other fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum PromptSpec {
+    /// A [Confirm](../struct.Confirm.html) prompt.
+    Confirm {
+        name: String,
+        prompt: String,
+        default: Option<bool>,
+    },
+    /// An [Input](../struct.Input.html) prompt for text.
+    Input {
+        name: String,
+        prompt: String,
+        default: Option<String>,
+    },
+    /// A [Password](../struct.Password.html) prompt.
+    Password { name: String, prompt: String },
+    /// A [Select](../struct.Select.html) prompt.
+    Select {
+        name: String,
+        prompt: String,
+        items: Vec<String>,
+        default: Option<usize>,
+    },
+    /// A [FuzzySelect](../struct.FuzzySelect.html) prompt.
+    FuzzySelect {
+        name: String,
+        prompt: String,
+        items: Vec<String>,
+        default: Option<usize>,
+    },
+    /// A [MultiSelect](../struct.MultiSelect.html) prompt.
+    MultiSelect {
+        name: String,
+        prompt: String,
+        items: Vec<String>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        defaults: Vec<usize>,
+    },
+    /// A [Sort](../struct.Sort.html) prompt.
+    Sort {
+        name: String,
+        prompt: String,
+        items: Vec<String>,
+    },
+}
+
+/// Answer to a prompt described by a [PromptSpec](enum.PromptSpec.html).
+///
+/// With the `serde` feature answers are serialized as the plain value, such
+/// as `true`, `"text"`, `1` or `[0, 2]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Answer {
+    /// Answer of a confirm prompt.
+    Bool(bool),
+    /// Answer of an input or password prompt.
+    Text(String),
+    /// Index of the item picked in a select or fuzzy select prompt.
+    Index(usize),
+    /// Indices of the items picked in a multi select prompt, or of all
+    /// items in the chosen order in a sort prompt.
+    Indices(Vec<usize>),
+}
+
+impl PromptSpec {
+    /// Returns the name of the prompt.
+    pub fn name(&self) -> &str {
+        match self {
+            PromptSpec::Confirm { name, .. }
+            | PromptSpec::Input { name, .. }
+            | PromptSpec::Password { name, .. }
+            | PromptSpec::Select { name, .. }
+            | PromptSpec::FuzzySelect { name, .. }
+            | PromptSpec::MultiSelect { name, .. }
+            | PromptSpec::Sort { name, .. } => name,
+        }
+    }
+
+    /// Builds the described prompt with `theme`, runs it and returns the
+    /// answer.
+    ///
+    /// The dialog is rendered on stderr.
+    pub fn interact(&self, theme: &dyn Theme) -> Result<Answer> {
+        self.interact_on(theme, &Term::stderr())
+    }
+
+    /// Like [interact](#method.interact) but allows a specific terminal to be set.
+    ///
+    /// Specs with no items, or with a default index past the last item,
+    /// fail with an error of kind `InvalidInput` before anything is shown.
+    pub fn interact_on(&self, theme: &dyn Theme, term: &dyn Backend) -> Result<Answer> {
+        self.validate()?;
+        Ok(match self {
+            PromptSpec::Confirm {
+                prompt, default, ..
+            } => {
+                let mut confirm = Confirm::with_theme(theme);
+                confirm.with_prompt(prompt.as_str());
+                if let Some(default) = *default {
+                    confirm.default(default);
+                }
+                Answer::Bool(confirm.interact_on(term)?)
+            }
+            PromptSpec::Input {
+                prompt, default, ..
+            } => {
+                let mut input = Input::<String>::with_theme(theme);
+                input.with_prompt(prompt.as_str());
+                if let Some(default) = default {
+                    input.default(default.clone());
+                }
+                Answer::Text(input.interact_text_on(term)?)
+            }
+            PromptSpec::Password { prompt, .. } => Answer::Text(
+                Password::with_theme(theme)
+                    .with_prompt(prompt.as_str())
+                    .interact_on(term)?,
+            ),
+            PromptSpec::Select {
+                prompt,
+                items,
+                default,
+                ..
+            } => {
+                let mut select = Select::with_theme(theme);
+                select.with_prompt(prompt.as_str()).items(items);
+                if let Some(default) = *default {
+                    select.default(default);
+                }
+                Answer::Index(select.interact_on(term)?)
+            }
+            PromptSpec::FuzzySelect {
+                prompt,
+                items,
+                default,
+                ..
+            } => {
+                let mut select = FuzzySelect::with_theme(theme);
+                select.with_prompt(prompt.as_str()).items(items);
+                if let Some(default) = *default {
+                    select.default(default);
+                }
+                Answer::Index(select.interact_on(term)?)
+            }
+            PromptSpec::MultiSelect {
+                prompt,
+                items,
+                defaults,
+                ..
+            } => {
+                let checked: Vec<bool> = (0..items.len()).map(|i| defaults.contains(&i)).collect();
+                Answer::Indices(
+                    MultiSelect::with_theme(theme)
+                        .with_prompt(prompt.as_str())
+                        .items(items)
+                        .defaults(&checked)
+                        .interact_on(term)?,
+                )
+            }
+            PromptSpec::Sort { prompt, items, .. } => Answer::Indices(
+                Sort::with_theme(theme)
+                    .with_prompt(prompt.as_str())
+                    .items(items)
+                    .interact_on(term)?,
+            ),
+        })
+    }
+
+    /// Checks the items and defaults, which may come from a file.
+    fn validate(&self) -> Result<()> {
+        let msg = match self {
+            PromptSpec::Select { items, .. }
+            | PromptSpec::FuzzySelect { items, .. }
+            | PromptSpec::MultiSelect { items, .. }
+            | PromptSpec::Sort { items, .. }
+                if items.is_empty() =>
+            {
+                "prompt has no items"
+            }
+            PromptSpec::Select {
+                items,
+                default: Some(default),
+                ..
+            }
+            | PromptSpec::FuzzySelect {
+                items,
+                default: Some(default),
+                ..
+            } if *default >= items.len() => "default is not an index of the items",
+            PromptSpec::MultiSelect {
+                items, defaults, ..
+            } if defaults.iter().any(|&idx| idx >= items.len()) => {
+                "defaults are not indices of the items"
+            }
+            _ => return Ok(()),
+        };
+        Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, msg)))
+    }
+}
+
+/// Runs the described prompts one after the other and returns their answers
+/// by name.
+///
+/// The dialogs are rendered on stderr.  The first failing prompt stops the
+/// run.
+pub fn interact_specs(specs: &[PromptSpec], theme: &dyn Theme) -> Result<BTreeMap<String, Answer>> {
+    interact_specs_on(specs, theme, &Term::stderr())
+}
+
+/// Like [interact_specs](fn.interact_specs.html) but allows a specific
+/// terminal to be set.
+pub fn interact_specs_on(
+    specs: &[PromptSpec],
+    theme: &dyn Theme,
+    term: &dyn Backend,
+) -> Result<BTreeMap<String, Answer>> {
+    let mut answers = BTreeMap::new();
+    for spec in specs {
+        answers.insert(spec.name().to_string(), spec.interact_on(theme, term)?);
+    }
+    Ok(answers)
+}
+
+/// Runs the described prompts like [interact_specs](fn.interact_specs.html)
+/// and returns their answers as a JSON object keyed by name.
+///
+/// This requires the `serde` feature.
+#[cfg(feature = "serde")]
+pub fn interact_specs_value(specs: &[PromptSpec], theme: &dyn Theme) -> Result<serde_json::Value> {
+    interact_specs_value_on(specs, theme, &Term::stderr())
+}
+
+/// Like [interact_specs_value](fn.interact_specs_value.html) but allows a
+/// specific terminal to be set.
+#[cfg(feature = "serde")]
+pub fn interact_specs_value_on(
+    specs: &[PromptSpec],
+    theme: &dyn Theme,
+    term: &dyn Backend,
+) -> Result<serde_json::Value> {
+    let answers = interact_specs_on(specs, theme, term)?
+        .into_iter()
+        .map(|(name, answer)| (name, answer.into()))
+        .collect();
+    Ok(serde_json::Value::Object(answers))
+}
+
+#[cfg(feature = "serde")]
+impl From<Answer> for serde_json::Value {
+    fn from(answer: Answer) -> serde_json::Value {
+        match answer {
+            Answer::Bool(val) => val.into(),
+            Answer::Text(val) => val.into(),
+            Answer::Index(val) => val.into(),
+            Answer::Indices(val) => val.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::TestTerm, theme::SimpleTheme};
+    use console::Key;
+
+    #[test]
+    fn test_interact_specs() {
+        let specs = vec![
+            PromptSpec::Confirm {
+                name: "publish".into(),
+                prompt: "Publish?".into(),
+                default: Some(false),
+            },
+            PromptSpec::Input {
+                name: "project".into(),
+                prompt: "Project".into(),
+                default: Some("demo".into()),
+            },
+            PromptSpec::MultiSelect {
+                name: "targets".into(),
+                prompt: "Targets".into(),
+                items: vec!["linux".into(), "macos".into()],
+                defaults: vec![1],
+            },
+        ];
+
+        let term = TestTerm::new();
+        term.push_key(Key::Enter)
+            .push_key(Key::Enter)
+            .push_key(Key::Char(' '))
+            .push_key(Key::Enter);
+
+        let answers = interact_specs_on(&specs, &SimpleTheme, &term).unwrap();
+        assert_eq!(answers["publish"], Answer::Bool(false));
+        assert_eq!(answers["project"], Answer::Text("demo".into()));
+        assert_eq!(answers["targets"], Answer::Indices(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        let specs = vec![
+            PromptSpec::Select {
+                name: "empty".into(),
+                prompt: "Empty".into(),
+                items: vec![],
+                default: None,
+            },
+            PromptSpec::FuzzySelect {
+                name: "default".into(),
+                prompt: "Default".into(),
+                items: vec!["a".into(), "b".into()],
+                default: Some(2),
+            },
+            PromptSpec::MultiSelect {
+                name: "defaults".into(),
+                prompt: "Defaults".into(),
+                items: vec!["a".into(), "b".into()],
+                defaults: vec![0, 5],
+            },
+        ];
+
+        for spec in &specs {
+            let term = TestTerm::new();
+            term.push_key(Key::Enter);
+            match spec.interact_on(&SimpleTheme, &term) {
+                Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::InvalidInput),
+                res => panic!("{} was accepted: {:?}", spec.name(), res),
+            }
+            assert_eq!(term.transcript(), "");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_specs() {
+        let specs: Vec<PromptSpec> = serde_json::from_str(
+            r#"[
+                {"type": "confirm", "name": "publish", "prompt": "Publish?"},
+                {"type": "multi_select", "name": "targets", "prompt": "Targets",
+                 "items": ["linux", "macos"]}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            specs,
+            vec![
+                PromptSpec::Confirm {
+                    name: "publish".into(),
+                    prompt: "Publish?".into(),
+                    default: None,
+                },
+                PromptSpec::MultiSelect {
+                    name: "targets".into(),
+                    prompt: "Targets".into(),
+                    items: vec!["linux".into(), "macos".into()],
+                    defaults: vec![],
+                },
+            ]
+        );
+
+        let term = TestTerm::new();
+        term.push_str("y")
+            .push_key(Key::Char(' '))
+            .push_key(Key::Enter);
+
+        let answers = interact_specs_value_on(&specs, &SimpleTheme, &term).unwrap();
+        assert_eq!(
+            answers,
+            serde_json::json!({"publish": true, "targets": [0]})
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Provides an in-memory terminal to test code which uses prompts
+//!
+//! A [TestTerm](struct.TestTerm.html) plays back key presses queued up
+//! front and keeps track of what the prompts show, so prompts can run in
+//! tests without a terminal:
+//!
+//! ```rust
+//! use dialoguer::{test::TestTerm, Confirm};
+//!
+//! let term = TestTerm::new();
+//! term.push_str("y");
+//!
+//! let answer = Confirm::new()
+//!     .with_prompt("Continue?")
+//!     .interact_on(&term)
+//!     .unwrap();
+//!
+//! assert!(answer);
+//! assert_eq!(term.contents(), "Continue? yes");
+//! ```
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+use console::{measure_text_width, strip_ansi_codes, Key};
+
+use crate::backend::Backend;
+
+/// A queued up input of a [TestTerm](struct.TestTerm.html).
+enum Event {
+    Key(Key),
+    Interrupt,
+    Resize(u16, u16),
+}
+
+/// What a [TestTerm](struct.TestTerm.html) shows.
+struct Screen {
+    lines: Vec<Vec<char>>,
+    row: usize,
+    column: usize,
+    width: usize,
+    cursor_hidden: bool,
+    transcript: String,
+}
+
+impl Screen {
+    fn line(&mut self) -> &mut Vec<char> {
+        if self.lines.len() <= self.row {
+            self.lines.resize(self.row + 1, vec![]);
+        }
+        &mut self.lines[self.row]
+    }
+
+    fn write(&mut self, s: &str) {
+        let s = strip_ansi_codes(s);
+        self.transcript.push_str(&s);
+
+        for chr in s.chars() {
+            match chr {
+                '\n' => {
+                    self.row += 1;
+                    self.column = 0;
+                    self.line();
+                }
+                '\r' => self.column = 0,
+                _ => {
+                    if self.column >= self.width {
+                        self.row += 1;
+                        self.column = 0;
+                    }
+
+                    let column = self.column;
+                    let line = self.line();
+                    if line.len() <= column {
+                        line.resize(column + 1, ' ');
+                    }
+                    line[column] = chr;
+                    self.column += measure_text_width(&chr.to_string()).max(1);
+                }
+            }
+        }
+    }
+
+    fn truncate_line(&mut self) {
+        let column = self.column;
+        self.line().truncate(column);
+    }
+
+    fn contents(&self) -> String {
+        let mut lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| line.iter().collect::<String>().trim_end().to_string())
+            .collect();
+
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// An in-memory terminal for tests.
+///
+/// Prompts read the queued key presses in order and fail with an error of
+/// kind `UnexpectedEof` once there are none left, so a test never waits
+/// for input.  Pass the terminal to the `interact_on` methods of the
+/// prompts.
+pub struct TestTerm {
+    events: Mutex<VecDeque<Event>>,
+    screen: Mutex<Screen>,
+    size: Mutex<(u16, u16)>,
+    is_term: AtomicBool,
+}
+
+impl Default for TestTerm {
+    fn default() -> TestTerm {
+        TestTerm::new()
+    }
+}
+
+impl TestTerm {
+    /// Creates a terminal with 24 rows and 80 columns.
+    pub fn new() -> TestTerm {
+        TestTerm::with_size(24, 80)
+    }
+
+    /// Creates a terminal with a specific size.
+    pub fn with_size(rows: u16, columns: u16) -> TestTerm {
+        TestTerm {
+            events: Mutex::new(VecDeque::new()),
+            screen: Mutex::new(Screen {
+                lines: vec![],
+                row: 0,
+                column: 0,
+                width: columns.max(1) as usize,
+                cursor_hidden: false,
+                transcript: String::new(),
+            }),
+            size: Mutex::new((rows, columns)),
+            is_term: AtomicBool::new(true),
+        }
+    }
+
+    /// Queues up a key press.
+    pub fn push_key(&self, key: Key) -> &TestTerm {
+        self.events().push_back(Event::Key(key));
+        self
+    }
+
+    /// Queues up several key presses.
+    pub fn push_keys<I: IntoIterator<Item = Key>>(&self, keys: I) -> &TestTerm {
+        self.events().extend(keys.into_iter().map(Event::Key));
+        self
+    }
+
+    /// Queues up typing `s`.
+    pub fn push_str(&self, s: &str) -> &TestTerm {
+        self.push_keys(s.chars().map(Key::Char))
+    }
+
+    /// Queues up typing `line` followed by enter.
+    pub fn push_line(&self, line: &str) -> &TestTerm {
+        self.push_str(line).push_key(Key::Enter)
+    }
+
+    /// Queues up pressing Ctrl+C.
+    pub fn push_interrupt(&self) -> &TestTerm {
+        self.events().push_back(Event::Interrupt);
+        self
+    }
+
+    /// Queues up resizing the terminal before the next key press is read.
+    ///
+    /// Lines already shown are not rewrapped.
+    pub fn push_resize(&self, rows: u16, columns: u16) -> &TestTerm {
+        self.events().push_back(Event::Resize(rows, columns));
+        self
+    }
+
+    /// Sets whether the terminal claims to be attended by a user.
+    ///
+    /// Prompts behave like on a terminal whose output is redirected when
+    /// this is `false`.  The default is `true`.
+    pub fn set_is_term(&self, val: bool) -> &TestTerm {
+        self.is_term.store(val, Ordering::Relaxed);
+        self
+    }
+
+    /// Returns the number of queued up inputs which were not read yet.
+    pub fn pending(&self) -> usize {
+        self.events().len()
+    }
+
+    /// Returns the text the terminal shows, without styles.
+    ///
+    /// Trailing whitespace and empty lines at the end are left out.
+    pub fn contents(&self) -> String {
+        self.screen().contents()
+    }
+
+    /// Returns all text written to the terminal so far, without styles.
+    ///
+    /// Unlike [contents](#method.contents) this keeps what the prompts
+    /// cleared again, such as earlier renderings of a list or validation
+    /// errors.
+    pub fn transcript(&self) -> String {
+        self.screen().transcript.clone()
+    }
+
+    /// Checks whether the cursor is hidden.
+    pub fn is_cursor_hidden(&self) -> bool {
+        self.screen().cursor_hidden
+    }
+
+    fn events(&self) -> MutexGuard<'_, VecDeque<Event>> {
+        self.events.lock().unwrap()
+    }
+
+    fn screen(&self) -> MutexGuard<'_, Screen> {
+        self.screen.lock().unwrap()
+    }
+
+    /// Reads keys up to enter, editing the line like a terminal does.
+    fn read_line_with(&self, initial: &str, echo: bool) -> io::Result<String> {
+        let mut line: String = initial.to_string();
+        if echo {
+            self.write_str(initial)?;
+        }
+
+        loop {
+            match self.read_key()? {
+                Key::Enter => {
+                    self.write_str("\n")?;
+                    return Ok(line);
+                }
+                Key::Backspace => {
+                    let erased = line.pop().is_some();
+                    if erased && echo {
+                        self.clear_chars(1)?;
+                    }
+                }
+                Key::Char(chr) if !chr.is_control() => {
+                    line.push(chr);
+                    if echo {
+                        self.write_str(&chr.to_string())?;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Backend for TestTerm {
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.screen().write(s);
+        Ok(())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn read_key(&self) -> io::Result<Key> {
+        loop {
+            match self.events().pop_front() {
+                Some(Event::Key(key)) => return Ok(key),
+                Some(Event::Interrupt) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Interrupted,
+                        "read interrupted",
+                    ))
+                }
+                Some(Event::Resize(rows, columns)) => {
+                    *self.size.lock().unwrap() = (rows, columns);
+                    self.screen().width = columns.max(1) as usize;
+                }
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "no key presses left",
+                    ))
+                }
+            }
+        }
+    }
+
+    fn read_line(&self) -> io::Result<String> {
+        self.read_line_with("", true)
+    }
+
+    fn read_line_initial_text(&self, initial: &str) -> io::Result<String> {
+        self.read_line_with(initial, true)
+    }
+
+    fn read_secure_line(&self) -> io::Result<String> {
+        self.read_line_with("", false)
+    }
+
+    fn is_term(&self) -> bool {
+        self.is_term.load(Ordering::Relaxed)
+    }
+
+    fn size(&self) -> (u16, u16) {
+        *self.size.lock().unwrap()
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        let mut screen = self.screen();
+        screen.row = screen.row.saturating_sub(n);
+        Ok(())
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        let mut screen = self.screen();
+        screen.row += n;
+        screen.line();
+        Ok(())
+    }
+
+    fn move_cursor_left(&self, n: usize) -> io::Result<()> {
+        let mut screen = self.screen();
+        screen.column = screen.column.saturating_sub(n);
+        Ok(())
+    }
+
+    fn move_cursor_right(&self, n: usize) -> io::Result<()> {
+        let mut screen = self.screen();
+        screen.column = (screen.column + n).min(screen.width - 1);
+        Ok(())
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        let mut screen = self.screen();
+        screen.line().clear();
+        screen.column = 0;
+        Ok(())
+    }
+
+    fn clear_chars(&self, n: usize) -> io::Result<()> {
+        let mut screen = self.screen();
+        screen.column = screen.column.saturating_sub(n);
+        screen.truncate_line();
+        Ok(())
+    }
+
+    fn clear_to_end_of_screen(&self) -> io::Result<()> {
+        // console returns to the start of the line before clearing
+        let mut screen = self.screen();
+        screen.column = 0;
+        screen.truncate_line();
+        let rows = screen.row + 1;
+        screen.lines.truncate(rows);
+        Ok(())
+    }
+
+    fn show_cursor(&self) -> io::Result<()> {
+        self.screen().cursor_hidden = false;
+        Ok(())
+    }
+
+    fn hide_cursor(&self) -> io::Result<()> {
+        self.screen().cursor_hidden = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Confirm, Error, Input, MultiSelect, Select};
+
+    #[test]
+    fn test_screen() {
+        let term = TestTerm::with_size(24, 10);
+
+        term.write_line("hello").unwrap();
+        term.write_line("0123456789ab").unwrap();
+        assert_eq!(term.contents(), "hello\n0123456789\nab");
+
+        term.clear_last_lines(2).unwrap();
+        term.write_str("world").unwrap();
+        assert_eq!(term.contents(), "hello\nworld");
+
+        term.write_str("\nab cd\nef").unwrap();
+        term.move_cursor_up(1).unwrap();
+        term.clear_to_end_of_screen().unwrap();
+        assert_eq!(term.contents(), "hello\nworld");
+    }
+
+    #[test]
+    fn test_confirm() {
+        let term = TestTerm::new();
+        term.push_str("xn");
+
+        let answer = Confirm::new().with_prompt("Continue?").interact_on(&term);
+        assert!(!answer.unwrap());
+        assert_eq!(term.contents(), "Continue? no");
+        assert!(!term.is_cursor_hidden());
+    }
+
+    #[test]
+    fn test_input() {
+        let term = TestTerm::new();
+        term.push_line("abc").push_line("42");
+
+        let answer = Input::<u32>::new()
+            .with_prompt("Number")
+            .interact_text_on(&term);
+        assert_eq!(answer.unwrap(), 42);
+        assert_eq!(term.contents(), "Number: 42");
+        assert_eq!(term.pending(), 0);
+    }
+
+    #[test]
+    fn test_transcript() {
+        let term = TestTerm::new();
+        term.push_line("abc").push_line("42");
+
+        let answer = Input::<u32>::new()
+            .with_prompt("Number")
+            .interact_text_on(&term);
+        assert_eq!(answer.unwrap(), 42);
+        assert!(term.transcript().contains("invalid digit"));
+        assert!(!term.contents().contains("invalid digit"));
+    }
+
+    #[test]
+    fn test_input_not_a_term() {
+        let term = TestTerm::new();
+        term.set_is_term(false).push_line("42");
+
+        let err = Input::<u32>::new().interact_text_on(&term).unwrap_err();
+        assert!(matches!(err, Error::NotATerminal));
+    }
+
+    #[test]
+    fn test_select() {
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::ArrowDown, Key::ArrowDown, Key::Enter]);
+
+        let answer = Select::new()
+            .with_prompt("Color")
+            .items(&["red", "green", "blue"])
+            .default(0)
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), 2);
+        assert_eq!(term.contents(), "Color: blue");
+    }
+
+    #[test]
+    fn test_multi_select() {
+        let term = TestTerm::new();
+        term.push_keys(vec![Key::Char(' '), Key::ArrowDown, Key::Enter]);
+
+        let answer = MultiSelect::new()
+            .items(&["red", "green"])
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn test_resize() {
+        let term = TestTerm::new();
+        term.push_key(Key::ArrowDown)
+            .push_resize(10, 40)
+            .push_key(Key::Enter);
+
+        let mut sizes = vec![];
+        let answer = Select::new()
+            .with_prompt("Color")
+            .items(&["red", "green"])
+            .default(0)
+            .on_resize(|rows, columns| sizes.push((rows, columns)))
+            .interact_on(&term);
+        assert_eq!(answer.unwrap(), 1);
+        assert_eq!(term.contents(), "Color: green");
+        assert_eq!(sizes, vec![(10, 40)]);
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let term = TestTerm::new();
+        term.push_interrupt();
+
+        let err = Select::new()
+            .items(&["red"])
+            .interact_on(&term)
+            .unwrap_err();
+        assert!(matches!(err, Error::Interrupted));
+        assert!(!term.is_cursor_hidden());
+    }
+
+    #[test]
+    fn test_panic_shows_cursor() {
+        let term = TestTerm::new();
+        term.push_key(Key::Char(' '));
+
+        let result = std::panic::catch_unwind(|| {
+            MultiSelect::new()
+                .items(&["red"])
+                .on_toggle(|_, _| panic!("toggled"))
+                .interact_on(&term)
+        });
+        assert!(result.is_err());
+        assert!(!term.is_cursor_hidden());
+    }
+
+    #[test]
+    fn test_no_keys_left() {
+        let term = TestTerm::new();
+
+        let err = Confirm::new().interact_on(&term).unwrap_err();
+        assert!(matches!(err, Error::Io(ref err) if err.kind() == io::ErrorKind::UnexpectedEof));
+    }
+}
@@ -1,7 +1,44 @@
 //! Customizes the rendering of the elements.
-use std::{fmt, io};
-
-use console::{style, Style, StyledObject, Term};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::HashMap,
+    env, fmt, io, mem,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "async")]
+use std::{future::Future, sync::Arc};
+
+use console::{
+    measure_text_width, strip_ansi_codes, style, truncate_str, Color, Style, StyledObject, Term,
+};
+
+use crate::{
+    backend::{term_size, Backend},
+    events::{self, PromptEvent},
+};
+
+/// The kind of a prompt, for parts of a theme which differ between prompts.
+///
+/// Fuzzy select prompts count as select and multi select prompts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PromptKind {
+    /// A [Confirm](../struct.Confirm.html) prompt.
+    Confirm,
+    /// An [Input](../struct.Input.html) prompt.
+    Input,
+    /// A [Password](../struct.Password.html) prompt.
+    Password,
+    /// A [Select](../struct.Select.html) prompt.
+    Select,
+    /// A [MultiSelect](../struct.MultiSelect.html) prompt.
+    MultiSelect,
+    /// A [Sort](../struct.Sort.html) prompt.
+    Sort,
+}
 
 /// Implements a theme for dialoguer.
 pub trait Theme {
@@ -17,6 +54,18 @@ pub trait Theme {
         write!(f, "error: {}", err)
     }
 
+    /// Formats out an informational message.
+    #[inline]
+    fn format_info(&self, f: &mut dyn fmt::Write, msg: &str) -> fmt::Result {
+        write!(f, "info: {}", msg)
+    }
+
+    /// Formats out a warning.
+    #[inline]
+    fn format_warning(&self, f: &mut dyn fmt::Write, msg: &str) -> fmt::Result {
+        write!(f, "warning: {}", msg)
+    }
+
     /// Formats a confirm prompt.
     fn format_confirm_prompt(
         &self,
@@ -27,14 +76,66 @@ pub trait Theme {
         if !prompt.is_empty() {
             write!(f, "{} ", &prompt)?;
         }
+        if let Some(default) = default {
+            self.format_confirm_options(f, default)?;
+            write!(f, " ")?;
+        }
+        Ok(())
+    }
+
+    /// Formats the options hint of a confirm prompt, such as `[Y/n]`.
+    ///
+    /// `default` is the answer picked on enter.  Override this to
+    /// translate the hint.
+    fn format_confirm_options(&self, f: &mut dyn fmt::Write, default: bool) -> fmt::Result {
+        write!(f, "[")?;
         match default {
-            None => {}
-            Some(true) => write!(f, "[Y/n] ")?,
-            Some(false) => write!(f, "[y/N] ")?,
+            true => self.format_confirm_default_option(f, "y")?,
+            false => self.format_confirm_non_default_option(f, "y")?,
         }
+        write!(f, "/")?;
+        match default {
+            true => self.format_confirm_non_default_option(f, "n")?,
+            false => self.format_confirm_default_option(f, "n")?,
+        }
+        write!(f, "]")
+    }
+
+    /// Formats the option of a confirm prompt hint which is picked on enter.
+    #[inline]
+    fn format_confirm_default_option(&self, f: &mut dyn fmt::Write, option: &str) -> fmt::Result {
+        write!(f, "{}", option.to_uppercase())
+    }
+
+    /// Formats an option of a confirm prompt hint which is not the default.
+    #[inline]
+    fn format_confirm_non_default_option(
+        &self,
+        f: &mut dyn fmt::Write,
+        option: &str,
+    ) -> fmt::Result {
+        write!(f, "{}", option)
+    }
+
+    /// Formats the prefix of a prompt after selection.
+    ///
+    /// This allows a different symbol for every kind of prompt.  The simple
+    /// theme has no such prefix.
+    #[inline]
+    fn format_success_prefix(&self, _f: &mut dyn fmt::Write, _kind: PromptKind) -> fmt::Result {
         Ok(())
     }
 
+    /// Formats the separator between a prompt and its answer after
+    /// selection.
+    #[inline]
+    fn format_prompt_separator(&self, f: &mut dyn fmt::Write, kind: PromptKind) -> fmt::Result {
+        match kind {
+            PromptKind::Confirm => write!(f, " "),
+            _ => write!(f, ": "),
+        }
+    }
+
     /// Formats a confirm prompt after selection.
     fn format_confirm_prompt_selection(
         &self,
@@ -42,11 +143,12 @@ pub trait Theme {
         prompt: &str,
         selection: bool,
     ) -> fmt::Result {
-        if prompt.is_empty() {
-            write!(f, "{}", if selection { "yes" } else { "no" })
-        } else {
-            write!(f, "{} {}", &prompt, if selection { "yes" } else { "no" })
+        if !prompt.is_empty() {
+            write!(f, "{}", prompt)?;
+            self.format_prompt_separator(f, PromptKind::Confirm)?;
         }
+
+        write!(f, "{}", if selection { "yes" } else { "no" })
     }
 
     /// Formats an input prompt.
@@ -57,12 +159,33 @@ pub trait Theme {
         default: Option<&str>,
     ) -> fmt::Result {
         match default {
-            Some(default) if prompt.is_empty() => write!(f, "[{}]: ", default),
-            Some(default) => write!(f, "{} [{}]: ", prompt, default),
+            Some(default) => {
+                if !prompt.is_empty() {
+                    write!(f, "{} ", prompt)?;
+                }
+                self.format_default_hint(f, default)?;
+                write!(f, ": ")
+            }
             None => write!(f, "{}: ", prompt),
         }
     }
 
+    /// Formats the hint showing the default value of an input prompt, such
+    /// as `[default]`.
+    ///
+    /// Override this to translate the hint.
+    #[inline]
+    fn format_default_hint(&self, f: &mut dyn fmt::Write, default: &str) -> fmt::Result {
+        write!(f, "[{}]", default)
+    }
+
+    /// Formats the ghost text after the cursor of an input prompt, the
+    /// rest of the only completion of the input.
+    #[inline]
+    fn format_ghost_suggestion(&self, f: &mut dyn fmt::Write, suggestion: &str) -> fmt::Result {
+        write!(f, "{}", suggestion)
+    }
+
     /// Formats an input prompt after selection.
     #[inline]
     fn format_input_prompt_selection(
@@ -71,7 +194,9 @@ pub trait Theme {
         prompt: &str,
         sel: &str,
     ) -> fmt::Result {
-        write!(f, "{}: {}", prompt, sel)
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f, PromptKind::Input)?;
+        write!(f, "{}", sel)
     }
 
     /// Formats a password prompt.
@@ -87,7 +212,9 @@ pub trait Theme {
         f: &mut dyn fmt::Write,
         prompt: &str,
     ) -> fmt::Result {
-        self.format_input_prompt_selection(f, prompt, "[hidden]")
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f, PromptKind::Password)?;
+        write!(f, "[hidden]")
     }
 
     /// Formats a select prompt.
@@ -104,7 +231,9 @@ pub trait Theme {
         prompt: &str,
         sel: &str,
     ) -> fmt::Result {
-        self.format_input_prompt_selection(f, prompt, sel)
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f, PromptKind::Select)?;
+        write!(f, "{}", sel)
     }
 
     /// Formats a multi select prompt.
@@ -126,7 +255,8 @@ pub trait Theme {
         prompt: &str,
         selections: &[&str],
     ) -> fmt::Result {
-        write!(f, "{}: ", prompt)?;
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f, PromptKind::MultiSelect)?;
         for (idx, sel) in selections.iter().enumerate() {
             write!(f, "{}{}", if idx == 0 { "" } else { ", " }, sel)?;
         }
@@ -134,14 +264,18 @@ pub trait Theme {
     }
 
     /// Formats a sort prompt after selection.
-    #[inline]
     fn format_sort_prompt_selection(
         &self,
         f: &mut dyn fmt::Write,
         prompt: &str,
         selections: &[&str],
     ) -> fmt::Result {
-        self.format_multi_select_prompt_selection(f, prompt, selections)
+        write!(f, "{}", prompt)?;
+        self.format_prompt_separator(f, PromptKind::Sort)?;
+        for (idx, sel) in selections.iter().enumerate() {
+            write!(f, "{}{}", if idx == 0 { "" } else { ", " }, sel)?;
+        }
+        Ok(())
     }
 
     /// Formats a select prompt item.
@@ -154,6 +288,56 @@ pub trait Theme {
         write!(f, "{} {}", if active { ">" } else { " " }, text)
     }
 
+    /// Formats the indicator below the items of a paged list.
+    ///
+    /// `page` is counted from `1`.
+    fn format_page_indicator(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+    ) -> fmt::Result {
+        write!(f, "  Page {}/{}", page, pages)
+    }
+
+    /// Returns the style of a background band which spans the active item
+    /// of select, multi select and sort prompts across the terminal.
+    ///
+    /// The simple theme has no band.
+    #[inline]
+    fn active_item_band_style(&self) -> Option<&Style> {
+        None
+    }
+
+    /// Returns whether select prompts lay their items out on a single line.
+    #[inline]
+    fn horizontal_items(&self) -> bool {
+        false
+    }
+
+    /// Formats a select prompt with its items laid out on a single line.
+    fn format_inline_select_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        items: &[&str],
+        active: usize,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            self.format_select_prompt(f, prompt)?;
+            write!(f, " ")?;
+        }
+
+        for (idx, item) in items.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " | ")?;
+            }
+            self.format_select_prompt_item(f, item, idx == active)?;
+        }
+
+        Ok(())
+    }
+
     /// Formats a multi select prompt item.
     fn format_multi_select_prompt_item(
         &self,
@@ -175,6 +359,75 @@ pub trait Theme {
         )
     }
 
+    /// Formats a required multi select prompt item.
+    ///
+    /// Required items are always checked and cannot be toggled.
+    fn format_multi_select_prompt_required_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        write!(f, "{} {}", if active { "> [*]" } else { "  [*]" }, text)
+    }
+
+    /// Formats a multi select prompt item that is part of an exclusive group.
+    fn format_multi_select_prompt_radio_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        checked: bool,
+        active: bool,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            match (checked, active) {
+                (true, true) => "> (*)",
+                (true, false) => "  (*)",
+                (false, true) => "> ( )",
+                (false, false) => "  ( )",
+            },
+            text
+        )
+    }
+
+    /// Formats a pair of items the user has to rank in a sort prompt.
+    fn format_sort_prompt_pair(
+        &self,
+        f: &mut dyn fmt::Write,
+        left: &str,
+        right: &str,
+    ) -> fmt::Result {
+        write!(f, "< {} | {} >", left, right)
+    }
+
+    /// Formats the picked up sort prompt item with a custom marker.
+    fn format_sort_prompt_grabbed_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        symbol: &str,
+    ) -> fmt::Result {
+        write!(f, "{} {}", symbol, text)
+    }
+
+    /// Formats the position of a sort prompt item.
+    ///
+    /// `position` starts at 1 and `total` is the number of items which can
+    /// be used to align the positions.
+    fn format_sort_prompt_item_position(
+        &self,
+        f: &mut dyn fmt::Write,
+        position: usize,
+        total: usize,
+        _picked: bool,
+        _active: bool,
+    ) -> fmt::Result {
+        let width = total.to_string().len();
+        write!(f, "{:>width$}.", position, width = width)
+    }
+
     /// Formats a sort prompt item.
     fn format_sort_prompt_item(
         &self,
@@ -194,14 +447,206 @@ pub trait Theme {
             text
         )
     }
+
+    /// Formats a fuzzy select prompt.
+    ///
+    /// `cursor` is the position of the cursor in `query`, counted in
+    /// characters.
+    fn format_fuzzy_select_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        query: &str,
+        cursor: usize,
+    ) -> fmt::Result {
+        let (head, tail) = split_at_char(query, cursor);
+
+        if !prompt.is_empty() {
+            write!(f, "{}: ", prompt)?;
+        }
+
+        write!(f, "{}|{}", head, tail)
+    }
+
+    /// Formats a fuzzy select prompt item.
+    ///
+    /// `matches` are the positions of the characters of `text` which
+    /// matched the query.
+    fn format_fuzzy_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+        _matches: &[usize],
+    ) -> fmt::Result {
+        self.format_select_prompt_item(f, text, active)
+    }
+
+    /// Formats a fuzzy select prompt item which can be checked.
+    fn format_fuzzy_multi_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        checked: bool,
+        active: bool,
+        _matches: &[usize],
+    ) -> fmt::Result {
+        self.format_multi_select_prompt_item(f, text, checked, active)
+    }
+
+    /// Formats the entry of a fuzzy select prompt which creates a new item.
+    fn format_fuzzy_select_prompt_create_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        query: &str,
+        active: bool,
+    ) -> fmt::Result {
+        self.format_select_prompt_item(f, &format!("Create '{}'", query), active)
+    }
+
+    /// Formats the text of a prompt with a badge for the step of a multi
+    /// step flow.
+    ///
+    /// `current` starts at 1.  The result replaces the prompt text in the
+    /// other format methods, so the badge can go on either side of it.
+    fn format_step_badge(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        current: usize,
+        total: usize,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(f, "{} ", prompt)?;
+        }
+
+        write!(f, "(step {}/{})", current, total)
+    }
+
+    /// Formats a prompt which waits for a slow operation, such as a
+    /// validator, with a spinner.
+    ///
+    /// `frame` counts up to [wait_spinner_frames](#method.wait_spinner_frames)
+    /// and starts over.
+    fn format_wait_spinner(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        frame: usize,
+    ) -> fmt::Result {
+        let frame = ["|", "/", "-", "\\"][frame % 4];
+
+        if prompt.is_empty() {
+            write!(f, "{}", frame)
+        } else {
+            write!(f, "{} {}", frame, prompt)
+        }
+    }
+
+    /// Returns the number of frames of the spinner of
+    /// [format_wait_spinner](#method.format_wait_spinner).
+    #[inline]
+    fn wait_spinner_frames(&self) -> usize {
+        4
+    }
+
+    /// Formats the ellipsis which ends items truncated to the terminal width.
+    #[inline]
+    fn format_item_ellipsis(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "...")
+    }
+
+    /// Returns a copy of the theme with a different prompt prefix or suffix.
+    ///
+    /// This backs the per prompt overrides such as
+    /// [Select::with_prompt_prefix](../struct.Select.html#method.with_prompt_prefix).
+    /// Themes without a prompt prefix and suffix return `None`, which keeps
+    /// the theme unchanged.
+    fn with_prompt_affixes(
+        &self,
+        _prefix: Option<&StyledObject<String>>,
+        _suffix: Option<&StyledObject<String>>,
+    ) -> Option<Box<dyn Theme>> {
+        None
+    }
+
+    /// Returns a copy of the theme with the separator between prompts and
+    /// their answers replaced by `separator`.
+    ///
+    /// Themes which cannot be copied return `None` and keep their own
+    /// separator.
+    #[inline]
+    fn with_prompt_separator(&self, _separator: &StyledObject<String>) -> Option<Box<dyn Theme>> {
+        None
+    }
 }
 
+/// The frames of the spinner of [ColorfulTheme](struct.ColorfulTheme.html).
+const BRAILLE_SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 /// The default theme.
 pub struct SimpleTheme;
 
-impl Theme for SimpleTheme {}
+impl Theme for SimpleTheme {
+    fn with_prompt_separator(&self, separator: &StyledObject<String>) -> Option<Box<dyn Theme>> {
+        Some(Box::new(SeparatedSimpleTheme(separator.clone())))
+    }
+}
+
+/// The simple theme with a custom separator between prompts and answers.
+struct SeparatedSimpleTheme(StyledObject<String>);
+
+impl Theme for SeparatedSimpleTheme {
+    fn format_prompt_separator(&self, f: &mut dyn fmt::Write, _kind: PromptKind) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A compact theme for dense scripts.
+///
+/// Select prompts are laid out on a single line, with the active item in
+/// brackets: `Pick: one | [two] | three`.
+pub struct InlineTheme;
+
+impl Theme for InlineTheme {
+    fn horizontal_items(&self) -> bool {
+        true
+    }
+
+    fn format_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        if active {
+            write!(f, "[{}]", text)
+        } else {
+            write!(f, "{}", text)
+        }
+    }
+}
 
 /// A colorful theme
+///
+/// All styles are public fields, so single ones can be tweaked on top of a
+/// preset:
+///
+/// ```rust,no_run
+/// # fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// use console::style;
+/// use dialoguer::{theme::ColorfulTheme, Confirm};
+///
+/// let mut theme = ColorfulTheme::default();
+/// theme.prompt_prefix = style("»".to_string()).for_stderr().cyan();
+///
+/// if Confirm::with_theme(&theme).with_prompt("Continue?").interact()? {
+///     println!("Looks like you want to continue");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
 pub struct ColorfulTheme {
     /// The style for default values
     pub defaults_style: Style,
@@ -213,14 +658,29 @@ pub struct ColorfulTheme {
     pub prompt_suffix: StyledObject<String>,
     /// Prompt on success prefix value and style
     pub success_prefix: StyledObject<String>,
+    /// Prompt on success prefix values and styles for single kinds of prompts,
+    /// replacing `success_prefix`
+    pub success_prefixes: HashMap<PromptKind, StyledObject<String>>,
     /// Prompt on success suffix value and style
     pub success_suffix: StyledObject<String>,
     /// Error prefix value and style
     pub error_prefix: StyledObject<String>,
     /// The style for error message
     pub error_style: Style,
+    /// Info prefix value and style
+    pub info_prefix: StyledObject<String>,
+    /// The style for info messages
+    pub info_style: Style,
+    /// Warning prefix value and style
+    pub warning_prefix: StyledObject<String>,
+    /// The style for warning messages
+    pub warning_style: Style,
     /// The style for hints
     pub hint_style: Style,
+    /// The style for the ghost text of completions in input prompts
+    pub ghost_suggestion_style: Style,
+    /// The style for the default option in the hint of confirm prompts
+    pub confirm_default_option_style: Style,
     /// The style for values on prompt success
     pub values_style: Style,
     /// The style for active items
@@ -235,26 +695,63 @@ pub struct ColorfulTheme {
     pub checked_item_prefix: StyledObject<String>,
     /// Unchecked item in multi select prefix value and style
     pub unchecked_item_prefix: StyledObject<String>,
+    /// Required item in multi select prefix value and style
+    pub required_item_prefix: StyledObject<String>,
+    /// Checked item of an exclusive group in multi select prefix value and style
+    pub checked_radio_item_prefix: StyledObject<String>,
+    /// Unchecked item of an exclusive group in multi select prefix value and style
+    pub unchecked_radio_item_prefix: StyledObject<String>,
     /// Picked item in sort prefix value and style
     pub picked_item_prefix: StyledObject<String>,
     /// Unpicked item in sort prefix value and style
     pub unpicked_item_prefix: StyledObject<String>,
+    /// The style for the cursor in the query of a fuzzy select
+    pub fuzzy_cursor_style: Style,
+    /// The style for matched characters in fuzzy select items
+    pub fuzzy_match_highlight_style: Style,
+    /// The style for the highlighted row in fuzzy select prompts, applied
+    /// on top of the item and match styles
+    pub fuzzy_active_row_style: Style,
+    /// The style for the background band across the active item of select,
+    /// multi select and sort prompts, if any
+    pub active_item_band_style: Option<Style>,
+    /// The style for the step badge of prompts in multi step flows
+    pub step_badge_style: Style,
+    /// Ellipsis value and style for items truncated to the terminal width
+    pub item_ellipsis: StyledObject<String>,
+    /// Separator value and style between an answered prompt and its answer,
+    /// replacing the success suffix and the spaces around it
+    pub prompt_separator: Option<StyledObject<String>>,
     /// Show the selections from certain prompts inline
     pub inline_selections: bool,
 }
 
 impl Default for ColorfulTheme {
     fn default() -> ColorfulTheme {
+        ColorfulTheme::colored().with_no_color_env()
+    }
+}
+
+impl ColorfulTheme {
+    /// Creates the default theme regardless of `NO_COLOR`.
+    fn colored() -> ColorfulTheme {
         ColorfulTheme {
             defaults_style: Style::new().for_stderr().cyan(),
             prompt_style: Style::new().for_stderr().bold(),
             prompt_prefix: style("?".to_string()).for_stderr().yellow(),
             prompt_suffix: style("›".to_string()).for_stderr().black().bright(),
             success_prefix: style("✔".to_string()).for_stderr().green(),
+            success_prefixes: HashMap::new(),
             success_suffix: style("·".to_string()).for_stderr().black().bright(),
             error_prefix: style("✘".to_string()).for_stderr().red(),
             error_style: Style::new().for_stderr().red(),
+            info_prefix: style("ℹ".to_string()).for_stderr().blue(),
+            info_style: Style::new().for_stderr(),
+            warning_prefix: style("⚠".to_string()).for_stderr().yellow(),
+            warning_style: Style::new().for_stderr().yellow(),
             hint_style: Style::new().for_stderr().black().bright(),
+            ghost_suggestion_style: Style::new().for_stderr().black().bright(),
+            confirm_default_option_style: Style::new().for_stderr().green().bold(),
             values_style: Style::new().for_stderr().green(),
             active_item_style: Style::new().for_stderr().cyan(),
             inactive_item_style: Style::new().for_stderr(),
@@ -262,8 +759,18 @@ impl Default for ColorfulTheme {
             inactive_item_prefix: style(" ".to_string()).for_stderr(),
             checked_item_prefix: style("✔".to_string()).for_stderr().green(),
             unchecked_item_prefix: style("✔".to_string()).for_stderr().black(),
+            required_item_prefix: style("✔".to_string()).for_stderr().yellow(),
+            checked_radio_item_prefix: style("◉".to_string()).for_stderr().green(),
+            unchecked_radio_item_prefix: style("◯".to_string()).for_stderr().black(),
             picked_item_prefix: style("❯".to_string()).for_stderr().green(),
             unpicked_item_prefix: style(" ".to_string()).for_stderr(),
+            fuzzy_cursor_style: Style::new().for_stderr().black().on_white(),
+            fuzzy_match_highlight_style: Style::new().for_stderr().yellow().bold(),
+            fuzzy_active_row_style: Style::new().for_stderr().on_color256(236),
+            active_item_band_style: None,
+            step_badge_style: Style::new().for_stderr().black().bright(),
+            item_ellipsis: style("…".to_string()).for_stderr().black().bright(),
+            prompt_separator: None,
             inline_selections: true,
         }
     }
@@ -294,6 +801,21 @@ impl Theme for ColorfulTheme {
         )
     }
 
+    /// Formats an informational message.
+    fn format_info(&self, f: &mut dyn fmt::Write, msg: &str) -> fmt::Result {
+        write!(f, "{} {}", &self.info_prefix, self.info_style.apply_to(msg))
+    }
+
+    /// Formats a warning.
+    fn format_warning(&self, f: &mut dyn fmt::Write, msg: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            &self.warning_prefix,
+            self.warning_style.apply_to(msg)
+        )
+    }
+
     /// Formats an input prompt.
     fn format_input_prompt(
         &self,
@@ -310,15 +832,22 @@ impl Theme for ColorfulTheme {
             )?;
         }
 
-        match default {
-            Some(default) => write!(
-                f,
-                "{} {} ",
-                self.hint_style.apply_to(&format!("({})", default)),
-                &self.prompt_suffix
-            ),
-            None => write!(f, "{} ", &self.prompt_suffix),
+        if let Some(default) = default {
+            self.format_default_hint(f, default)?;
+            write!(f, " ")?;
         }
+
+        write!(f, "{} ", &self.prompt_suffix)
+    }
+
+    /// Formats the hint showing the default value of an input prompt.
+    fn format_default_hint(&self, f: &mut dyn fmt::Write, default: &str) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to(&format!("({})", default)))
+    }
+
+    /// Formats the ghost text after the cursor of an input prompt.
+    fn format_ghost_suggestion(&self, f: &mut dyn fmt::Write, suggestion: &str) -> fmt::Result {
+        write!(f, "{}", self.ghost_suggestion_style.apply_to(suggestion))
     }
 
     /// Formats a confirm prompt.
@@ -337,22 +866,74 @@ impl Theme for ColorfulTheme {
             )?;
         }
 
+        let default = match default {
+            None => return write!(f, "{}", &self.prompt_suffix),
+            Some(default) => default,
+        };
+
+        self.format_confirm_options(f, default)?;
+
+        write!(
+            f,
+            " {} {}",
+            &self.prompt_suffix,
+            self.defaults_style
+                .apply_to(if default { "yes" } else { "no" })
+        )
+    }
+
+    /// Formats the options hint of a confirm prompt.
+    fn format_confirm_options(&self, f: &mut dyn fmt::Write, default: bool) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to("("))?;
         match default {
-            None => write!(f, "{}", &self.prompt_suffix),
-            Some(true) => write!(
-                f,
-                "{} {} {}",
-                self.hint_style.apply_to("(Y/n)"),
-                &self.prompt_suffix,
-                self.defaults_style.apply_to("yes")
-            ),
-            Some(false) => write!(
-                f,
-                "{} {} {}",
-                self.hint_style.apply_to("(y/N)"),
-                &self.prompt_suffix,
-                self.defaults_style.apply_to("no")
-            ),
+            true => self.format_confirm_default_option(f, "y")?,
+            false => self.format_confirm_non_default_option(f, "y")?,
+        }
+        write!(f, "{}", self.hint_style.apply_to("/"))?;
+        match default {
+            true => self.format_confirm_non_default_option(f, "n")?,
+            false => self.format_confirm_default_option(f, "n")?,
+        }
+        write!(f, "{}", self.hint_style.apply_to(")"))
+    }
+
+    /// Formats the option of a confirm prompt hint which is picked on enter.
+    fn format_confirm_default_option(&self, f: &mut dyn fmt::Write, option: &str) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.confirm_default_option_style
+                .apply_to(option.to_uppercase())
+        )
+    }
+
+    /// Formats an option of a confirm prompt hint which is not the default.
+    fn format_confirm_non_default_option(
+        &self,
+        f: &mut dyn fmt::Write,
+        option: &str,
+    ) -> fmt::Result {
+        write!(f, "{}", self.hint_style.apply_to(option))
+    }
+
+    /// Formats the prefix of a prompt after selection.
+    fn format_success_prefix(&self, f: &mut dyn fmt::Write, kind: PromptKind) -> fmt::Result {
+        match self.success_prefixes.get(&kind) {
+            Some(prefix) => write!(f, "{}", prefix),
+            None => write!(f, "{}", &self.success_prefix),
+        }
+    }
+
+    /// Returns the style of the background band across the active item.
+    fn active_item_band_style(&self) -> Option<&Style> {
+        self.active_item_band_style.as_ref()
+    }
+
+    /// Formats the separator between a prompt and its answer.
+    fn format_prompt_separator(&self, f: &mut dyn fmt::Write, _kind: PromptKind) -> fmt::Result {
+        match self.prompt_separator {
+            Some(ref separator) => write!(f, "{}", separator),
+            None => write!(f, " {} ", &self.success_suffix),
         }
     }
 
@@ -363,21 +944,11 @@ impl Theme for ColorfulTheme {
         prompt: &str,
         selection: bool,
     ) -> fmt::Result {
-        if !prompt.is_empty() {
-            write!(
-                f,
-                "{} {} ",
-                &self.success_prefix,
-                self.prompt_style.apply_to(prompt)
-            )?;
-        }
-
-        write!(
+        self.write_selection(
             f,
-            "{} {}",
-            &self.success_suffix,
-            self.values_style
-                .apply_to(if selection { "yes" } else { "no" })
+            PromptKind::Confirm,
+            prompt,
+            if selection { "yes" } else { "no" },
         )
     }
 
@@ -388,21 +959,7 @@ impl Theme for ColorfulTheme {
         prompt: &str,
         sel: &str,
     ) -> fmt::Result {
-        if !prompt.is_empty() {
-            write!(
-                f,
-                "{} {} ",
-                &self.success_prefix,
-                self.prompt_style.apply_to(prompt)
-            )?;
-        }
-
-        write!(
-            f,
-            "{} {}",
-            &self.success_suffix,
-            self.values_style.apply_to(sel)
-        )
+        self.write_selection(f, PromptKind::Input, prompt, sel)
     }
 
     /// Formats a password prompt after selection.
@@ -411,7 +968,17 @@ impl Theme for ColorfulTheme {
         f: &mut dyn fmt::Write,
         prompt: &str,
     ) -> fmt::Result {
-        self.format_input_prompt_selection(f, prompt, "********")
+        self.write_selection(f, PromptKind::Password, prompt, "********")
+    }
+
+    /// Formats a select prompt after selection.
+    fn format_select_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        sel: &str,
+    ) -> fmt::Result {
+        self.write_selection(f, PromptKind::Select, prompt, sel)
     }
 
     /// Formats a multi select prompt after selection.
@@ -421,29 +988,17 @@ impl Theme for ColorfulTheme {
         prompt: &str,
         selections: &[&str],
     ) -> fmt::Result {
-        if !prompt.is_empty() {
-            write!(
-                f,
-                "{} {} ",
-                &self.success_prefix,
-                self.prompt_style.apply_to(prompt)
-            )?;
-        }
-
-        write!(f, "{} ", &self.success_suffix)?;
-
-        if self.inline_selections {
-            for (idx, sel) in selections.iter().enumerate() {
-                write!(
-                    f,
-                    "{}{}",
-                    if idx == 0 { "" } else { ", " },
-                    self.values_style.apply_to(sel)
-                )?;
-            }
-        }
+        self.write_selections(f, PromptKind::MultiSelect, prompt, selections)
+    }
 
-        Ok(())
+    /// Formats a sort prompt after selection.
+    fn format_sort_prompt_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        selections: &[&str],
+    ) -> fmt::Result {
+        self.write_selections(f, PromptKind::Sort, prompt, selections)
     }
 
     /// Formats a select prompt item.
@@ -467,6 +1022,20 @@ impl Theme for ColorfulTheme {
         write!(f, "{} {}", details.0, details.1)
     }
 
+    /// Formats the indicator below the items of a paged list.
+    fn format_page_indicator(
+        &self,
+        f: &mut dyn fmt::Write,
+        page: usize,
+        pages: usize,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "  {}",
+            self.hint_style.apply_to(format!("Page {}/{}", page, pages))
+        )
+    }
+
     /// Formats a multi select prompt item.
     fn format_multi_select_prompt_item(
         &self,
@@ -497,6 +1066,92 @@ impl Theme for ColorfulTheme {
         write!(f, "{} {}", details.0, details.1)
     }
 
+    /// Formats a required multi select prompt item.
+    fn format_multi_select_prompt_required_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+    ) -> fmt::Result {
+        let text = match active {
+            true => self.active_item_style.apply_to(text),
+            false => self.inactive_item_style.apply_to(text),
+        };
+
+        write!(f, "{} {}", &self.required_item_prefix, text)
+    }
+
+    /// Formats a multi select prompt item that is part of an exclusive group.
+    fn format_multi_select_prompt_radio_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        checked: bool,
+        active: bool,
+    ) -> fmt::Result {
+        let prefix = match checked {
+            true => &self.checked_radio_item_prefix,
+            false => &self.unchecked_radio_item_prefix,
+        };
+        let text = match active {
+            true => self.active_item_style.apply_to(text),
+            false => self.inactive_item_style.apply_to(text),
+        };
+
+        write!(f, "{} {}", prefix, text)
+    }
+
+    /// Formats a pair of items the user has to rank in a sort prompt.
+    fn format_sort_prompt_pair(
+        &self,
+        f: &mut dyn fmt::Write,
+        left: &str,
+        right: &str,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.hint_style.apply_to("←"),
+            self.active_item_style.apply_to(left),
+            self.hint_style.apply_to("|"),
+            self.active_item_style.apply_to(right),
+            self.hint_style.apply_to("→"),
+        )
+    }
+
+    /// Formats the picked up sort prompt item with a custom marker.
+    fn format_sort_prompt_grabbed_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        symbol: &str,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.active_item_style.apply_to(symbol),
+            self.active_item_style.apply_to(text)
+        )
+    }
+
+    /// Formats the position of a sort prompt item.
+    fn format_sort_prompt_item_position(
+        &self,
+        f: &mut dyn fmt::Write,
+        position: usize,
+        total: usize,
+        picked: bool,
+        active: bool,
+    ) -> fmt::Result {
+        let width = total.to_string().len();
+        let position = format!("{:>width$}.", position, width = width);
+
+        match (picked, active) {
+            (true, true) => write!(f, "{}", self.active_item_style.apply_to(position).bold()),
+            _ => write!(f, "{}", self.hint_style.apply_to(position)),
+        }
+    }
+
     /// Formats a sort prompt item.
     fn format_sort_prompt_item(
         &self,
@@ -522,188 +1177,2275 @@ impl Theme for ColorfulTheme {
 
         write!(f, "{} {}", details.0, details.1)
     }
-}
 
-/// Helper struct to conveniently render a theme ot a term.
-pub(crate) struct TermThemeRenderer<'a> {
-    term: &'a Term,
-    theme: &'a dyn Theme,
-    height: usize,
-    prompt_height: usize,
-    prompts_reset_height: bool,
-}
+    /// Formats a fuzzy select prompt.
+    fn format_fuzzy_select_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        query: &str,
+        cursor: usize,
+    ) -> fmt::Result {
+        let (head, tail) = split_at_char(query, cursor);
+        let mut tail = tail.chars();
+        let cursor = tail.next().unwrap_or(' ');
 
-impl<'a> TermThemeRenderer<'a> {
-    pub fn new(term: &'a Term, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
-        TermThemeRenderer {
-            term,
-            theme,
-            height: 0,
-            prompt_height: 0,
-            prompts_reset_height: true,
+        if !prompt.is_empty() {
+            write!(
+                f,
+                "{} {} ",
+                &self.prompt_prefix,
+                self.prompt_style.apply_to(prompt)
+            )?;
         }
-    }
 
-    pub fn set_prompts_reset_height(&mut self, val: bool) {
-        self.prompts_reset_height = val;
+        write!(
+            f,
+            "{} {}{}{}",
+            &self.prompt_suffix,
+            head,
+            self.fuzzy_cursor_style.apply_to(cursor),
+            tail.as_str()
+        )
     }
 
-    pub fn term(&self) -> &Term {
-        self.term
-    }
+    /// Formats a fuzzy select prompt item.
+    fn format_fuzzy_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        active: bool,
+        matches: &[usize],
+    ) -> fmt::Result {
+        let (prefix, style) = match active {
+            true => (&self.active_item_prefix, &self.active_item_style),
+            false => (&self.inactive_item_prefix, &self.inactive_item_style),
+        };
 
-    pub fn add_line(&mut self) {
-        self.height += 1;
+        write!(f, "{} ", prefix)?;
+        self.write_fuzzy_matches(f, text, style, active, matches)
     }
 
-    fn write_formatted_str<
-        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
-    >(
-        &mut self,
-        f: F,
-    ) -> io::Result<()> {
-        let mut buf = String::new();
-        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-        self.height += buf.chars().filter(|&x| x == '\n').count();
-        self.term.write_str(&buf)
-    }
+    /// Formats a fuzzy select prompt item which can be checked.
+    fn format_fuzzy_multi_select_prompt_item(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        checked: bool,
+        active: bool,
+        matches: &[usize],
+    ) -> fmt::Result {
+        let prefix = match checked {
+            true => &self.checked_item_prefix,
+            false => &self.unchecked_item_prefix,
+        };
+        let style = match active {
+            true => &self.active_item_style,
+            false => &self.inactive_item_style,
+        };
 
-    fn write_formatted_line<
-        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
-    >(
-        &mut self,
-        f: F,
-    ) -> io::Result<()> {
-        let mut buf = String::new();
-        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-        self.height += buf.chars().filter(|&x| x == '\n').count() + 1;
-        self.term.write_line(&buf)
+        write!(f, "{} ", prefix)?;
+        self.write_fuzzy_matches(f, text, style, active, matches)
     }
 
-    fn write_formatted_prompt<
-        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
-    >(
-        &mut self,
-        f: F,
-    ) -> io::Result<()> {
-        self.write_formatted_line(f)?;
-        if self.prompts_reset_height {
-            self.prompt_height = self.height;
-            self.height = 0;
+    /// Formats the text of a prompt with a badge for the step of a multi
+    /// step flow.
+    fn format_step_badge(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        current: usize,
+        total: usize,
+    ) -> fmt::Result {
+        if !prompt.is_empty() {
+            write!(f, "{} ", prompt)?;
         }
-        Ok(())
-    }
 
-    pub fn error(&mut self, err: &str) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| this.theme.format_error(buf, err))
+        write!(
+            f,
+            "{}",
+            self.step_badge_style
+                .apply_to(format!("(step {}/{})", current, total))
+        )
     }
 
-    pub fn confirm_prompt(&mut self, prompt: &str, default: Option<bool>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| this.theme.format_confirm_prompt(buf, prompt, default))
-    }
+    /// Formats a prompt which waits for a slow operation with a spinner.
+    fn format_wait_spinner(
+        &self,
+        f: &mut dyn fmt::Write,
+        prompt: &str,
+        frame: usize,
+    ) -> fmt::Result {
+        let frame = BRAILLE_SPINNER[frame % BRAILLE_SPINNER.len()];
 
-    pub fn confirm_prompt_selection(&mut self, prompt: &str, sel: bool) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_confirm_prompt_selection(buf, prompt, sel)
-        })
+        if prompt.is_empty() {
+            write!(f, "{}", self.hint_style.apply_to(frame))
+        } else {
+            write!(
+                f,
+                "{} {}",
+                self.hint_style.apply_to(frame),
+                self.prompt_style.apply_to(prompt)
+            )
+        }
     }
 
-    pub fn input_prompt(&mut self, prompt: &str, default: Option<&str>) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| this.theme.format_input_prompt(buf, prompt, default))
+    /// Returns the number of frames of the spinner.
+    fn wait_spinner_frames(&self) -> usize {
+        BRAILLE_SPINNER.len()
     }
 
-    pub fn input_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_input_prompt_selection(buf, prompt, sel)
-        })
+    /// Formats the ellipsis which ends items truncated to the terminal width.
+    fn format_item_ellipsis(&self, f: &mut dyn fmt::Write) -> fmt::Result {
+        write!(f, "{}", &self.item_ellipsis)
     }
 
-    pub fn password_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_str(|this, buf| {
-            write!(buf, "\r")?;
-            this.theme.format_password_prompt(buf, prompt)
-        })
-    }
+    /// Returns a copy of the theme with a different prompt prefix or suffix.
+    fn with_prompt_affixes(
+        &self,
+        prefix: Option<&StyledObject<String>>,
+        suffix: Option<&StyledObject<String>>,
+    ) -> Option<Box<dyn Theme>> {
+        let mut theme = self.clone();
 
-    pub fn password_prompt_selection(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_password_prompt_selection(buf, prompt)
-        })
-    }
+        if let Some(prefix) = prefix {
+            theme.prompt_prefix = prefix.clone();
+        }
+        if let Some(suffix) = suffix {
+            theme.prompt_suffix = suffix.clone();
+        }
 
-    pub fn select_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| this.theme.format_select_prompt(buf, prompt))
+        Some(Box::new(theme))
     }
 
-    pub fn select_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_select_prompt_selection(buf, prompt, sel)
-        })
+    fn with_prompt_separator(&self, separator: &StyledObject<String>) -> Option<Box<dyn Theme>> {
+        let mut theme = self.clone();
+        theme.prompt_separator = Some(separator.clone());
+        Some(Box::new(theme))
     }
+}
 
-    pub fn select_prompt_item(&mut self, text: &str, active: bool) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
-            this.theme.format_select_prompt_item(buf, text, active)
-        })
-    }
+impl ColorfulTheme {
+    /// Removes all colors and other styles while keeping the symbols.
+    ///
+    /// Only the cursor of fuzzy select prompts stays visible in reverse
+    /// video.  Themes do this on their own if the `NO_COLOR` variable is
+    /// set.  Colors are also left out if the terminal does not support
+    /// them, as detected by console for stdout and stderr separately.
+    pub fn no_color(&mut self) -> &mut ColorfulTheme {
+        macro_rules! plain {
+            ($($field:ident),*) => {
+                $(self.$field = self.$field.clone().force_styling(false);)*
+            };
+        }
 
-    pub fn multi_select_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| this.theme.format_multi_select_prompt(buf, prompt))
+        plain!(
+            defaults_style,
+            prompt_style,
+            prompt_prefix,
+            prompt_suffix,
+            success_prefix,
+            success_suffix,
+            error_prefix,
+            error_style,
+            info_prefix,
+            info_style,
+            warning_prefix,
+            warning_style,
+            hint_style,
+            ghost_suggestion_style,
+            confirm_default_option_style,
+            values_style,
+            active_item_style,
+            inactive_item_style,
+            active_item_prefix,
+            inactive_item_prefix,
+            checked_item_prefix,
+            unchecked_item_prefix,
+            required_item_prefix,
+            checked_radio_item_prefix,
+            unchecked_radio_item_prefix,
+            picked_item_prefix,
+            unpicked_item_prefix,
+            fuzzy_match_highlight_style,
+            fuzzy_active_row_style,
+            step_badge_style,
+            item_ellipsis
+        );
+
+        for prefix in self.success_prefixes.values_mut() {
+            *prefix = prefix.clone().force_styling(false);
+        }
+        if let Some(ref mut separator) = self.prompt_separator {
+            *separator = separator.clone().force_styling(false);
+        }
+        if let Some(ref mut band) = self.active_item_band_style {
+            *band = band.clone().force_styling(false);
+        }
+        self.fuzzy_cursor_style = Style::new().for_stderr().reverse();
+        self
     }
 
-    pub fn multi_select_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme
-                .format_multi_select_prompt_selection(buf, prompt, sel)
-        })
+    /// Applies [no_color](#method.no_color) if `NO_COLOR` is set.
+    fn with_no_color_env(mut self) -> ColorfulTheme {
+        if env::var_os("NO_COLOR").is_some_and(|val| !val.is_empty()) {
+            self.no_color();
+        }
+        self
     }
 
-    pub fn multi_select_prompt_item(
-        &mut self,
-        text: &str,
-        checked: bool,
-        active: bool,
-    ) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
-            this.theme
-                .format_multi_select_prompt_item(buf, text, checked, active)
-        })
+    /// Creates a colorful theme tuned for terminals with a light background.
+    pub fn light() -> ColorfulTheme {
+        ColorfulTheme {
+            defaults_style: Style::new().for_stderr().blue(),
+            prompt_prefix: style("?".to_string()).for_stderr().blue(),
+            prompt_suffix: style("›".to_string()).for_stderr().color256(244),
+            success_suffix: style("·".to_string()).for_stderr().color256(244),
+            hint_style: Style::new().for_stderr().color256(244),
+            active_item_style: Style::new().for_stderr().blue(),
+            unchecked_item_prefix: style("✔".to_string()).for_stderr().color256(250),
+            required_item_prefix: style("✔".to_string()).for_stderr().magenta(),
+            unchecked_radio_item_prefix: style("◯".to_string()).for_stderr().color256(250),
+            fuzzy_cursor_style: Style::new().for_stderr().white().on_black(),
+            fuzzy_match_highlight_style: Style::new().for_stderr().magenta().bold(),
+            fuzzy_active_row_style: Style::new().for_stderr().on_color256(254),
+            ..ColorfulTheme::colored()
+        }
+        .with_no_color_env()
     }
 
-    pub fn sort_prompt(&mut self, prompt: &str) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| this.theme.format_sort_prompt(buf, prompt))
+    /// Creates a colorful theme matching the background of the terminal.
+    ///
+    /// The background is taken from the `COLORFGBG` variable which many
+    /// terminals set.  Without it the theme for dark backgrounds is used.
+    pub fn adaptive() -> ColorfulTheme {
+        match env::var("COLORFGBG") {
+            Ok(ref colors) if is_light_background(colors) => ColorfulTheme::light(),
+            _ => ColorfulTheme::default(),
+        }
     }
 
-    pub fn sort_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
-        self.write_formatted_prompt(|this, buf| {
-            this.theme.format_sort_prompt_selection(buf, prompt, sel)
-        })
+    /// Creates a colorful theme with ASCII symbols only.
+    ///
+    /// This is the fallback of the [nerdfont](#method.nerdfont) and
+    /// [emoji](#method.emoji) presets.
+    pub fn ascii() -> ColorfulTheme {
+        ColorfulTheme {
+            prompt_suffix: style(">".to_string()).for_stderr().black().bright(),
+            success_prefix: style("+".to_string()).for_stderr().green(),
+            success_suffix: style("-".to_string()).for_stderr().black().bright(),
+            error_prefix: style("!".to_string()).for_stderr().red(),
+            item_ellipsis: style("...".to_string()).for_stderr().black().bright(),
+            info_prefix: style("i".to_string()).for_stderr().blue(),
+            warning_prefix: style("*".to_string()).for_stderr().yellow(),
+            active_item_prefix: style(">".to_string()).for_stderr().green(),
+            checked_item_prefix: style("[x]".to_string()).for_stderr().green(),
+            unchecked_item_prefix: style("[ ]".to_string()).for_stderr().black(),
+            required_item_prefix: style("[*]".to_string()).for_stderr().yellow(),
+            checked_radio_item_prefix: style("(*)".to_string()).for_stderr().green(),
+            unchecked_radio_item_prefix: style("( )".to_string()).for_stderr().black(),
+            picked_item_prefix: style(">".to_string()).for_stderr().green(),
+            ..ColorfulTheme::colored()
+        }
+        .with_no_color_env()
     }
 
-    pub fn sort_prompt_item(&mut self, text: &str, picked: bool, active: bool) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| {
-            this.theme
-                .format_sort_prompt_item(buf, text, picked, active)
-        })
-    }
+    /// Creates a colorful theme with icons from [nerd fonts](https://www.nerdfonts.com).
+    ///
+    /// The icons need a patched font which cannot be detected, so this only
+    /// falls back to [ascii](#method.ascii) if the terminal does not want
+    /// emojis.  The icons can be overridden afterwards through the fields.
+    pub fn nerdfont() -> ColorfulTheme {
+        if !Term::stderr().features().wants_emoji() {
+            return ColorfulTheme::ascii();
+        }
 
-    pub fn clear(&mut self) -> io::Result<()> {
-        self.term
-            .clear_last_lines(self.height + self.prompt_height)?;
-        self.height = 0;
-        Ok(())
+        ColorfulTheme {
+            prompt_prefix: style("\u{f059}".to_string()).for_stderr().yellow(),
+            prompt_suffix: style("\u{f054}".to_string()).for_stderr().black().bright(),
+            success_prefix: style("\u{f00c}".to_string()).for_stderr().green(),
+            error_prefix: style("\u{f00d}".to_string()).for_stderr().red(),
+            info_prefix: style("\u{f05a}".to_string()).for_stderr().blue(),
+            warning_prefix: style("\u{f071}".to_string()).for_stderr().yellow(),
+            active_item_prefix: style("\u{f0da}".to_string()).for_stderr().green(),
+            checked_item_prefix: style("\u{f14a}".to_string()).for_stderr().green(),
+            unchecked_item_prefix: style("\u{f096}".to_string()).for_stderr().black(),
+            required_item_prefix: style("\u{f023}".to_string()).for_stderr().yellow(),
+            checked_radio_item_prefix: style("\u{f192}".to_string()).for_stderr().green(),
+            unchecked_radio_item_prefix: style("\u{f10c}".to_string()).for_stderr().black(),
+            picked_item_prefix: style("\u{f0dc}".to_string()).for_stderr().green(),
+            ..ColorfulTheme::colored()
+        }
+        .with_no_color_env()
     }
 
-    pub fn clear_preserve_prompt(&mut self, size_vec: &[usize]) -> io::Result<()> {
-        let mut new_height = self.height;
-        //Check each item size, increment on finding an overflow
-        for size in size_vec {
-            if *size > self.term.size().1 as usize {
-                new_height += 1;
-            }
+    /// Creates a colorful theme with emoji symbols.
+    ///
+    /// Falls back to [ascii](#method.ascii) if the terminal does not want
+    /// emojis.  The symbols can be overridden afterwards through the fields.
+    pub fn emoji() -> ColorfulTheme {
+        if !Term::stderr().features().wants_emoji() {
+            return ColorfulTheme::ascii();
         }
-        self.term.clear_last_lines(new_height)?;
-        self.height = 0;
-        Ok(())
+
+        ColorfulTheme {
+            prompt_prefix: style("❓".to_string()).for_stderr(),
+            success_prefix: style("✅".to_string()).for_stderr(),
+            error_prefix: style("❌".to_string()).for_stderr(),
+            info_prefix: style("💡".to_string()).for_stderr(),
+            warning_prefix: style("🚧".to_string()).for_stderr(),
+            active_item_prefix: style("👉".to_string()).for_stderr(),
+            inactive_item_prefix: style("  ".to_string()).for_stderr(),
+            checked_item_prefix: style("✅".to_string()).for_stderr(),
+            unchecked_item_prefix: style("⬜".to_string()).for_stderr(),
+            required_item_prefix: style("🔒".to_string()).for_stderr(),
+            checked_radio_item_prefix: style("🔘".to_string()).for_stderr(),
+            unchecked_radio_item_prefix: style("⚪".to_string()).for_stderr(),
+            picked_item_prefix: style("👉".to_string()).for_stderr(),
+            unpicked_item_prefix: style("  ".to_string()).for_stderr(),
+            ..ColorfulTheme::colored()
+        }
+        .with_no_color_env()
+    }
+
+    /// Writes a prompt after selection with a single value.
+    fn write_selection(
+        &self,
+        f: &mut dyn fmt::Write,
+        kind: PromptKind,
+        prompt: &str,
+        sel: &str,
+    ) -> fmt::Result {
+        self.write_selection_prompt(f, kind, prompt)?;
+        write!(f, "{}", self.values_style.apply_to(sel))
+    }
+
+    /// Writes a prompt after selection up to the answer.
+    ///
+    /// Without a prompt only the success suffix is shown.
+    fn write_selection_prompt(
+        &self,
+        f: &mut dyn fmt::Write,
+        kind: PromptKind,
+        prompt: &str,
+    ) -> fmt::Result {
+        if prompt.is_empty() {
+            return write!(f, "{} ", &self.success_suffix);
+        }
+
+        self.format_success_prefix(f, kind)?;
+        write!(f, " {}", self.prompt_style.apply_to(prompt))?;
+        self.format_prompt_separator(f, kind)
+    }
+
+    /// Writes a prompt after selection with a list of values.
+    fn write_selections(
+        &self,
+        f: &mut dyn fmt::Write,
+        kind: PromptKind,
+        prompt: &str,
+        selections: &[&str],
+    ) -> fmt::Result {
+        self.write_selection_prompt(f, kind, prompt)?;
+
+        if self.inline_selections {
+            for (idx, sel) in selections.iter().enumerate() {
+                write!(
+                    f,
+                    "{}{}",
+                    if idx == 0 { "" } else { ", " },
+                    self.values_style.apply_to(sel)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes `text` with the characters at `matches` highlighted.
+    ///
+    /// Every character is styled on its own so that the row style of an
+    /// active item wraps the match style instead of being reset by it.
+    fn write_fuzzy_matches(
+        &self,
+        f: &mut dyn fmt::Write,
+        text: &str,
+        style: &Style,
+        active: bool,
+        matches: &[usize],
+    ) -> fmt::Result {
+        for (idx, chr) in text.chars().enumerate() {
+            let chr = match matches.contains(&idx) {
+                true => self.fuzzy_match_highlight_style.apply_to(chr),
+                false => style.apply_to(chr),
+            };
+
+            if active {
+                write!(f, "{}", self.fuzzy_active_row_style.apply_to(chr))?;
+            } else {
+                write!(f, "{}", chr)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds a theme by overriding single parts of [ColorfulTheme](struct.ColorfulTheme.html).
+///
+/// This avoids implementing [Theme](trait.Theme.html) just to change a
+/// glyph or a color.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::{console::style, theme::ThemeBuilder, Select};
+///
+/// fn main() -> std::io::Result<()> {
+///     let theme = ThemeBuilder::new()
+///         .prompt_prefix(style("»".to_string()).for_stderr().magenta())
+///         .active_item_prefix(style("→".to_string()).for_stderr().magenta())
+///         .build();
+///
+///     let selection = Select::with_theme(&*theme)
+///         .item("Option A")
+///         .item("Option B")
+///         .interact()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Default)]
+pub struct ThemeBuilder {
+    theme: ColorfulTheme,
+}
+
+impl ThemeBuilder {
+    /// Creates a theme builder starting from the default colorful theme.
+    pub fn new() -> ThemeBuilder {
+        ThemeBuilder::default()
+    }
+
+    /// Sets the style for default values.
+    pub fn defaults_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.defaults_style = val;
+        self
+    }
+
+    /// Sets the style for prompts.
+    pub fn prompt_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.prompt_style = val;
+        self
+    }
+
+    /// Sets the prompt prefix value and style.
+    pub fn prompt_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.prompt_prefix = val;
+        self
+    }
+
+    /// Sets the prompt suffix value and style.
+    pub fn prompt_suffix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.prompt_suffix = val;
+        self
+    }
+
+    /// Sets the prefix value and style of a prompt on success.
+    pub fn success_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.success_prefix = val;
+        self
+    }
+
+    /// Sets the prefix value and style of one kind of prompt on success.
+    ///
+    /// This replaces the [success_prefix](#method.success_prefix) for that
+    /// kind.
+    pub fn success_prefix_for(
+        &mut self,
+        kind: PromptKind,
+        val: StyledObject<String>,
+    ) -> &mut ThemeBuilder {
+        self.theme.success_prefixes.insert(kind, val);
+        self
+    }
+
+    /// Sets the suffix value and style of a prompt on success.
+    pub fn success_suffix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.success_suffix = val;
+        self
+    }
+
+    /// Sets the error prefix value and style.
+    pub fn error_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.error_prefix = val;
+        self
+    }
+
+    /// Sets the style for error messages.
+    pub fn error_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.error_style = val;
+        self
+    }
+
+    /// Sets the info prefix value and style.
+    pub fn info_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.info_prefix = val;
+        self
+    }
+
+    /// Sets the style for info messages.
+    pub fn info_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.info_style = val;
+        self
+    }
+
+    /// Sets the warning prefix value and style.
+    pub fn warning_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.warning_prefix = val;
+        self
+    }
+
+    /// Sets the style for warning messages.
+    pub fn warning_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.warning_style = val;
+        self
+    }
+
+    /// Sets the style for hints.
+    pub fn hint_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.hint_style = val;
+        self
+    }
+
+    /// Sets the style for the default option in the hint of confirm prompts.
+    pub fn confirm_default_option_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.confirm_default_option_style = val;
+        self
+    }
+
+    /// Sets the style for values on prompt success.
+    pub fn values_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.values_style = val;
+        self
+    }
+
+    /// Sets the style for active items.
+    pub fn active_item_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.active_item_style = val;
+        self
+    }
+
+    /// Sets the style for inactive items.
+    pub fn inactive_item_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.inactive_item_style = val;
+        self
+    }
+
+    /// Sets the prefix value and style of the active item in selects.
+    pub fn active_item_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.active_item_prefix = val;
+        self
+    }
+
+    /// Sets the prefix value and style of inactive items in selects.
+    pub fn inactive_item_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.inactive_item_prefix = val;
+        self
+    }
+
+    /// Sets the prefix value and style of checked items in multi selects.
+    pub fn checked_item_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.checked_item_prefix = val;
+        self
+    }
+
+    /// Sets the prefix value and style of unchecked items in multi selects.
+    pub fn unchecked_item_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.unchecked_item_prefix = val;
+        self
+    }
+
+    /// Sets the prefix value and style of picked items in sort prompts.
+    pub fn picked_item_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.picked_item_prefix = val;
+        self
+    }
+
+    /// Sets the prefix value and style of unpicked items in sort prompts.
+    pub fn unpicked_item_prefix(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.unpicked_item_prefix = val;
+        self
+    }
+
+    /// Sets the style for a background band across the active item of
+    /// select, multi select and sort prompts.
+    pub fn active_item_band_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.active_item_band_style = Some(val);
+        self
+    }
+
+    /// Sets the style for the ghost text of completions in input prompts.
+    pub fn ghost_suggestion_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.ghost_suggestion_style = val;
+        self
+    }
+
+    /// Sets the style for the step badge of prompts in multi step flows.
+    pub fn step_badge_style(&mut self, val: Style) -> &mut ThemeBuilder {
+        self.theme.step_badge_style = val;
+        self
+    }
+
+    /// Sets the ellipsis value and style for items truncated to the terminal width.
+    pub fn item_ellipsis(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.item_ellipsis = val;
+        self
+    }
+
+    /// Sets the separator value and style between answered prompts and
+    /// their answers.
+    pub fn prompt_separator(&mut self, val: StyledObject<String>) -> &mut ThemeBuilder {
+        self.theme.prompt_separator = Some(val);
+        self
+    }
+
+    /// Shows the selections from certain prompts inline.
+    ///
+    /// The default is to show them.
+    pub fn inline_selections(&mut self, val: bool) -> &mut ThemeBuilder {
+        self.theme.inline_selections = val;
+        self
+    }
+
+    /// Removes all colors and other styles while keeping the symbols.
+    ///
+    /// See [ColorfulTheme::no_color](struct.ColorfulTheme.html#method.no_color).
+    pub fn no_color(&mut self) -> &mut ThemeBuilder {
+        self.theme.no_color();
+        self
+    }
+
+    /// Builds the theme.
+    ///
+    /// The builder can be changed afterwards to build further themes.
+    pub fn build(&self) -> Box<dyn Theme> {
+        Box::new(self.theme.clone())
+    }
+}
+
+/// Returns the color of the 256 color palette closest to an RGB color.
+///
+/// console only renders the 16 and 256 color palettes, so RGB colors such
+/// as brand colors are approximated with the 6x6x6 color cube or the gray
+/// ramp of the palette.  Styles are still dropped entirely on terminals
+/// without color support.
+///
+/// ## Examples
+///
+/// ```rust
+/// use dialoguer::{console::Style, theme::{rgb, ThemeBuilder}};
+///
+/// let theme = ThemeBuilder::new()
+///     .active_item_style(Style::new().for_stderr().fg(rgb(0xff, 0x87, 0x00)))
+///     .build();
+/// ```
+pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    fn nearest_level(val: u8) -> usize {
+        (0..LEVELS.len())
+            .min_by_key(|&idx| (LEVELS[idx] as i32 - val as i32).abs())
+            .unwrap()
+    }
+
+    fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+        let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2);
+        d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+    }
+
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let cube_idx = 16 + 36 * ri + 6 * gi + bi;
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_step;
+    let gray_idx = 232 + gray_step as usize;
+
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube, (r, g, b)) {
+        Color::Color256(gray_idx as u8)
+    } else {
+        Color::Color256(cube_idx as u8)
+    }
+}
+
+/// Checks if a `COLORFGBG` value such as `0;15` names a light background.
+fn is_light_background(colorfgbg: &str) -> bool {
+    match colorfgbg.rsplit(';').next().map(str::parse::<u8>) {
+        Some(Ok(bg)) => bg == 7 || bg == 15,
+        _ => false,
+    }
+}
+
+/// Splits `text` at the character position `pos`.
+fn split_at_char(text: &str, pos: usize) -> (&str, &str) {
+    let idx = text
+        .char_indices()
+        .nth(pos)
+        .map_or(text.len(), |(idx, _)| idx);
+    text.split_at(idx)
+}
+
+/// Prints an informational message styled by `theme` to `term`.
+///
+/// This matches the look of the prompts for status lines in between.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use dialoguer::{console::Term, theme::{self, ColorfulTheme}};
+///
+/// fn main() -> std::io::Result<()> {
+///     theme::print_info(&Term::stderr(), &ColorfulTheme::default(), "Fetching items")?;
+///     Ok(())
+/// }
+/// ```
+pub fn print_info(term: &dyn Backend, theme: &dyn Theme, msg: &str) -> io::Result<()> {
+    TermThemeRenderer::new(term, theme).info_line(msg)
+}
+
+/// Prints a warning styled by `theme` to `term`.
+pub fn print_warning(term: &dyn Backend, theme: &dyn Theme, msg: &str) -> io::Result<()> {
+    TermThemeRenderer::new(term, theme).warning_line(msg)
+}
+
+/// Prints an error styled by `theme` to `term`.
+pub fn print_error(term: &dyn Backend, theme: &dyn Theme, msg: &str) -> io::Result<()> {
+    TermThemeRenderer::new(term, theme).error_line(msg)
+}
+
+/// Mirrors a formatted line around `text` and aligns it to the right.
+///
+/// The words before and after `text` swap sides and are reversed, while
+/// style codes stay attached to what they style.  Lines which do not
+/// contain `text` as is, such as items with highlighted matches, are only
+/// aligned.
+fn mirror_line(line: &str, text: &str, width: usize) -> String {
+    let mirrored = match line.find(text).filter(|_| !text.is_empty()) {
+        Some(idx) => {
+            let (before, rest) = line.split_at(idx);
+            let (text, after) = rest.split_at(text.len());
+            let (before, open) = split_trailing_ansi_codes(before);
+            let (close, after) = split_leading_ansi_codes(after);
+
+            let parts = [
+                reverse_words(after),
+                format!("{}{}{}", open, text, close),
+                reverse_words(before),
+            ];
+            parts
+                .iter()
+                .filter(|part| !part.is_empty())
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+        None => line.to_string(),
+    };
+
+    let padding = width.saturating_sub(measure_text_width(&mirrored));
+    format!("{}{}", " ".repeat(padding), mirrored)
+}
+
+/// Reverses the order of the space separated words in `text`.
+fn reverse_words(text: &str) -> String {
+    text.split(' ')
+        .filter(|word| !word.is_empty())
+        .rev()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Checks if `code` is the body of a style code, without `ESC [` and `m`.
+fn is_ansi_code_body(code: &str) -> bool {
+    code.chars().all(|c| c.is_ascii_digit() || c == ';')
+}
+
+/// Splits style codes off the end of `text`.
+fn split_trailing_ansi_codes(text: &str) -> (&str, &str) {
+    let mut end = text.len();
+
+    while text[..end].ends_with('m') {
+        match text[..end].rfind("\x1b[") {
+            Some(idx) if is_ansi_code_body(&text[idx + 2..end - 1]) => end = idx,
+            _ => break,
+        }
+    }
+
+    text.split_at(end)
+}
+
+/// Splits style codes off the start of `text`.
+fn split_leading_ansi_codes(text: &str) -> (&str, &str) {
+    let mut start = 0;
+
+    while text[start..].starts_with("\x1b[") {
+        match text[start..].find('m') {
+            Some(idx) if is_ansi_code_body(&text[start + 2..start + idx]) => start += idx + 1,
+            _ => break,
+        }
+    }
+
+    text.split_at(start)
+}
+
+/// Pads `line` with spaces to `width` columns and applies `band` to all
+/// of it.
+///
+/// Styles inside `line` end with a reset, which would also end the
+/// background, so the band is started again after every reset.
+fn band_line(line: &str, band: &Style, width: usize) -> String {
+    let padding = " ".repeat(width.saturating_sub(measure_text_width(line)));
+    let marked = band.apply_to("\0").to_string();
+    let (start, end) = marked.split_once('\0').unwrap_or(("", ""));
+
+    if start.is_empty() {
+        return format!("{}{}", line, padding);
+    }
+
+    let line = line.replace("\x1b[0m", &format!("\x1b[0m{}", start));
+    format!("{}{}{}{}", start, line, padding, end)
+}
+
+/// Returns the number of terminal rows `line` takes up when it wraps.
+///
+/// The width is measured in columns, so wide characters such as CJK and
+/// emoji count twice.
+fn line_rows(line: &str, width: usize) -> usize {
+    rows(&[measure_text_width(line)], width)
+}
+
+/// Returns the number of terminal rows lines of the given widths take up
+/// on a terminal `width` columns wide.
+fn rows(lines: &[usize], width: usize) -> usize {
+    lines
+        .iter()
+        .map(|&columns| match width {
+            0 => 1,
+            width => ((columns + width - 1) / width).max(1),
+        })
+        .sum()
+}
+
+/// Removes the last `n` rows and an unfinished line from `buffer`.
+///
+/// Lines wider than `width` count as the rows they wrap into.
+fn clear_last_lines(buffer: &mut String, mut n: usize, width: usize) {
+    if n == 0 {
+        return;
+    }
+
+    let mut end = buffer.rfind('\n').map_or(0, |idx| idx + 1);
+    while n > 0 && end > 0 {
+        let start = buffer[..end - 1].rfind('\n').map_or(0, |idx| idx + 1);
+        n = n.saturating_sub(line_rows(&buffer[start..end - 1], width));
+        end = start;
+    }
+
+    buffer.truncate(end);
+}
+
+/// Draws `frames` on the current line until `done` is set and clears the
+/// line again.
+fn spin(term: &dyn Backend, frames: &[String], done: &AtomicBool) -> io::Result<()> {
+    let started = Instant::now();
+    let mut drawn = false;
+
+    while !done.load(Ordering::Acquire) {
+        let elapsed = started.elapsed();
+        if elapsed >= SPINNER_DELAY {
+            let tick = (elapsed - SPINNER_DELAY).as_millis() / SPINNER_INTERVAL.as_millis();
+            term.clear_line()?;
+            term.write_str(&frames[tick as usize % frames.len()])?;
+            term.flush()?;
+            drawn = true;
+        }
+        thread::sleep(SPINNER_INTERVAL / 4);
+    }
+
+    if drawn {
+        term.clear_line()?;
+    }
+    Ok(())
+}
+
+/// Sets a flag once it is dropped.
+#[cfg(feature = "async")]
+struct StopOnDrop(Arc<AtomicBool>);
+
+#[cfg(feature = "async")]
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// How long a prompt waits before it shows a spinner.
+const SPINNER_DELAY: Duration = Duration::from_millis(100);
+
+/// How long a spinner frame is shown.
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Writer which receives a plain text transcript of a prompt.
+pub(crate) type MirrorWriter<'a> = RefCell<Box<dyn io::Write + 'a>>;
+
+/// Erases the lifetime of the writer in a
+/// [MirrorWriter](type.MirrorWriter.html) so renderers can borrow it.
+trait Mirror {
+    fn write_mirror_line(&self, line: &str) -> io::Result<()>;
+}
+
+impl Mirror for MirrorWriter<'_> {
+    fn write_mirror_line(&self, line: &str) -> io::Result<()> {
+        let mut writer = self.borrow_mut();
+        writeln!(writer, "{}", line)?;
+        writer.flush()
+    }
+}
+
+/// Callback which is told the new size of the terminal after a resize.
+pub(crate) type ResizeCallback<'a> = RefCell<Box<dyn FnMut(u16, u16) + 'a>>;
+
+/// Erases the lifetime of the closure in a
+/// [ResizeCallback](type.ResizeCallback.html) so renderers can borrow it.
+trait ResizeHook {
+    fn resized(&self, rows: u16, columns: u16);
+}
+
+impl ResizeHook for ResizeCallback<'_> {
+    fn resized(&self, rows: u16, columns: u16) {
+        (self.borrow_mut())(rows, columns)
+    }
+}
+
+/// Helper struct to conveniently render a theme ot a term.
+pub(crate) struct TermThemeRenderer<'a> {
+    term: &'a dyn Backend,
+    theme: &'a dyn Theme,
+    overridden_theme: Option<Box<dyn Theme>>,
+    lines: Vec<usize>,
+    prompt_lines: Vec<usize>,
+    prompts_reset_height: bool,
+    size: (u16, u16),
+    on_resize: Option<&'a dyn ResizeHook>,
+    truncate_items: bool,
+    right_to_left: bool,
+    horizontal: bool,
+    step: Option<(usize, usize)>,
+    indent: usize,
+    mirror: Option<&'a dyn Mirror>,
+    report: bool,
+    column: usize,
+    listed_candidates: Option<(usize, usize)>,
+    buffer: Option<String>,
+    shown: Option<(String, Instant)>,
+}
+
+impl<'a> TermThemeRenderer<'a> {
+    pub fn new(term: &'a dyn Backend, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
+        TermThemeRenderer {
+            term,
+            theme,
+            overridden_theme: None,
+            lines: vec![],
+            prompt_lines: vec![],
+            prompts_reset_height: true,
+            size: term_size(term),
+            on_resize: None,
+            truncate_items: false,
+            right_to_left: false,
+            horizontal: false,
+            step: None,
+            indent: 0,
+            mirror: None,
+            report: true,
+            column: 0,
+            listed_candidates: None,
+            buffer: None,
+            shown: None,
+        }
+    }
+
+    /// Creates a renderer which writes into a string instead of `term`.
+    ///
+    /// `term` is only asked for its size.  Cleared lines are removed from
+    /// the end of the buffer, so it holds what the terminal would show.
+    #[cfg(test)]
+    pub fn buffered(term: &'a dyn Backend, theme: &'a dyn Theme) -> TermThemeRenderer<'a> {
+        TermThemeRenderer {
+            buffer: Some(String::new()),
+            ..TermThemeRenderer::new(term, theme)
+        }
+    }
+
+    /// Returns the rendered output of a buffered renderer.
+    #[cfg(test)]
+    pub fn buffer(&self) -> Option<&str> {
+        self.buffer.as_deref()
+    }
+
+    /// Overrides the prompt prefix and suffix of the theme.
+    ///
+    /// This has no effect if both are `None` or the theme does not support
+    /// [with_prompt_affixes](trait.Theme.html#method.with_prompt_affixes).
+    pub fn set_prompt_affixes(
+        &mut self,
+        prefix: Option<&StyledObject<String>>,
+        suffix: Option<&StyledObject<String>>,
+    ) {
+        self.overridden_theme = match (prefix, suffix) {
+            (None, None) => None,
+            _ => self.theme.with_prompt_affixes(prefix, suffix),
+        };
+    }
+
+    /// Replaces the separator between the prompt and its answer.
+    ///
+    /// Call this after [set_prompt_affixes](#method.set_prompt_affixes), as
+    /// the separator is applied on top of the theme with the affixes.
+    pub fn set_prompt_separator(&mut self, separator: Option<&StyledObject<String>>) {
+        if let Some(separator) = separator {
+            if let Some(theme) = self.theme().with_prompt_separator(separator) {
+                self.overridden_theme = Some(theme);
+            }
+        }
+    }
+
+    /// Shows the step of the prompt in a multi step flow.
+    pub fn set_step(&mut self, step: Option<(usize, usize)>) {
+        self.step = step;
+    }
+
+    /// Indents every line by `indent` spaces.
+    pub fn set_indent(&mut self, indent: usize) {
+        self.indent = indent;
+    }
+
+    /// Returns the prompt text with the step badge of the theme.
+    ///
+    /// Every prompt passes its text through here, so this is also where the
+    /// prompt counts as shown.
+    fn step_prompt<'p>(&mut self, prompt: &'p str) -> io::Result<Cow<'p, str>> {
+        if self.shown.is_none() {
+            self.shown = Some((prompt.to_string(), Instant::now()));
+            events::emit(PromptEvent::Shown { prompt });
+        }
+
+        match self.step {
+            Some((current, total)) => {
+                let mut buf = String::new();
+                self.theme()
+                    .format_step_badge(&mut buf, prompt, current, total)
+                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+                Ok(Cow::Owned(buf))
+            }
+            None => Ok(Cow::Borrowed(prompt)),
+        }
+    }
+
+    fn theme(&self) -> &dyn Theme {
+        self.overridden_theme.as_deref().unwrap_or(self.theme)
+    }
+
+    pub fn set_prompts_reset_height(&mut self, val: bool) {
+        self.prompts_reset_height = val;
+    }
+
+    /// Truncates items wider than the terminal with the ellipsis of the theme.
+    pub fn set_truncate_items(&mut self, val: bool) {
+        self.truncate_items = val;
+    }
+
+    /// Mirrors prompt and item lines for right-to-left languages.
+    ///
+    /// The lines are aligned to the right edge of the terminal and the
+    /// prefixes and suffixes of the theme swap sides around the text.
+    pub fn set_right_to_left(&mut self, val: bool) {
+        self.right_to_left = val;
+    }
+
+    pub fn set_horizontal(&mut self, val: bool) {
+        self.horizontal = val;
+    }
+
+    /// Returns whether select items go on a single line, either because it
+    /// was asked for or because the theme prefers it.
+    pub fn horizontal(&self) -> bool {
+        self.horizontal || self.theme().horizontal_items()
+    }
+
+    /// Echoes a plain text copy of every committed line, that is every
+    /// answered prompt and every error, warning and info line, to `mirror`.
+    pub fn set_mirror<'w: 'a>(&mut self, mirror: Option<&'a MirrorWriter<'w>>) {
+        self.mirror = mirror.map(|mirror| mirror as &dyn Mirror);
+    }
+
+    /// Calls `on_resize` with the new size when the prompt is cleared for a
+    /// redraw after the terminal was resized.
+    pub fn set_on_resize<'c: 'a>(&mut self, on_resize: Option<&'a ResizeCallback<'c>>) {
+        self.on_resize = on_resize.map(|on_resize| on_resize as &dyn ResizeHook);
+    }
+
+    /// Shows the answered prompt once a prompt is done.
+    ///
+    /// If disabled, the final line is only echoed to the mirror, so the
+    /// caller can show its own confirmation.
+    pub fn set_report(&mut self, val: bool) {
+        self.report = val;
+    }
+
+    pub fn term(&self) -> &dyn Backend {
+        self.term
+    }
+
+    pub fn add_line(&mut self) {
+        self.lines.push(0);
+    }
+
+    fn write_str(&mut self, s: &str) -> io::Result<()> {
+        match self.buffer {
+            Some(ref mut buffer) => {
+                buffer.push_str(s);
+                Ok(())
+            }
+            None => self.term.write_str(s),
+        }
+    }
+
+    fn write_line(&mut self, s: &str) -> io::Result<()> {
+        match self.buffer {
+            Some(ref mut buffer) => {
+                buffer.push_str(s);
+                buffer.push('\n');
+                Ok(())
+            }
+            None => self.term.write_line(s),
+        }
+    }
+
+    fn clear_last_lines(&mut self, n: usize) -> io::Result<()> {
+        match self.buffer {
+            Some(ref mut buffer) => {
+                clear_last_lines(buffer, n, term_size(self.term).1 as usize);
+                Ok(())
+            }
+            None => self.term.clear_last_lines(n),
+        }
+    }
+
+    /// Returns the width left for a line after the indent.
+    fn line_width(&self) -> usize {
+        (term_size(self.term).1 as usize).saturating_sub(self.indent)
+    }
+
+    /// Prepends the indent to every line of `buf`, keeping carriage returns
+    /// at the start of a line in front of it.
+    fn indent_lines<'b>(&self, buf: &'b str) -> Cow<'b, str> {
+        if self.indent == 0 {
+            return Cow::Borrowed(buf);
+        }
+
+        let indent = " ".repeat(self.indent);
+        let lines: Vec<_> = buf
+            .split('\n')
+            .map(|line| match line.strip_prefix('\r') {
+                Some(line) => format!("\r{}{}", indent, line),
+                None => format!("{}{}", indent, line),
+            })
+            .collect();
+        Cow::Owned(lines.join("\n"))
+    }
+
+    fn write_formatted_str<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        let mut buf = String::new();
+        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let buf = self.indent_lines(&buf);
+        let mut lines: Vec<_> = buf.split('\n').collect();
+        self.column = lines
+            .pop()
+            .map_or(0, |line| measure_text_width(line.trim_start_matches('\r')));
+        self.lines.extend(lines.into_iter().map(measure_text_width));
+        self.write_str(&buf)
+    }
+
+    fn write_formatted_line<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        let mut buf = String::new();
+        f(self, &mut buf).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let buf = self.indent_lines(&buf);
+        self.lines.extend(buf.split('\n').map(measure_text_width));
+        self.write_line(&buf)
+    }
+
+    fn write_formatted_item<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        text: &str,
+        f: F,
+    ) -> io::Result<()> {
+        if !self.truncate_items {
+            return self.write_formatted_mirrored_line(text, f);
+        }
+
+        let width = self.line_width();
+        let mut ellipsis = String::new();
+        self.theme()
+            .format_item_ellipsis(&mut ellipsis)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        self.write_formatted_mirrored_line(text, |this, buf| {
+            let mut item = String::new();
+            f(this, &mut item)?;
+
+            for (idx, line) in item.split('\n').enumerate() {
+                if idx > 0 {
+                    writeln!(buf)?;
+                }
+                if measure_text_width(line) > width {
+                    write!(buf, "{}", truncate_str(line, width, &ellipsis))?;
+                } else {
+                    write!(buf, "{}", line)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Like [write_formatted_item](#method.write_formatted_item) but lays
+    /// the background band of the theme across active items.
+    ///
+    /// Right-to-left prompts are aligned to the right and get no band.
+    fn write_formatted_active_item<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        text: &str,
+        active: bool,
+        f: F,
+    ) -> io::Result<()> {
+        let band = match self.theme().active_item_band_style() {
+            Some(band) if active && !self.right_to_left => band.clone(),
+            _ => return self.write_formatted_item(text, f),
+        };
+        let width = self.line_width();
+
+        self.write_formatted_item(text, |this, buf| {
+            let mut item = String::new();
+            f(this, &mut item)?;
+            write!(buf, "{}", band_line(&item, &band, width))
+        })
+    }
+
+    /// Writes a line which is mirrored around `text` in right-to-left mode.
+    fn write_formatted_mirrored_line<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        text: &str,
+        f: F,
+    ) -> io::Result<()> {
+        if !self.right_to_left {
+            return self.write_formatted_line(f);
+        }
+
+        let width = self.line_width();
+
+        self.write_formatted_line(|this, buf| {
+            let mut line = String::new();
+            f(this, &mut line)?;
+
+            for (idx, line) in line.split('\n').enumerate() {
+                if idx > 0 {
+                    writeln!(buf)?;
+                }
+                write!(buf, "{}", mirror_line(line, text, width))?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Writes a line which stays on the terminal and echoes it to the
+    /// mirror.
+    fn write_committed_line<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        f: F,
+    ) -> io::Result<()> {
+        let mut line = String::new();
+        f(self, &mut line).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        if let Some(mirror) = self.mirror {
+            mirror.write_mirror_line(&strip_ansi_codes(&line))?;
+        }
+
+        self.write_formatted_line(|_, buf| buf.write_str(&line))
+    }
+
+    /// Writes the final line of a prompt and echoes it to the mirror.
+    fn write_committed_prompt<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        prompt: &str,
+        f: F,
+    ) -> io::Result<()> {
+        let mut line = String::new();
+        f(self, &mut line).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        let plain = strip_ansi_codes(&line);
+        if let Some(mirror) = self.mirror {
+            mirror.write_mirror_line(&plain)?;
+        }
+
+        if let Some((ref shown_prompt, shown_at)) = self.shown {
+            events::emit(PromptEvent::Answered {
+                prompt: shown_prompt,
+                line: &plain,
+                elapsed: shown_at.elapsed(),
+            });
+        }
+
+        if !self.report {
+            return Ok(());
+        }
+
+        self.write_formatted_prompt(prompt, |_, buf| buf.write_str(&line))
+    }
+
+    fn write_formatted_prompt<
+        F: FnOnce(&mut TermThemeRenderer, &mut dyn fmt::Write) -> fmt::Result,
+    >(
+        &mut self,
+        prompt: &str,
+        f: F,
+    ) -> io::Result<()> {
+        self.write_formatted_mirrored_line(prompt, f)?;
+        if self.prompts_reset_height {
+            self.prompt_lines = mem::take(&mut self.lines);
+        }
+        Ok(())
+    }
+
+    pub fn error(&mut self, err: &str) -> io::Result<()> {
+        self.write_committed_line(|this, buf| this.theme().format_error(buf, err))
+    }
+
+    pub fn info_line(&mut self, msg: &str) -> io::Result<()> {
+        self.write_committed_line(|this, buf| this.theme().format_info(buf, msg))
+    }
+
+    pub fn warning_line(&mut self, msg: &str) -> io::Result<()> {
+        self.write_committed_line(|this, buf| this.theme().format_warning(buf, msg))
+    }
+
+    pub fn error_line(&mut self, msg: &str) -> io::Result<()> {
+        self.error(msg)
+    }
+
+    pub fn confirm_prompt(&mut self, prompt: &str, default: Option<bool>) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_str(|this, buf| {
+            this.theme().format_confirm_prompt(buf, &prompt, default)
+        })
+    }
+
+    pub fn confirm_prompt_selection(&mut self, prompt: &str, sel: bool) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_committed_prompt(&prompt, |this, buf| {
+            this.theme()
+                .format_confirm_prompt_selection(buf, &prompt, sel)
+        })
+    }
+
+    pub fn input_prompt(&mut self, prompt: &str, default: Option<&str>) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_str(|this, buf| {
+            this.theme().format_input_prompt(buf, &prompt, default)
+        })
+    }
+
+    /// Writes a ghost suggestion after the cursor and moves the cursor
+    /// back, returning the width to pass to
+    /// [clear_ghost_suggestion](#method.clear_ghost_suggestion).
+    pub fn ghost_suggestion(&mut self, suggestion: &str) -> io::Result<usize> {
+        let mut buf = String::new();
+        self.theme()
+            .format_ghost_suggestion(&mut buf, suggestion)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let width = measure_text_width(&buf);
+        self.write_str(&buf)?;
+
+        // The buffer only records the text.
+        if self.buffer.is_none() {
+            self.term.move_cursor_left(width)?;
+        }
+        Ok(width)
+    }
+
+    /// Blanks a ghost suggestion of `width` columns after the cursor.
+    pub fn clear_ghost_suggestion(&mut self, width: usize) -> io::Result<()> {
+        if width == 0 {
+            return Ok(());
+        }
+
+        self.write_str(&" ".repeat(width))?;
+        if self.buffer.is_none() {
+            self.term.move_cursor_left(width)?;
+        }
+        Ok(())
+    }
+
+    /// Lists completion candidates below the input line, wrapped to the
+    /// terminal width.
+    ///
+    /// The cursor has to be at the end of `input`, the text typed after the
+    /// input prompt, and stays there.
+    pub fn completion_candidates(&mut self, candidates: &[String], input: &str) -> io::Result<()> {
+        self.clear_completion_candidates()?;
+
+        let width = (term_size(self.term).1 as usize).max(1);
+        let cell = candidates
+            .iter()
+            .map(|candidate| measure_text_width(candidate) + 2)
+            .max()
+            .unwrap_or(0)
+            .min(width);
+        let lines: Vec<String> = candidates
+            .chunks((width / cell.max(1)).max(1))
+            .map(|chunk| {
+                let line: String = chunk
+                    .iter()
+                    .map(|candidate| {
+                        let candidate = truncate_str(candidate, width, "…");
+                        let padding = cell.saturating_sub(measure_text_width(&candidate));
+                        format!("{}{}", candidate, " ".repeat(padding))
+                    })
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect();
+
+        let column = self.column + measure_text_width(input);
+        self.write_str(&format!("\n{}", lines.join("\n")))?;
+        if self.buffer.is_none() {
+            self.term.move_cursor_up(lines.len())?;
+            self.term.write_str("\r")?;
+            self.term.move_cursor_right(column)?;
+        }
+        self.listed_candidates = Some((lines.len(), column));
+        Ok(())
+    }
+
+    /// Clears the candidates listed by
+    /// [completion_candidates](#method.completion_candidates), if any.
+    pub fn clear_completion_candidates(&mut self) -> io::Result<()> {
+        let (lines, column) = match self.listed_candidates.take() {
+            Some(listed) => listed,
+            None => return Ok(()),
+        };
+
+        match self.buffer {
+            Some(ref mut buffer) => {
+                for _ in 0..lines {
+                    let len = buffer.rfind('\n').unwrap_or(0);
+                    buffer.truncate(len);
+                }
+                Ok(())
+            }
+            None => {
+                self.term.move_cursor_down(1)?;
+                self.term.clear_to_end_of_screen()?;
+                self.term.move_cursor_up(1)?;
+                self.term.write_str("\r")?;
+                self.term.move_cursor_right(column)
+            }
+        }
+    }
+
+    pub fn input_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_committed_prompt(&prompt, |this, buf| {
+            this.theme()
+                .format_input_prompt_selection(buf, &prompt, sel)
+        })
+    }
+
+    pub fn password_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_str(|this, buf| {
+            write!(buf, "\r")?;
+            this.theme().format_password_prompt(buf, &prompt)
+        })
+    }
+
+    pub fn password_prompt_selection(&mut self, prompt: &str) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_committed_prompt(&prompt, |this, buf| {
+            this.theme().format_password_prompt_selection(buf, &prompt)
+        })
+    }
+
+    pub fn select_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_prompt(&prompt, |this, buf| {
+            this.theme().format_select_prompt(buf, &prompt)
+        })
+    }
+
+    pub fn select_prompt_selection(&mut self, prompt: &str, sel: &str) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_committed_prompt(&prompt, |this, buf| {
+            this.theme()
+                .format_select_prompt_selection(buf, &prompt, sel)
+        })
+    }
+
+    pub fn page_indicator(&mut self, page: usize, pages: usize) -> io::Result<()> {
+        self.write_formatted_line(|this, buf| this.theme().format_page_indicator(buf, page, pages))
+    }
+
+    pub fn select_prompt_item(&mut self, text: &str, active: bool) -> io::Result<()> {
+        self.write_formatted_active_item(text, active, |this, buf| {
+            this.theme().format_select_prompt_item(buf, text, active)
+        })
+    }
+
+    pub fn select_prompt_inline(
+        &mut self,
+        prompt: &str,
+        items: &[&str],
+        active: usize,
+    ) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_line(|this, buf| {
+            this.theme()
+                .format_inline_select_prompt(buf, &prompt, items, active)
+        })
+    }
+
+    pub fn multi_select_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_prompt(&prompt, |this, buf| {
+            this.theme().format_multi_select_prompt(buf, &prompt)
+        })
+    }
+
+    pub fn multi_select_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_committed_prompt(&prompt, |this, buf| {
+            this.theme()
+                .format_multi_select_prompt_selection(buf, &prompt, sel)
+        })
+    }
+
+    pub fn multi_select_prompt_item(
+        &mut self,
+        text: &str,
+        checked: bool,
+        active: bool,
+    ) -> io::Result<()> {
+        self.write_formatted_active_item(text, active, |this, buf| {
+            this.theme()
+                .format_multi_select_prompt_item(buf, text, checked, active)
+        })
+    }
+
+    pub fn multi_select_prompt_required_item(
+        &mut self,
+        text: &str,
+        active: bool,
+    ) -> io::Result<()> {
+        self.write_formatted_active_item(text, active, |this, buf| {
+            this.theme()
+                .format_multi_select_prompt_required_item(buf, text, active)
+        })
+    }
+
+    pub fn multi_select_prompt_radio_item(
+        &mut self,
+        text: &str,
+        checked: bool,
+        active: bool,
+    ) -> io::Result<()> {
+        self.write_formatted_active_item(text, active, |this, buf| {
+            this.theme()
+                .format_multi_select_prompt_radio_item(buf, text, checked, active)
+        })
+    }
+
+    pub fn sort_prompt(&mut self, prompt: &str) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_prompt(&prompt, |this, buf| {
+            this.theme().format_sort_prompt(buf, &prompt)
+        })
+    }
+
+    pub fn sort_prompt_selection(&mut self, prompt: &str, sel: &[&str]) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_committed_prompt(&prompt, |this, buf| {
+            this.theme().format_sort_prompt_selection(buf, &prompt, sel)
+        })
+    }
+
+    pub fn sort_prompt_item(&mut self, text: &str, picked: bool, active: bool) -> io::Result<()> {
+        self.write_formatted_active_item(text, active, |this, buf| {
+            this.theme()
+                .format_sort_prompt_item(buf, text, picked, active)
+        })
+    }
+
+    pub fn sort_prompt_pair(&mut self, left: &str, right: &str) -> io::Result<()> {
+        self.write_formatted_item("", |this, buf| {
+            this.theme().format_sort_prompt_pair(buf, left, right)
+        })
+    }
+
+    pub fn sort_prompt_grabbed_item(&mut self, text: &str, symbol: &str) -> io::Result<()> {
+        self.write_formatted_item(text, |this, buf| {
+            this.theme()
+                .format_sort_prompt_grabbed_item(buf, text, symbol)
+        })
+    }
+
+    pub fn sort_prompt_grabbed_item_with_position(
+        &mut self,
+        text: &str,
+        symbol: &str,
+        position: usize,
+        total: usize,
+    ) -> io::Result<()> {
+        self.write_formatted_item(text, |this, buf| {
+            this.theme()
+                .format_sort_prompt_item_position(buf, position, total, true, true)?;
+            write!(buf, " ")?;
+            this.theme()
+                .format_sort_prompt_grabbed_item(buf, text, symbol)
+        })
+    }
+
+    pub fn sort_prompt_item_with_position(
+        &mut self,
+        text: &str,
+        position: usize,
+        total: usize,
+        picked: bool,
+        active: bool,
+    ) -> io::Result<()> {
+        self.write_formatted_active_item(text, active, |this, buf| {
+            this.theme()
+                .format_sort_prompt_item_position(buf, position, total, picked, active)?;
+            write!(buf, " ")?;
+            this.theme()
+                .format_sort_prompt_item(buf, text, picked, active)
+        })
+    }
+
+    pub fn fuzzy_select_prompt(
+        &mut self,
+        prompt: &str,
+        query: &str,
+        cursor: usize,
+    ) -> io::Result<()> {
+        let prompt = self.step_prompt(prompt)?;
+        self.write_formatted_line(|this, buf| {
+            this.theme()
+                .format_fuzzy_select_prompt(buf, &prompt, query, cursor)
+        })
+    }
+
+    pub fn fuzzy_select_prompt_item(
+        &mut self,
+        text: &str,
+        active: bool,
+        matches: &[usize],
+    ) -> io::Result<()> {
+        self.write_formatted_item(text, |this, buf| {
+            this.theme()
+                .format_fuzzy_select_prompt_item(buf, text, active, matches)
+        })
+    }
+
+    pub fn fuzzy_multi_select_prompt_item(
+        &mut self,
+        text: &str,
+        checked: bool,
+        active: bool,
+        matches: &[usize],
+    ) -> io::Result<()> {
+        self.write_formatted_item(text, |this, buf| {
+            this.theme()
+                .format_fuzzy_multi_select_prompt_item(buf, text, checked, active, matches)
+        })
+    }
+
+    pub fn fuzzy_select_prompt_create_item(&mut self, query: &str, active: bool) -> io::Result<()> {
+        self.write_formatted_item(query, |this, buf| {
+            this.theme()
+                .format_fuzzy_select_prompt_create_item(buf, query, active)
+        })
+    }
+
+    /// Runs `f` and shows a spinner with the prompt if it takes a while.
+    ///
+    /// The frames are formatted up front and drawn by a helper thread, as
+    /// themes do not have to be thread safe.  The spinner line is cleared
+    /// once `f` returns.
+    pub fn wait_with_spinner<T, F: FnOnce() -> T>(&mut self, prompt: &str, f: F) -> io::Result<T> {
+        if self.buffer.is_some() {
+            return Ok(f());
+        }
+
+        let frames = self.spinner_frames(prompt)?;
+        let term = self.term;
+        let done = AtomicBool::new(false);
+
+        let rv = thread::scope(|scope| {
+            let spinner = scope.spawn(|| spin(term, &frames, &done));
+            let rv = f();
+            done.store(true, Ordering::Release);
+            spinner.join().map(|res| res.map(|_| rv))
+        });
+
+        match rv {
+            Ok(rv) => rv,
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "spinner thread panicked",
+            )),
+        }
+    }
+
+    /// Async variant of [wait_with_spinner](#method.wait_with_spinner)
+    /// which awaits `future`.
+    #[cfg(feature = "async")]
+    pub async fn wait_with_spinner_async<F: Future>(
+        &mut self,
+        prompt: &str,
+        future: F,
+    ) -> io::Result<F::Output> {
+        if self.buffer.is_some() {
+            return Ok(future.await);
+        }
+
+        let term = match self.term.as_term() {
+            Some(term) => term.clone(),
+            None => return Ok(future.await),
+        };
+        let frames = self.spinner_frames(prompt)?;
+        let done = Arc::new(AtomicBool::new(false));
+        // Stops the spinner even if the future is dropped early.
+        let stop = StopOnDrop(done.clone());
+        let spinner = thread::spawn(move || spin(&term, &frames, &done));
+
+        let rv = future.await;
+        drop(stop);
+
+        match spinner.join() {
+            Ok(res) => res.map(|_| rv),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "spinner thread panicked",
+            )),
+        }
+    }
+
+    /// Formats the frames of the spinner for `prompt`.
+    fn spinner_frames(&mut self, prompt: &str) -> io::Result<Vec<String>> {
+        let prompt = self.step_prompt(prompt)?;
+        let mut frames = Vec::new();
+        for frame in 0..self.theme().wait_spinner_frames().max(1) {
+            let mut buf = String::from("\r");
+            self.theme()
+                .format_wait_spinner(&mut buf, &prompt, frame)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+            frames.push(self.indent_lines(&buf).into_owned());
+        }
+        Ok(frames)
+    }
+
+    /// Returns the number of rows the lines take up on the terminal.
+    ///
+    /// This is worked out from the current width rather than the width at
+    /// the time the lines were written, as terminals rewrap lines when they
+    /// are resized.
+    fn height(&self, lines: &[usize]) -> usize {
+        rows(lines, term_size(self.term).1 as usize)
+    }
+
+    /// Tells the resize callback if the terminal changed its size since the
+    /// last check.
+    ///
+    /// console has no resize events, so this happens when a prompt redraws
+    /// after the next key press.
+    fn check_resize(&mut self) {
+        let size = term_size(self.term);
+        if size != self.size {
+            self.size = size;
+            if let Some(on_resize) = self.on_resize {
+                on_resize.resized(size.0, size.1);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) -> io::Result<()> {
+        self.clear_last_lines(self.height(&self.lines) + self.height(&self.prompt_lines))?;
+        self.lines.clear();
+        self.check_resize();
+        Ok(())
+    }
+
+    pub fn clear_preserve_prompt(&mut self) -> io::Result<()> {
+        self.clear_last_lines(self.height(&self.lines))?;
+        self.lines.clear();
+        self.check_resize();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(rgb(0, 0, 0), Color::Color256(16));
+        assert_eq!(rgb(255, 255, 255), Color::Color256(231));
+        assert_eq!(rgb(255, 0, 0), Color::Color256(196));
+        assert_eq!(rgb(0xff, 0x87, 0x00), Color::Color256(208));
+        assert_eq!(rgb(128, 128, 128), Color::Color256(244));
+    }
+
+    #[test]
+    fn test_is_light_background() {
+        assert!(is_light_background("0;15"));
+        assert!(is_light_background("0;default;7"));
+        assert!(!is_light_background("15;0"));
+        assert!(!is_light_background("default;default"));
+        assert!(!is_light_background(""));
+    }
+
+    #[test]
+    fn test_buffered_render() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+
+        render.select_prompt("Pick one").unwrap();
+        render.select_prompt_item("first", true).unwrap();
+        render.select_prompt_item("second", false).unwrap();
+        assert_eq!(render.buffer(), Some("Pick one:\n> first\n  second\n"));
+
+        render.clear_preserve_prompt().unwrap();
+        assert_eq!(render.buffer(), Some("Pick one:\n"));
+
+        render.select_prompt_item("first", false).unwrap();
+        render.select_prompt_item("second", true).unwrap();
+        render.clear().unwrap();
+        assert_eq!(render.buffer(), Some(""));
+
+        render
+            .select_prompt_selection("Pick one", "second")
+            .unwrap();
+        assert_eq!(render.buffer(), Some("Pick one: second\n"));
+    }
+
+    #[test]
+    fn test_prompt_affixes() {
+        console::set_colors_enabled_stderr(false);
+
+        let term = Term::stderr();
+        let theme = ColorfulTheme::default();
+        let mut render = TermThemeRenderer::buffered(&term, &theme);
+
+        render.set_prompt_affixes(Some(&style(">>".to_string())), None);
+        render.select_prompt("Pick one").unwrap();
+        render.set_prompt_affixes(None, None);
+        render.select_prompt("Pick one").unwrap();
+        assert_eq!(render.buffer(), Some(">> Pick one ›\n? Pick one ›\n"));
+    }
+
+    #[test]
+    fn test_truncate_items() {
+        let term = Term::stderr();
+        let width = term_size(&term).1 as usize;
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        render.set_truncate_items(true);
+
+        render
+            .select_prompt_item(&"漢字".repeat(width), true)
+            .unwrap();
+        render.select_prompt_item("short", false).unwrap();
+
+        let buffer = render.buffer().unwrap();
+        let lines: Vec<_> = buffer.lines().collect();
+        assert!(lines[0].starts_with("> 漢字"));
+        assert!(lines[0].ends_with("..."));
+        assert!(measure_text_width(lines[0]) <= width);
+        assert_eq!(lines[1], "  short");
+    }
+
+    #[test]
+    fn test_right_to_left() {
+        let term = Term::stderr();
+        let width = term_size(&term).1 as usize;
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        render.set_right_to_left(true);
+
+        render.select_prompt("בחר").unwrap();
+        render.multi_select_prompt_item("אחד", true, true).unwrap();
+
+        let buffer = render.buffer().unwrap();
+        let lines: Vec<_> = buffer.lines().collect();
+        assert_eq!(lines[0].trim_start(), ": בחר");
+        assert_eq!(lines[1].trim_start(), "אחד [x] >");
+        assert_eq!(measure_text_width(lines[1]), width);
+    }
+
+    #[test]
+    fn test_mirror_line_keeps_styles() {
+        let line = format!(
+            "{} {} {}",
+            style("?").red().force_styling(true),
+            style("prompt").bold().force_styling(true),
+            style("›").force_styling(true)
+        );
+        assert_eq!(
+            mirror_line(&line, "prompt", 0),
+            "› \x1b[1mprompt\x1b[0m \x1b[31m?\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_success_prefix_for() {
+        console::set_colors_enabled_stderr(false);
+
+        let term = Term::stderr();
+        let theme = ThemeBuilder::new()
+            .success_prefix_for(PromptKind::Confirm, style("!".to_string()))
+            .build();
+        let mut render = TermThemeRenderer::buffered(&term, &*theme);
+
+        render.confirm_prompt_selection("Sure?", true).unwrap();
+        render.select_prompt_selection("Pick", "one").unwrap();
+        assert_eq!(render.buffer(), Some("! Sure? · yes\n✔ Pick · one\n"));
+    }
+
+    #[test]
+    fn test_step_badge() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        render.set_step(Some((2, 5)));
+
+        render.select_prompt("Pick one").unwrap();
+        render.select_prompt_selection("Pick one", "first").unwrap();
+        render.confirm_prompt("", None).unwrap();
+        assert_eq!(
+            render.buffer(),
+            Some("Pick one (step 2/5):\nPick one (step 2/5): first\n(step 2/5) ")
+        );
+    }
+
+    #[test]
+    fn test_no_color() {
+        let mut theme = ColorfulTheme::colored();
+        theme.prompt_prefix = theme.prompt_prefix.force_styling(true);
+        theme.prompt_style = theme.prompt_style.force_styling(true);
+        theme.no_color();
+
+        let mut buf = String::new();
+        theme.format_prompt(&mut buf, "Pick one").unwrap();
+        assert_eq!(buf, "? Pick one ›");
+    }
+
+    #[test]
+    fn test_indent() {
+        let term = Term::stderr();
+        let width = term_size(&term).1 as usize;
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        render.set_indent(2);
+
+        render.select_prompt("Pick one").unwrap();
+        render.select_prompt_item("first\nline", true).unwrap();
+        render.password_prompt("Secret").unwrap();
+        assert_eq!(
+            render.buffer(),
+            Some("  Pick one:\n  > first\n  line\n\r  Secret: ")
+        );
+
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        render.set_indent(4);
+        render.set_truncate_items(true);
+        render
+            .select_prompt_item(&"x".repeat(width), false)
+            .unwrap();
+        assert_eq!(
+            measure_text_width(render.buffer().unwrap().trim_end()),
+            width
+        );
+        render.clear_preserve_prompt().unwrap();
+        assert_eq!(render.buffer(), Some(""));
+    }
+
+    #[test]
+    fn test_status_lines() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+
+        render.info_line("one").unwrap();
+        render.warning_line("two").unwrap();
+        render.error_line("three").unwrap();
+        assert_eq!(
+            render.buffer(),
+            Some("info: one\nwarning: two\nerror: three\n")
+        );
+    }
+
+    #[test]
+    fn test_confirm_prompt_options() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+
+        render.confirm_prompt("Continue?", Some(true)).unwrap();
+        render.confirm_prompt("Continue?", Some(false)).unwrap();
+        render.confirm_prompt("Continue?", None).unwrap();
+        assert_eq!(
+            render.buffer(),
+            Some("Continue? [Y/n] Continue? [y/N] Continue? ")
+        );
+    }
+
+    #[test]
+    fn test_localized_hints() {
+        struct GermanTheme;
+
+        impl Theme for GermanTheme {
+            fn format_confirm_options(&self, f: &mut dyn fmt::Write, default: bool) -> fmt::Result {
+                write!(f, "{}", if default { "[J/n]" } else { "[j/N]" })
+            }
+
+            fn format_default_hint(&self, f: &mut dyn fmt::Write, default: &str) -> fmt::Result {
+                write!(f, "(Standard: {})", default)
+            }
+        }
+
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &GermanTheme);
+
+        render.confirm_prompt("Weiter?", Some(true)).unwrap();
+        render.input_prompt("Name", Some("Anna")).unwrap();
+        assert_eq!(
+            render.buffer(),
+            Some("Weiter? [J/n] Name (Standard: Anna): ")
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        render.set_report(false);
+
+        render.select_prompt("Pick one").unwrap();
+        render.select_prompt_item("one", true).unwrap();
+        render.clear().unwrap();
+        render.select_prompt_selection("Pick one", "one").unwrap();
+        render.confirm_prompt_selection("Continue?", true).unwrap();
+        assert_eq!(render.buffer(), Some(""));
+    }
+
+    #[test]
+    fn test_mirror() {
+        let term = Term::stderr();
+        let mut out = Vec::new();
+
+        {
+            let mirror: MirrorWriter = RefCell::new(Box::new(&mut out));
+            let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+            render.set_mirror(Some(&mirror));
+
+            render.select_prompt("Pick one").unwrap();
+            render.select_prompt_item("one", true).unwrap();
+            render.error("not allowed").unwrap();
+            render.clear().unwrap();
+            render.select_prompt_selection("Pick one", "one").unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "error: not allowed\nPick one: one\n"
+        );
+    }
+
+    #[test]
+    fn test_prompt_separator() {
+        console::set_colors_enabled_stderr(false);
+        let term = Term::stderr();
+        let separator = style(" → ".to_string());
+
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        render.input_prompt_selection("Name", "Ann").unwrap();
+        render.set_prompt_separator(Some(&separator));
+        render.input_prompt_selection("Name", "Ann").unwrap();
+        render.confirm_prompt_selection("Continue?", true).unwrap();
+        assert_eq!(
+            render.buffer(),
+            Some("Name: Ann\nName → Ann\nContinue? → yes\n")
+        );
+
+        let theme = ColorfulTheme::default();
+        let mut render = TermThemeRenderer::buffered(&term, &theme);
+        render.set_prompt_separator(Some(&separator));
+        render.select_prompt_selection("Pick", "one").unwrap();
+        assert_eq!(render.buffer(), Some("✔ Pick → one\n"));
+    }
+
+    #[test]
+    fn test_band_line() {
+        let band = Style::new().on_blue().force_styling(true);
+        assert_eq!(band_line("ab", &band, 5), "\x1b[44mab   \x1b[0m");
+
+        let item = style("x").red().force_styling(true).to_string();
+        assert_eq!(
+            band_line(&item, &band, 3),
+            "\x1b[44m\x1b[31mx\x1b[0m\x1b[44m  \x1b[0m"
+        );
+
+        let plain = Style::new().on_blue().force_styling(false);
+        assert_eq!(band_line("ab", &plain, 4), "ab  ");
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_wait_with_spinner_async() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+
+        let rv = crate::reader::block_on(render.wait_with_spinner_async("Check", async { 42 }));
+        assert_eq!(rv.unwrap(), 42);
+        assert_eq!(render.buffer(), Some(""));
+    }
+
+    #[test]
+    fn test_ghost_suggestion() {
+        let term = Term::stderr();
+        let mut theme = ColorfulTheme::default();
+        theme.no_color();
+        let mut render = TermThemeRenderer::buffered(&term, &theme);
+
+        assert_eq!(render.ghost_suggestion("uild").unwrap(), 4);
+        render.clear_ghost_suggestion(4).unwrap();
+        assert_eq!(render.buffer(), Some("uild    "));
+    }
+
+    #[test]
+    fn test_completion_candidates() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+        let width = term_size(&term).1 as usize;
+
+        render.input_prompt("Command", None).unwrap();
+        render.write_str("b").unwrap();
+        let candidates = vec!["bench".to_string(), "build".to_string()];
+        render.completion_candidates(&candidates, "b").unwrap();
+        assert_eq!(render.buffer(), Some("Command: b\nbench  build"));
+        render.clear_completion_candidates().unwrap();
+        assert_eq!(render.buffer(), Some("Command: b"));
+
+        let candidates: Vec<String> = (0..width / 5 + 1)
+            .map(|idx| format!("{:03}", idx))
+            .collect();
+        render.completion_candidates(&candidates, "b").unwrap();
+        assert_eq!(render.buffer().unwrap().lines().count(), 3);
+    }
+
+    #[test]
+    fn test_inline_select() {
+        let term = Term::stderr();
+        let mut render = TermThemeRenderer::buffered(&term, &InlineTheme);
+        assert!(render.horizontal());
+
+        render
+            .select_prompt_inline("Pick", &["one", "two", "three"], 1)
+            .unwrap();
+        assert_eq!(render.buffer(), Some("Pick: one | [two] | three\n"));
+        render.clear().unwrap();
+        assert_eq!(render.buffer(), Some(""));
+    }
+
+    #[test]
+    fn test_clear_last_lines() {
+        let mut buffer = "a\nb\nc\n".to_string();
+        clear_last_lines(&mut buffer, 0, 80);
+        assert_eq!(buffer, "a\nb\nc\n");
+        clear_last_lines(&mut buffer, 1, 80);
+        assert_eq!(buffer, "a\nb\n");
+
+        buffer.push_str("partial");
+        clear_last_lines(&mut buffer, 1, 80);
+        assert_eq!(buffer, "a\n");
+        clear_last_lines(&mut buffer, 5, 80);
+        assert_eq!(buffer, "");
+
+        let mut buffer = "a\n漢字漢字\nb\n".to_string();
+        clear_last_lines(&mut buffer, 3, 4);
+        assert_eq!(buffer, "a\n");
+    }
+
+    #[test]
+    fn test_line_rows() {
+        assert_eq!(line_rows("", 4), 1);
+        assert_eq!(line_rows("abcd", 4), 1);
+        assert_eq!(line_rows("abcde", 4), 2);
+        assert_eq!(line_rows("漢字漢", 4), 2);
+        assert_eq!(line_rows("😀😀😀", 4), 2);
+        assert_eq!(line_rows("e\u{301}e\u{301}", 2), 1);
+        assert_eq!(
+            line_rows(&style("abcd").red().force_styling(true).to_string(), 4),
+            1
+        );
+    }
+
+    #[test]
+    fn test_rows() {
+        assert_eq!(rows(&[], 4), 0);
+        assert_eq!(rows(&[0, 4, 9], 4), 5);
+        assert_eq!(rows(&[9], 0), 1);
+    }
+
+    #[test]
+    fn test_clear_wide_items() {
+        let term = Term::stderr();
+        let width = term_size(&term).1 as usize;
+        let mut render = TermThemeRenderer::buffered(&term, &SimpleTheme);
+
+        render.select_prompt("Pick one").unwrap();
+        render
+            .select_prompt_item(&"漢".repeat(width), true)
+            .unwrap();
+        render.select_prompt_item("short", false).unwrap();
+        render.clear_preserve_prompt().unwrap();
+        assert_eq!(render.buffer(), Some("Pick one:\n"));
     }
 }
@@ -22,3 +22,37 @@ impl<T, F: Fn(&T) -> Result<(), E>, E: Debug + Display> Validator<T> for F {
         self(input)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{test::TestTerm, Input};
+
+    #[test]
+    fn test_errors_cleared() {
+        let min_len = |s: &String| {
+            if s.len() > 1 {
+                Ok(())
+            } else {
+                Err("too short")
+            }
+        };
+
+        let term = TestTerm::new();
+        term.push_line("x").push_line("ok");
+        let name = Input::<String>::new()
+            .with_prompt("Name")
+            .validate_with(min_len)
+            .interact_text_on(&term)
+            .unwrap();
+        assert_eq!(name, "ok");
+        assert_eq!(term.contents(), "Name: ok");
+
+        term.push_line("x").push_line("ok");
+        Input::<String>::new()
+            .with_prompt("Name")
+            .validate_with(min_len)
+            .interact_on(&term)
+            .unwrap();
+        assert_eq!(term.contents(), "Name: ok\nName: ok");
+    }
+}
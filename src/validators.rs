@@ -0,0 +1,197 @@
+//! Provides ready-made validators for text inputs
+//!
+//! Every function returns a closure which can be passed to
+//! [Input::validate_with](../struct.Input.html#method.validate_with).
+//!
+//! ## Example usage
+//!
+//! ```rust,no_run
+//! # fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! use dialoguer::{validators, Input};
+//!
+//! let mail: String = Input::new()
+//!     .with_prompt("Your email")
+//!     .validate_with(validators::email())
+//!     .interact_text()?;
+//!
+//! let age: u32 = Input::new()
+//!     .with_prompt("Your age")
+//!     .validate_with(validators::integer_range(1..=150))
+//!     .interact_text()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{fmt::Display, ops::RangeInclusive};
+
+/// Rejects values which are empty or only consist of whitespace.
+pub fn non_empty<T: AsRef<str>>() -> impl Fn(&T) -> Result<(), String> {
+    |input: &T| {
+        if input.as_ref().trim().is_empty() {
+            Err("Value must not be empty".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Rejects values which do not look like a mail address.
+///
+/// This only checks for a local part, an `@` and a domain with a dot, as
+/// the full syntax of mail addresses is rarely what users mean.
+pub fn email<T: AsRef<str>>() -> impl Fn(&T) -> Result<(), String> {
+    |input: &T| {
+        let input = input.as_ref();
+        let valid = match input.rsplit_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !input.chars().any(char::is_whitespace)
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+            }
+            None => false,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err("This is not a mail address".to_string())
+        }
+    }
+}
+
+/// Rejects values which are not a URL with a scheme and a host, such as
+/// `https://example.com/path`.
+pub fn url<T: AsRef<str>>() -> impl Fn(&T) -> Result<(), String> {
+    |input: &T| {
+        let input = input.as_ref();
+        let valid = match input.split_once("://") {
+            Some((scheme, rest)) => {
+                scheme
+                    .chars()
+                    .next()
+                    .is_some_and(|chr| chr.is_ascii_alphabetic())
+                    && scheme
+                        .chars()
+                        .all(|chr| chr.is_ascii_alphanumeric() || "+-.".contains(chr))
+                    && !rest.starts_with('/')
+                    && !rest.is_empty()
+                    && !input.chars().any(char::is_whitespace)
+            }
+            None => false,
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err("This is not a URL".to_string())
+        }
+    }
+}
+
+/// Rejects numbers outside of `range`.
+pub fn integer_range<T: PartialOrd + Display>(
+    range: RangeInclusive<T>,
+) -> impl Fn(&T) -> Result<(), String> {
+    move |input: &T| {
+        if range.contains(input) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Value must be between {} and {}",
+                range.start(),
+                range.end()
+            ))
+        }
+    }
+}
+
+/// Rejects values which are not in `values`.
+pub fn one_of<T, I>(values: I) -> impl Fn(&T) -> Result<(), String>
+where
+    T: PartialEq + Display,
+    I: IntoIterator<Item = T>,
+{
+    let values: Vec<T> = values.into_iter().collect();
+
+    move |input: &T| {
+        if values.contains(input) {
+            return Ok(());
+        }
+
+        let names: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+        Err(format!("Value must be one of {}", names.join(", ")))
+    }
+}
+
+/// Rejects values which do not match `regex`.
+///
+/// The pattern is not anchored, use `^` and `$` to match the whole value.
+/// This requires the `regex` feature.
+#[cfg(feature = "regex")]
+pub fn matches_regex<T: AsRef<str>>(regex: regex::Regex) -> impl Fn(&T) -> Result<(), String> {
+    move |input: &T| {
+        if regex.is_match(input.as_ref()) {
+            Ok(())
+        } else {
+            Err(format!("Value must match {}", regex.as_str()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_empty() {
+        assert!(non_empty()(&"a").is_ok());
+        assert!(non_empty()(&" ").is_err());
+    }
+
+    #[test]
+    fn test_email() {
+        let email = email();
+        assert!(email(&"jane@example.com").is_ok());
+        assert!(email(&"jane@localhost").is_err());
+        assert!(email(&"@example.com").is_err());
+        assert!(email(&"jane doe@example.com").is_err());
+        assert!(email(&"jane@example.").is_err());
+    }
+
+    #[test]
+    fn test_url() {
+        let url = url();
+        assert!(url(&"https://example.com/path").is_ok());
+        assert!(url(&"git+ssh://host").is_ok());
+        assert!(url(&"example.com").is_err());
+        assert!(url(&"https://").is_err());
+        assert!(url(&"1http://example.com").is_err());
+    }
+
+    #[test]
+    fn test_integer_range() {
+        let range = integer_range(1..=10);
+        assert!(range(&1).is_ok());
+        assert!(range(&10).is_ok());
+        assert_eq!(range(&11), Err("Value must be between 1 and 10".into()));
+    }
+
+    #[test]
+    fn test_one_of() {
+        let one_of = one_of(vec!["red".to_string(), "green".to_string()]);
+        assert!(one_of(&"red".to_string()).is_ok());
+        assert_eq!(
+            one_of(&"blue".to_string()),
+            Err("Value must be one of red, green".into())
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_matches_regex() {
+        let matches = matches_regex(regex::Regex::new("^[a-z]+$").unwrap());
+        assert!(matches(&"abc").is_ok());
+        assert!(matches(&"ab1").is_err());
+    }
+}